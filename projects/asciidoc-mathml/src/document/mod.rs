@@ -0,0 +1,240 @@
+use mathml_core::MathRoot;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// Inline macros, the name is followed by `:[`.
+const INLINE_MACROS: &[&str] = &["stem", "latexmath", "asciimath"];
+
+/// Delimiters of blocks whose content is never math.
+const VERBATIM_DELIMITERS: &[char] = &['-', '.', '/', '+'];
+
+/// The notation of a formula, from the macro name or the `:stem:` attribute.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StemNotation {
+    /// `asciimath`, the notation of `stem` when `:stem:` has no value or is not set.
+    #[default]
+    AsciiMath,
+    /// `latexmath`
+    LaTeX,
+}
+
+/// Renders the stem macros and blocks of AsciiDoc documents, all formulas share the engines.
+#[derive(Debug)]
+pub struct AsciiDocMath {
+    latex: mathml_latex::LaTeXEngine,
+    asciimath: mathml_asciimath::LaTeXEngine,
+}
+
+/// A formula of the document which failed to render, kept as written.
+#[derive(Clone, Debug)]
+pub struct AsciiDocError {
+    /// The line of the formula, starting from 1.
+    pub line: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+/// Render the math of an AsciiDoc document, returns the document and the formulas which failed.
+///
+/// ```
+/// # use asciidoc_mathml::rewrite_asciidoc;
+/// let (document, errors) = rewrite_asciidoc(":stem: latexmath\n\nSolve stem:[a + b].\n");
+/// assert_eq!(document, ":stem: latexmath\n\nSolve pass:[<math display=\"inline\"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math>].\n");
+/// assert!(errors.is_empty());
+/// ```
+pub fn rewrite_asciidoc(document: &str) -> (String, Vec<AsciiDocError>) {
+    AsciiDocMath::default().rewrite(document)
+}
+
+impl Default for AsciiDocMath {
+    fn default() -> Self {
+        Self { latex: mathml_latex::LaTeXEngine::builtin(), asciimath: mathml_asciimath::LaTeXEngine::builtin() }
+    }
+}
+
+impl StemNotation {
+    /// The notation named by a macro, a block style or the value of `:stem:`, `stem` is the given default.
+    pub fn from_name(name: &str, stem: StemNotation) -> Option<Self> {
+        match name {
+            "stem" => Some(stem),
+            "latexmath" | "tex" => Some(Self::LaTeX),
+            "asciimath" | "ascii" => Some(Self::AsciiMath),
+            _ => None,
+        }
+    }
+}
+
+impl AsciiDocMath {
+    /// Render the math of the document, returns the document and the formulas which failed.
+    pub fn rewrite(&self, document: &str) -> (String, Vec<AsciiDocError>) {
+        let mut output = String::with_capacity(document.len());
+        let mut errors = vec![];
+        let mut stem = StemNotation::default();
+        let mut verbatim: Option<&str> = None;
+        let mut lines = document.split_inclusive('\n').enumerate().peekable();
+        while let Some((index, line)) = lines.next() {
+            let trimmed = line.trim_end();
+            if let Some(delimiter) = verbatim {
+                output.push_str(line);
+                if trimmed == delimiter {
+                    verbatim = None;
+                }
+                continue;
+            }
+            if is_verbatim_delimiter(trimmed) {
+                verbatim = Some(trimmed);
+                output.push_str(line);
+                continue;
+            }
+            // line comments
+            if trimmed.starts_with("//") {
+                output.push_str(line);
+                continue;
+            }
+            if let Some(value) = trimmed.strip_prefix(":stem:") {
+                stem = match value.trim() {
+                    "" => StemNotation::AsciiMath,
+                    value => StemNotation::from_name(value, stem).unwrap_or_else(|| {
+                        errors.push(AsciiDocError { line: index + 1, message: format!("unknown stem notation `{}`", value) });
+                        stem
+                    }),
+                };
+                output.push_str(line);
+                continue;
+            }
+            if let Some(notation) = block_style(trimmed).and_then(|name| StemNotation::from_name(name, stem)) {
+                // `++++` delimited block, or the following paragraph
+                let mut block = vec![line];
+                let mut content = String::new();
+                match lines.next_if(|(_, next)| is_passthrough_delimiter(next.trim_end())) {
+                    Some((_, open)) => {
+                        block.push(open);
+                        for (_, line) in lines.by_ref() {
+                            block.push(line);
+                            if line.trim_end() == open.trim_end() {
+                                break;
+                            }
+                            content.push_str(line);
+                        }
+                    }
+                    None => {
+                        while let Some((_, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+                            block.push(line);
+                            content.push_str(line);
+                        }
+                    }
+                }
+                match self.render(notation, content.trim(), true) {
+                    Ok(mathml) => {
+                        output.push_str("++++\n");
+                        output.push_str(&mathml);
+                        output.push_str("\n++++\n");
+                    }
+                    Err(message) => {
+                        errors.push(AsciiDocError { line: index + 1, message });
+                        block.iter().for_each(|line| output.push_str(line));
+                    }
+                }
+                continue;
+            }
+            output.push_str(&self.rewrite_inline(line, index + 1, stem, &mut errors));
+        }
+        (output, errors)
+    }
+    /// `stem:[..]` and similar macros, `]` is escaped as `\]` in the formula.
+    fn rewrite_inline(&self, line: &str, number: usize, stem: StemNotation, errors: &mut Vec<AsciiDocError>) -> String {
+        let mut output = String::with_capacity(line.len());
+        let mut last = 0;
+        let mut search = 0;
+        while let Some(found) = line[search..].find(":[") {
+            let colon = search + found;
+            search = colon + 2;
+            let name_start = line[..colon].rfind(|c: char| !c.is_ascii_alphabetic()).map_or(0, |i| i + 1);
+            let name = &line[name_start..colon];
+            // `\stem:[..]` is an escaped macro
+            if !INLINE_MACROS.contains(&name) || line[..name_start].ends_with(|c: char| c == '\\' || c.is_alphanumeric()) {
+                continue;
+            }
+            let close = match find_unescaped(&line[search..], ']') {
+                Some(close) => search + close,
+                None => break,
+            };
+            let source = line[search..close].replace("\\]", "]");
+            let notation = StemNotation::from_name(name, stem).unwrap_or_default();
+            match self.render(notation, &source, false) {
+                Ok(mathml) => {
+                    output.push_str(&line[last..name_start]);
+                    output.push_str("pass:[");
+                    output.push_str(&mathml.replace(']', "\\]"));
+                    output.push(']');
+                    last = close + 1;
+                }
+                Err(message) => errors.push(AsciiDocError { line: number, message }),
+            }
+            search = close + 1;
+        }
+        output.push_str(&line[last..]);
+        output
+    }
+    fn render(&self, notation: StemNotation, source: &str, display: bool) -> Result<String, String> {
+        let rendered = match notation {
+            StemNotation::LaTeX => self.latex.render(strip_delimiters(source)).map_err(|e| e.to_string()),
+            StemNotation::AsciiMath => {
+                mathml_asciimath::parse_latex(source).map(|node| node.as_mathml(&self.asciimath)).map_err(|e| e.to_string())
+            }
+        };
+        match rendered {
+            Ok(node) => Ok(MathRoot::new(vec![node]).with_display_style(display).to_string()),
+            Err(e) => Err(format!("invalid formula `{}`, {}", source, e)),
+        }
+    }
+}
+
+/// `----`, `....`, `////` and `++++` blocks, at least four characters.
+fn is_verbatim_delimiter(line: &str) -> bool {
+    line.len() >= 4 && VERBATIM_DELIMITERS.iter().any(|c| line.chars().all(|x| x == *c))
+}
+
+fn is_passthrough_delimiter(line: &str) -> bool {
+    line.len() >= 4 && line.chars().all(|c| c == '+')
+}
+
+/// The first positional attribute of a block attribute line, such as `stem` in `[stem,id=area]`.
+fn block_style(line: &str) -> Option<&str> {
+    let attributes = line.strip_prefix('[')?.strip_suffix(']')?;
+    attributes.split(',').next().map(str::trim)
+}
+
+/// LaTeX formulas may keep their delimiters, as in `latexmath:[$a + b$]`.
+fn strip_delimiters(source: &str) -> &str {
+    let pairs = [("\\[", "\\]"), ("\\(", "\\)"), ("$$", "$$"), ("$", "$")];
+    for (open, close) in pairs {
+        if let Some(inner) = source.strip_prefix(open).and_then(|s| s.strip_suffix(close)) {
+            return inner.trim();
+        }
+    }
+    source
+}
+
+fn find_unescaped(text: &str, target: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == target => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+impl Error for AsciiDocError {}
+
+impl Display for AsciiDocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
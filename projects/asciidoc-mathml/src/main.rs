@@ -0,0 +1,53 @@
+use asciidoc_mathml::rewrite_asciidoc;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: asciidoc-mathml <document.adoc> [-o <output>]";
+
+fn main() -> ExitCode {
+    let mut input = None;
+    let mut output = None;
+    let mut arguments = std::env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "-o" | "--output" => output = arguments.next(),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ if input.is_none() => input = Some(argument),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let input = match input {
+        Some(input) => input,
+        None => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let document = match std::fs::read_to_string(&input) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("asciidoc-mathml: {}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (document, errors) = rewrite_asciidoc(&document);
+    for error in errors {
+        eprintln!("asciidoc-mathml: {}: {}", input, error);
+    }
+    let written = match output {
+        Some(path) => std::fs::write(path, document),
+        None => std::io::Write::write_all(&mut std::io::stdout(), document.as_bytes()),
+    };
+    match written {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("asciidoc-mathml: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use asciidoc_mathml::rewrite_asciidoc;
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+#[test]
+fn rewrite() {
+    let document = r"= Handbook
+:stem:
+
+Default stem:[a + b], latexmath:[\(x - y\)] and escaped \stem:[c].
+
+[stem]
+++++
+c
+++++
+
+----
+stem:[a]
+----
+
+:stem: latexmath
+[stem,id=broken]
+a + {b
+
+// stem:[d]
+asciimath:[a + b] and stem:[\frac{p}{q}]
+";
+    let (output, errors) = rewrite_asciidoc(document);
+    let inline = |notation: &str| format!("pass:[<math display=\"inline\">{}</math>]", notation);
+    let sum = "<mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow>";
+    assert!(output.contains(&format!(
+        "Default {}, {} and escaped \\stem:[c].",
+        inline(sum),
+        inline("<mrow><mi>x</mi><mo>−</mo><mi>y</mi></mrow>")
+    )));
+    assert!(output.contains("++++\n<math display=\"block\"><mi>c</mi></math>\n++++\n"));
+    assert!(output.contains("----\nstem:[a]\n----\n"));
+    assert!(output.contains("[stem,id=broken]\na + {b\n\n// stem:[d]\n"));
+    assert!(output.ends_with(&format!("{} and {}\n", inline(sum), inline("<mfrac><mi>p</mi><mi>q</mi></mfrac>"))));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 16);
+}
@@ -0,0 +1,63 @@
+use jupyter_mathml::Notebook;
+use mathml_latex::LaTeXEngine;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: jupyter-mathml <notebook.ipynb> [--html] [-o <output>]";
+
+fn main() -> ExitCode {
+    let mut input = None;
+    let mut output = None;
+    let mut html = false;
+    let mut arguments = std::env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--html" => html = true,
+            "-o" | "--output" => output = arguments.next(),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ if input.is_none() => input = Some(argument),
+            _ => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let input = match input {
+        Some(input) => input,
+        None => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut notebook = match std::fs::read_to_string(&input)
+        .map_err(|e| e.to_string())
+        .and_then(|text| Notebook::parse(&text).map_err(|e| e.to_string()))
+    {
+        Ok(notebook) => notebook,
+        Err(e) => {
+            eprintln!("jupyter-mathml: {}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let context = LaTeXEngine::builtin();
+    for error in notebook.render_math(&context) {
+        eprintln!("jupyter-mathml: {}: {}", input, error);
+    }
+    let text = match html {
+        true => notebook.to_html(&context),
+        false => notebook.to_string(),
+    };
+    let written = match output {
+        Some(path) => std::fs::write(path, text),
+        None => std::io::Write::write_all(&mut std::io::stdout(), text.as_bytes()),
+    };
+    match written {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("jupyter-mathml: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use super::*;
+use mathml_latex::MarkdownMath;
+use pulldown_cmark::{html::push_html, Options, Parser};
+use std::fmt::Write;
+
+/// Outputs shown by the page, the richest first.
+const MIME_PRIORITY: &[&str] =
+    &["text/html", "image/svg+xml", "image/png", "image/jpeg", "text/latex", "text/markdown", "text/plain"];
+
+impl Notebook {
+    /// Export a static html page, the math of Markdown cells and `text/latex` outputs is rendered to MathML.
+    pub fn to_html(&self, context: &LaTeXEngine) -> String {
+        let mut html = String::new();
+        let title = self.json["metadata"]["title"].as_str().unwrap_or("Notebook");
+        let language = match &self.json["metadata"]["language_info"]["name"] {
+            Value::String(name) => name.as_str(),
+            _ => self.json["metadata"]["kernelspec"]["language"].as_str().unwrap_or("python"),
+        };
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        writeln!(html, "<title>{}</title>", escape_html(title)).ok();
+        html.push_str("</head>\n<body>\n");
+        for cell in self.json["cells"].as_array().into_iter().flatten() {
+            match cell["cell_type"].as_str() {
+                Some("markdown") => {
+                    html.push_str("<div class=\"cell markdown\">\n");
+                    push_markdown(&mut html, &join_text(&cell["source"]), context);
+                    html.push_str("</div>\n");
+                }
+                Some("code") => {
+                    html.push_str("<div class=\"cell code\">\n");
+                    let source = escape_html(&join_text(&cell["source"]));
+                    writeln!(html, "<pre><code class=\"language-{}\">{}</code></pre>", language, source).ok();
+                    for output in cell["outputs"].as_array().into_iter().flatten() {
+                        push_output(&mut html, output, context);
+                    }
+                    html.push_str("</div>\n");
+                }
+                // raw cells are meant for other formats
+                _ => {}
+            }
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn push_markdown(html: &mut String, markdown: &str, context: &LaTeXEngine) {
+    let options = Options::ENABLE_MATH | Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    push_html(html, MarkdownMath::new(Parser::new_ext(markdown, options), context));
+}
+
+fn push_output(html: &mut String, output: &Value, context: &LaTeXEngine) {
+    match output["output_type"].as_str() {
+        Some("stream") => {
+            writeln!(html, "<pre class=\"output stream\">{}</pre>", escape_html(&join_text(&output["text"]))).ok();
+        }
+        Some("error") => {
+            let error =
+                format!("{}: {}", output["ename"].as_str().unwrap_or_default(), output["evalue"].as_str().unwrap_or_default());
+            writeln!(html, "<pre class=\"output error\">{}</pre>", escape_html(&error)).ok();
+        }
+        Some("execute_result" | "display_data") => {
+            let data = &output["data"];
+            let mime = match MIME_PRIORITY.iter().find(|mime| !data[**mime].is_null()) {
+                Some(mime) => *mime,
+                None => return,
+            };
+            let text = join_text(&data[mime]);
+            html.push_str("<div class=\"output\">\n");
+            match mime {
+                "text/html" | "image/svg+xml" => html.push_str(&text),
+                "image/png" | "image/jpeg" => {
+                    let base64: String = text.split_whitespace().collect();
+                    write!(html, "<img src=\"data:{};base64,{}\">", mime, base64).ok();
+                }
+                "text/latex" => match render_latex_output(&text, context) {
+                    Ok(mathml) => html.push_str(&mathml),
+                    Err(_) => {
+                        write!(html, "<pre>{}</pre>", escape_html(&text)).ok();
+                    }
+                },
+                "text/markdown" => push_markdown(html, &text, context),
+                _ => {
+                    write!(html, "<pre>{}</pre>", escape_html(&text)).ok();
+                }
+            }
+            html.push_str("\n</div>\n");
+        }
+        _ => {}
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,135 @@
+use mathml_latex::{find_formulas, LaTeXEngine};
+use serde::Serialize;
+use serde_json::{ser::PrettyFormatter, Serializer, Value};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+mod html;
+
+/// A Jupyter notebook in the nbformat 4 json format, fields which are not about math are kept as they are.
+#[derive(Clone, Debug)]
+pub struct Notebook {
+    json: Value,
+}
+
+/// A formula of the notebook which failed to render, kept as written.
+#[derive(Clone, Debug)]
+pub struct NotebookError {
+    /// The index of the cell, starting from 0.
+    pub cell: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl Notebook {
+    /// Parse the content of an `.ipynb` file.
+    pub fn parse(text: &str) -> Result<Self, serde_json::Error> {
+        Ok(Self { json: serde_json::from_str(text)? })
+    }
+    /// The json of the notebook.
+    pub fn as_json(&self) -> &Value {
+        &self.json
+    }
+    /// Render the math of Markdown cells in place, and add a `text/html` version to the `text/latex` outputs.
+    ///
+    /// The source of code cells is never touched, outputs which already have a `text/html` version are kept.
+    pub fn render_math(&mut self, context: &LaTeXEngine) -> Vec<NotebookError> {
+        let mut errors = vec![];
+        let cells = match self.json["cells"].as_array_mut() {
+            Some(cells) => cells,
+            None => return errors,
+        };
+        for (index, cell) in cells.iter_mut().enumerate() {
+            let mut report = |messages: Vec<String>| {
+                errors.extend(messages.into_iter().map(|message| NotebookError { cell: index, message }))
+            };
+            match cell["cell_type"].as_str() {
+                Some("markdown") => {
+                    let (source, failed) = render_formulas(&join_text(&cell["source"]), context);
+                    cell["source"] = split_lines(&source);
+                    report(failed);
+                }
+                Some("code") => {
+                    for output in cell["outputs"].as_array_mut().into_iter().flatten() {
+                        let data = &mut output["data"];
+                        if data["text/latex"].is_null() || !data["text/html"].is_null() {
+                            continue;
+                        }
+                        match render_latex_output(&join_text(&data["text/latex"]), context) {
+                            Ok(html) => data["text/html"] = split_lines(&html),
+                            Err(failed) => report(failed),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        errors
+    }
+}
+
+/// Replace the formulas of a text with MathML, returns the messages of the formulas which failed.
+fn render_formulas(text: &str, context: &LaTeXEngine) -> (String, Vec<String>) {
+    let mut output = String::with_capacity(text.len());
+    let mut errors = vec![];
+    let mut last = 0;
+    for formula in find_formulas(text) {
+        output.push_str(&text[last..formula.range.start]);
+        match formula.as_mathml(context) {
+            Ok(mathml) => output.push_str(&mathml.to_string()),
+            Err(e) => {
+                errors.push(e.to_string());
+                output.push_str(&text[formula.range.clone()]);
+            }
+        }
+        last = formula.range.end;
+    }
+    output.push_str(&text[last..]);
+    (output, errors)
+}
+
+/// `text/latex` outputs are usually delimited, such as `$\displaystyle x$`, otherwise the whole output is one formula.
+fn render_latex_output(latex: &str, context: &LaTeXEngine) -> Result<String, Vec<String>> {
+    let (html, errors) = match find_formulas(latex).is_empty() {
+        true => render_formulas(&format!("$${}$$", latex.trim()), context),
+        false => render_formulas(latex, context),
+    };
+    match errors.is_empty() {
+        true => Ok(html),
+        false => Err(errors),
+    }
+}
+
+/// Multiline strings are either a string or a list of lines.
+fn join_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Jupyter writes multiline strings as a list of lines, with their line breaks.
+fn split_lines(text: &str) -> Value {
+    Value::Array(text.split_inclusive('\n').map(|line| Value::String(line.to_string())).collect())
+}
+
+impl Display for Notebook {
+    /// The json of the notebook, indented with one space as Jupyter does.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut buffer = Vec::new();
+        let mut serializer = Serializer::with_formatter(&mut buffer, PrettyFormatter::with_indent(b" "));
+        self.json.serialize(&mut serializer).map_err(|_| std::fmt::Error)?;
+        writeln!(f, "{}", String::from_utf8_lossy(&buffer))
+    }
+}
+
+impl Error for NotebookError {}
+
+impl Display for NotebookError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cell {}: {}", self.cell, self.message)
+    }
+}
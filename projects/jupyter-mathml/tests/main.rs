@@ -0,0 +1,53 @@
+use jupyter_mathml::Notebook;
+use mathml_latex::LaTeXEngine;
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+const NOTEBOOK: &str = r##"{
+ "cells": [
+  {"cell_type": "markdown", "metadata": {}, "source": ["# Sum\n", "Let $a + b$ be\n", "$$a + {b$$"]},
+  {"cell_type": "code", "execution_count": 1, "metadata": {}, "source": ["x = '$a$'"], "outputs": [
+   {"output_type": "execute_result", "execution_count": 1, "metadata": {}, "data": {"text/latex": ["$x = 1$"], "text/plain": ["x = 1"]}},
+   {"output_type": "display_data", "metadata": {}, "data": {"text/latex": "y"}},
+   {"output_type": "stream", "name": "stdout", "text": ["a < b\n"]}
+  ]}
+ ],
+ "metadata": {"language_info": {"name": "python"}},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}"##;
+
+#[test]
+fn render_math() {
+    let context = LaTeXEngine::builtin();
+    let mut notebook = Notebook::parse(NOTEBOOK).unwrap();
+    let errors = notebook.render_math(&context);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].cell, 0);
+    let cells = &notebook.as_json()["cells"];
+    assert_eq!(
+        cells[0]["source"][1],
+        r#"Let <math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math> be"#.to_string() + "\n"
+    );
+    assert_eq!(cells[0]["source"][2], "$$a + {b$$");
+    assert_eq!(cells[1]["source"][0], "x = '$a$'");
+    let outputs = &cells[1]["outputs"];
+    assert_eq!(
+        outputs[0]["data"]["text/html"][0],
+        r#"<math display="inline"><mrow><mi>x</mi><mo>=</mo><mn>1</mn></mrow></math>"#
+    );
+    assert_eq!(outputs[1]["data"]["text/html"][0], r#"<math display="block"><mi>y</mi></math>"#);
+    assert!(notebook.to_string().starts_with("{\n \"cells\": [\n  {\n"));
+}
+
+#[test]
+fn to_html() {
+    let html = Notebook::parse(NOTEBOOK).unwrap().to_html(&LaTeXEngine::builtin());
+    assert!(html.contains(r#"<p>Let <math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math> be"#));
+    assert!(html.contains(r#"<pre><code class="language-python">x = '$a$'</code></pre>"#));
+    assert!(html.contains(r#"<math display="inline"><mrow><mi>x</mi><mo>=</mo><mn>1</mn></mrow></math>"#));
+    assert!(html.contains(r#"<pre class="output stream">a &lt; b"#));
+}
@@ -12,9 +12,7 @@ impl<'i> AsciiNode<'i> {
             AsciiNode::Row { children } => MathRow::new(children.iter().map(|node| node.as_mathml(context))).into(),
             AsciiNode::Block(block) => block.as_mathml(context),
             AsciiNode::Command(cmd) => cmd.as_mathml(context),
-            AsciiNode::Text { .. } => {
-                todo!()
-            }
+            AsciiNode::Text { text } => MathML::text(text),
             AsciiNode::Number { number } => MathML::Number(Box::new(MathNumber::new(number))),
 
             AsciiNode::Letter { identifier } => MathIdentifier::italic(identifier).into(),
@@ -39,7 +37,7 @@ impl<'i> AsciiBlock<'i> {
             "Vmatrix" => Vmatrix(stream),
             "vmatrix" => vmatrix(stream),
             "cases" => cases(stream),
-            name => todo!("unknown block: {}", name),
+            name => MathML::error(MathML::text(format!("Unknown environment `{}`", name))),
         }
     }
 }
@@ -48,13 +46,10 @@ impl<'i> LaTeXCommand<'i> {
     pub fn as_mathml(&self, context: &LaTeXEngine) -> MathML {
         match self.name {
             kind @ ("frac" | "dfrac") => match self.children.as_slice() {
-                [] => panic!("frac command must have at least two arguments"),
-                [numerator] => panic!("frac command must have at least two arguments"),
                 [numerator, denominator, rest @ ..] => {
                     let term = match kind {
-                        "frac" => frac(numerator.as_mathml(context), denominator.as_mathml(context)),
                         "dfrac" => dfrac(numerator.as_mathml(context), denominator.as_mathml(context)),
-                        _ => unreachable!(),
+                        _ => frac(numerator.as_mathml(context), denominator.as_mathml(context)),
                     };
                     if rest.len() == 0 {
                         return term;
@@ -63,6 +58,7 @@ impl<'i> LaTeXCommand<'i> {
                     terms.mut_items().extend(rest.iter().map(|node| node.as_mathml(context)));
                     return terms.into();
                 }
+                _ => return self.missing_argument(),
             },
             _ => {}
         }
@@ -71,12 +67,12 @@ impl<'i> LaTeXCommand<'i> {
                 [numerator, denominator] => {
                     return binom(numerator.as_mathml(context), denominator.as_mathml(context));
                 }
-                _ => panic!("binom command must have exactly two arguments"),
+                _ => return self.missing_argument(),
             }
         }
         if self.name.eq("operatorname") {
             match self.children.as_slice() {
-                [] => panic!("operatorname command must have exactly one argument"),
+                [] => return self.missing_argument(),
                 [head, rest @ ..] => {
                     return MathFunction::new(head.as_identifier(), rest.iter().map(|node| node.as_mathml(context))).into();
                 }
@@ -94,7 +90,10 @@ impl<'i> LaTeXCommand<'i> {
         if let Some(s) = context.get_space(&self.name) {
             return MathSpace::new(s).into();
         }
-
-        todo!()
+        tracing::warn!("Unknown command `\\{}`", self.name);
+        MathML::error(MathML::text(format!("\\{}", self.name)))
+    }
+    fn missing_argument(&self) -> MathML {
+        MathML::error(MathML::text(format!("Missing argument for `\\{}`", self.name)))
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 mod builtin;
 
+#[derive(Clone, Debug)]
 pub struct LaTeXEngine {
     functions: BTreeMap<String, String>,
     operators: BTreeMap<String, String>,
@@ -8,8 +8,7 @@ impl<'i> AsciiNode<'i> {
     pub(super) fn parse_super_script(input: ParseState<'i>) -> ParseResult<AsciiNode<'i>> {
         let (state, lhs) = input.match_fn(Self::parse_atomic)?;
         if lhs.is_super_script() {
-            // StopBecause::ShouldNotBe("^", state.start_offset)
-            todo!()
+            Err(StopBecause::ShouldNotBe { message: "^", position: state.start_offset })?;
         }
         let (state, _) = state.skip(whitespace).match_str("^", false)?;
         let (state, rhs) = state.skip(whitespace).match_fn(Self::parse_atomic)?;
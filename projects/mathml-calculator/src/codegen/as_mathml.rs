@@ -0,0 +1,139 @@
+use super::*;
+use crate::definitions::{ADDITIVE, COMPARISON};
+use mathml_core::{
+    MathFenced, MathFunction, MathIdentifier, MathML, MathMultiScript, MathNumber, MathOperator, MathRow, MathSqrt,
+};
+
+impl<'i> CalculatorNode<'i> {
+    /// Converts the expression into a MathML node, with only the parentheses required by precedence.
+    pub fn as_mathml(&self) -> MathML {
+        match self {
+            CalculatorNode::Number { number, exponent: None } => MathNumber::new(number).into(),
+            CalculatorNode::Number { number, exponent: Some(exponent) } => {
+                let exponent = match exponent.strip_prefix('-') {
+                    Some(digits) => MathRow::new(vec![MathOperator::new("−").into(), MathNumber::new(digits).into()]).into(),
+                    None => MathNumber::new(exponent.trim_start_matches('+')).into(),
+                };
+                let power = MathMultiScript::super_script(MathNumber::new("10").into(), exponent);
+                MathRow::new(vec![MathNumber::new(number).into(), MathOperator::new("×").into(), power.into()]).into()
+            }
+            CalculatorNode::Identifier { name } => identifier(name),
+            CalculatorNode::Prefix { operator, operand } => {
+                let sign = if *operator == "-" { "−" } else { "+" };
+                let mut items = vec![MathOperator::new(sign).into()];
+                operand.push_operand(PREFIX, &mut items);
+                MathRow::new(items).into()
+            }
+            CalculatorNode::Factorial { operand } => {
+                let mut items = vec![];
+                operand.push_operand(POSTFIX, &mut items);
+                items.push(MathOperator::new("!").into());
+                MathRow::new(items).into()
+            }
+            CalculatorNode::Binary { operator, lhs, rhs } => binary(*operator, lhs, rhs),
+            CalculatorNode::Call { function, arguments } => call(function, arguments),
+        }
+    }
+    /// The node, in parentheses if it binds looser than `min`.
+    fn operand(&self, min: u8) -> MathML {
+        match self.precedence() < min {
+            true => MathFenced::parentheses(vec![self.as_mathml()]).into(),
+            false => self.as_mathml(),
+        }
+    }
+    /// Push the operand into a row, inner rows are flattened.
+    fn push_operand(&self, min: u8, items: &mut Vec<MathML>) {
+        match self.operand(min) {
+            MathML::Row(row) => items.extend(row.get_items().iter().cloned()),
+            node => items.push(node),
+        }
+    }
+}
+
+fn identifier(name: &str) -> MathML {
+    let text = |name| match identifier_text(name) {
+        (text, true) => MathIdentifier::normal(text),
+        (text, false) => MathIdentifier::italic(text),
+    };
+    match name.split_once('_') {
+        // `x_1`, `a_ij`
+        Some((base, index)) if !base.is_empty() && !index.is_empty() => {
+            let index = match index.chars().all(|c| c.is_ascii_digit()) {
+                true => MathNumber::new(index).into(),
+                false => text(index).into(),
+            };
+            MathMultiScript::sub_script(text(base).into(), index).into()
+        }
+        _ => text(name).into(),
+    }
+}
+
+fn binary(operator: CalculatorOperator, lhs: &CalculatorNode, rhs: &CalculatorNode) -> MathML {
+    let (symbol, left, right) = match operator {
+        CalculatorOperator::Divide => return MathML::fraction(lhs.as_mathml(), rhs.as_mathml()),
+        CalculatorOperator::FloorDivide => {
+            return MathFenced::new(vec![MathML::fraction(lhs.as_mathml(), rhs.as_mathml())], '⌊', '⌋').into();
+        }
+        CalculatorOperator::Power => {
+            let base = match lhs.is_script_base() {
+                true => lhs.as_mathml(),
+                false => MathFenced::parentheses(vec![lhs.as_mathml()]).into(),
+            };
+            return MathMultiScript::super_script(base, rhs.as_mathml()).into();
+        }
+        // `sin x`
+        CalculatorOperator::ImplicitTimes if lhs.as_bare_function().is_some() => {
+            let name = lhs.as_bare_function().unwrap_or_default();
+            return MathFunction::new(name, vec![rhs.operand(MULTIPLICATIVE + 1)]).into();
+        }
+        CalculatorOperator::Times | CalculatorOperator::ImplicitTimes => {
+            // `a b c` needs no parentheses, unlike `a (b / c)`
+            let associative = matches!(
+                rhs,
+                CalculatorNode::Binary { operator: CalculatorOperator::Times | CalculatorOperator::ImplicitTimes, .. }
+            );
+            let right = if associative { MULTIPLICATIVE } else { MULTIPLICATIVE + 1 };
+            let visible = rhs.precedence() >= right && rhs.starts_with_digit();
+            (if visible { "×" } else { "\u{2062}" }, MULTIPLICATIVE, right)
+        }
+        CalculatorOperator::Modulo => ("mod", MULTIPLICATIVE, MULTIPLICATIVE + 1),
+        CalculatorOperator::Plus => ("+", ADDITIVE, ADDITIVE),
+        CalculatorOperator::Minus => ("−", ADDITIVE, ADDITIVE + 1),
+        CalculatorOperator::Equal => ("=", COMPARISON, COMPARISON + 1),
+        CalculatorOperator::NotEqual => ("≠", COMPARISON, COMPARISON + 1),
+        CalculatorOperator::Less => ("<", COMPARISON, COMPARISON + 1),
+        CalculatorOperator::LessEqual => ("≤", COMPARISON, COMPARISON + 1),
+        CalculatorOperator::Greater => (">", COMPARISON, COMPARISON + 1),
+        CalculatorOperator::GreaterEqual => ("≥", COMPARISON, COMPARISON + 1),
+    };
+    // `a + (−b)`
+    let right = if matches!(rhs, CalculatorNode::Prefix { .. }) { POSTFIX } else { right };
+    let mut items = vec![];
+    lhs.push_operand(left, &mut items);
+    items.push(MathOperator::new(symbol).into());
+    rhs.push_operand(right, &mut items);
+    MathRow::new(items).into()
+}
+
+fn call(function: &str, arguments: &[CalculatorNode]) -> MathML {
+    match (function, arguments) {
+        ("sqrt", [x]) => return MathSqrt::sqrt(x.as_mathml()).into(),
+        ("cbrt", [x]) => return MathSqrt::surd(x.as_mathml(), MathNumber::new("3").into()).into(),
+        ("root", [x, n]) => return MathSqrt::surd(x.as_mathml(), n.as_mathml()).into(),
+        ("abs", [x]) => return MathFenced::new(vec![x.as_mathml()], '|', '|').into(),
+        ("floor", [x]) => return MathFenced::new(vec![x.as_mathml()], '⌊', '⌋').into(),
+        ("ceil", [x]) => return MathFenced::new(vec![x.as_mathml()], '⌈', '⌉').into(),
+        ("factorial", [n]) => return CalculatorNode::Factorial { operand: Box::new(n.clone()) }.as_mathml(),
+        _ => {}
+    }
+    let fenced = || MathFenced::parentheses(arguments.iter().map(|node| node.as_mathml())).into();
+    match (find(FUNCTIONS, function), arguments) {
+        // `sin x` but `sin(x + 1)`
+        (Some(name), [x @ (CalculatorNode::Identifier { .. } | CalculatorNode::Number { exponent: None, .. })]) => {
+            MathFunction::new(name, vec![x.as_mathml()]).into()
+        }
+        (Some(name), _) => MathFunction::new(name, vec![fenced()]).into(),
+        // `f(x)`, with the invisible function application operator
+        (None, _) => MathRow::new(vec![identifier(function), MathOperator::new("\u{2061}").into(), fenced()]).into(),
+    }
+}
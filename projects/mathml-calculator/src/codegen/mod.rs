@@ -0,0 +1,61 @@
+use crate::{
+    definitions::{find, ATOM, CONSTANTS, FUNCTIONS, GREEK, MULTIPLICATIVE, POSTFIX, PREFIX},
+    CalculatorNode, CalculatorOperator,
+};
+
+mod as_mathml;
+
+impl<'i> CalculatorNode<'i> {
+    /// The binding power of the displayed node, fractions and fences don't need parentheses.
+    fn precedence(&self) -> u8 {
+        match self {
+            CalculatorNode::Number { exponent: Some(_), .. } => MULTIPLICATIVE,
+            CalculatorNode::Number { .. } | CalculatorNode::Identifier { .. } | CalculatorNode::Call { .. } => ATOM,
+            CalculatorNode::Prefix { .. } => PREFIX,
+            CalculatorNode::Factorial { .. } => POSTFIX,
+            CalculatorNode::Binary { operator: CalculatorOperator::Divide | CalculatorOperator::FloorDivide, .. } => ATOM,
+            CalculatorNode::Binary { operator, .. } => operator.binding_power().0,
+        }
+    }
+    /// Whether the node can take a superscript without parentheses.
+    fn is_script_base(&self) -> bool {
+        match self {
+            CalculatorNode::Number { exponent, .. } => exponent.is_none(),
+            CalculatorNode::Identifier { .. } => true,
+            // `sin(x)^2` would read as `sin x²`
+            CalculatorNode::Call { function, .. } => find(FUNCTIONS, function).is_none(),
+            _ => false,
+        }
+    }
+    /// Whether the leftmost displayed symbol is a digit, `2 × 3` needs a visible operator but `2x` doesn't.
+    fn starts_with_digit(&self) -> bool {
+        match self {
+            CalculatorNode::Number { .. } => true,
+            CalculatorNode::Prefix { .. } => true,
+            CalculatorNode::Factorial { operand } => operand.starts_with_digit(),
+            // `2 ½` would read as a mixed number
+            CalculatorNode::Binary { operator: CalculatorOperator::Divide, .. } => true,
+            CalculatorNode::Binary { lhs, .. } => lhs.precedence() >= self.precedence() && lhs.starts_with_digit(),
+            CalculatorNode::Identifier { .. } | CalculatorNode::Call { .. } => false,
+        }
+    }
+    /// Whether the identifier is a function name written without parentheses, as in `sin x`.
+    fn as_bare_function(&self) -> Option<&'static str> {
+        match self {
+            CalculatorNode::Identifier { name } => find(FUNCTIONS, name),
+            _ => None,
+        }
+    }
+}
+
+/// The displayed text and whether it is upright, for `pi`, `alpha` or `x`.
+fn identifier_text(name: &str) -> (&str, bool) {
+    if let Some(constant) = find(CONSTANTS, name) {
+        return (constant, true);
+    }
+    match find(GREEK, name) {
+        // capital Greek letters are upright, as in TeX
+        Some(letter) => (letter, letter.starts_with(char::is_uppercase)),
+        None => (name, false),
+    }
+}
@@ -0,0 +1,53 @@
+/// Binding power of comparisons.
+pub(crate) const COMPARISON: u8 = 1;
+/// Binding power of `+` and `-`.
+pub(crate) const ADDITIVE: u8 = 3;
+/// Binding power of `*`, `/`, `//`, `%` and juxtaposition.
+pub(crate) const MULTIPLICATIVE: u8 = 5;
+/// Binding power of prefix `-` and `+`.
+pub(crate) const PREFIX: u8 = 7;
+/// Binding power of `**` and `^`.
+pub(crate) const POWER: u8 = 9;
+/// Binding power of postfix `!`.
+pub(crate) const POSTFIX: u8 = 11;
+/// Numbers, identifiers, calls and fences.
+pub(crate) const ATOM: u8 = 12;
+
+/// Functions written as `MathFunction`, with the name displayed.
+#[rustfmt::skip]
+pub(crate) const FUNCTIONS: &[(&str, &str)] = &[
+    ("sin", "sin"), ("cos", "cos"), ("tan", "tan"), ("sec", "sec"), ("csc", "csc"), ("cot", "cot"),
+    ("asin", "arcsin"), ("acos", "arccos"), ("atan", "arctan"), ("arcsin", "arcsin"), ("arccos", "arccos"),
+    ("arctan", "arctan"), ("sinh", "sinh"), ("cosh", "cosh"), ("tanh", "tanh"), ("asinh", "arsinh"),
+    ("acosh", "arcosh"), ("atanh", "artanh"), ("exp", "exp"), ("ln", "ln"), ("log", "log"), ("log10", "lg"),
+    ("log2", "lb"), ("max", "max"), ("min", "min"), ("gcd", "gcd"), ("lcm", "lcm"), ("det", "det"),
+    ("sign", "sgn"), ("sgn", "sgn"), ("arg", "arg"), ("erf", "erf"),
+];
+
+/// Functions with their own layout, see `CalculatorNode::as_mathml`.
+pub(crate) const SPECIAL_FUNCTIONS: &[&str] = &["sqrt", "cbrt", "root", "abs", "floor", "ceil", "factorial"];
+
+/// Identifiers always read as functions when followed by parentheses, any other `x(y)` is a product.
+pub(crate) const USER_FUNCTIONS: &[&str] = &["f", "g", "h"];
+
+/// Names of constants and the upright symbol displayed.
+pub(crate) const CONSTANTS: &[(&str, &str)] = &[("pi", "π"), ("e", "e"), ("inf", "∞"), ("oo", "∞"), ("infinity", "∞")];
+
+/// Names of Greek letters.
+#[rustfmt::skip]
+pub(crate) const GREEK: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"), ("epsilon", "ε"), ("zeta", "ζ"), ("eta", "η"),
+    ("theta", "θ"), ("iota", "ι"), ("kappa", "κ"), ("lambda", "λ"), ("mu", "μ"), ("nu", "ν"), ("xi", "ξ"),
+    ("rho", "ρ"), ("sigma", "σ"), ("tau", "τ"), ("upsilon", "υ"), ("phi", "φ"), ("chi", "χ"), ("psi", "ψ"),
+    ("omega", "ω"), ("Gamma", "Γ"), ("Delta", "Δ"), ("Theta", "Θ"), ("Lambda", "Λ"), ("Xi", "Ξ"), ("Pi", "Π"),
+    ("Sigma", "Σ"), ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"),
+];
+
+pub(crate) fn find(table: &'static [(&str, &str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+/// Whether `name(...)` is a call rather than a product.
+pub(crate) fn is_function(name: &str) -> bool {
+    find(FUNCTIONS, name).is_some() || SPECIAL_FUNCTIONS.contains(&name) || USER_FUNCTIONS.contains(&name)
+}
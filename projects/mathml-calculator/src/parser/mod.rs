@@ -0,0 +1,192 @@
+use crate::definitions::{is_function, POSTFIX, PREFIX};
+use pex::{
+    helpers::{dec_str, whitespace},
+    ParseResult, ParseState, StopBecause,
+};
+
+mod operator;
+
+pub use self::operator::CalculatorOperator;
+
+/// Parse a calculator or Python style expression, such as `x**2 + sin(x)/2`.
+pub fn parse_calculator(s: &str) -> Result<CalculatorNode<'_>, StopBecause> {
+    let state = ParseState::new(s.trim_end()).skip(whitespace);
+    match CalculatorNode::parse(state) {
+        ParseResult::Pending(state, node) if state.is_empty() => Ok(node),
+        ParseResult::Pending(state, ..) => Err(StopBecause::ExpectEof { position: state.start_offset }),
+        ParseResult::Stop(e) => Err(e),
+    }
+}
+
+/// A calculator expression, grouping parentheses are resolved into the tree.
+#[derive(Clone, Debug)]
+pub enum CalculatorNode<'i> {
+    /// `3.14`, or `6.02e23` with an exponent
+    Number {
+        /// The digits before the exponent.
+        number: &'i str,
+        /// The decimal exponent, such as `23` or `-3`.
+        exponent: Option<&'i str>,
+    },
+    /// `x`, `alpha` or `x_1`
+    Identifier {
+        /// The name of the identifier.
+        name: &'i str,
+    },
+    /// `-x` or `+x`
+    Prefix {
+        /// `-` or `+`.
+        operator: &'i str,
+        /// The operand.
+        operand: Box<CalculatorNode<'i>>,
+    },
+    /// `n!`
+    Factorial {
+        /// The operand.
+        operand: Box<CalculatorNode<'i>>,
+    },
+    /// `a + b`, `a ** b` or `2x`
+    Binary {
+        /// The infix operator.
+        operator: CalculatorOperator,
+        /// The left operand.
+        lhs: Box<CalculatorNode<'i>>,
+        /// The right operand.
+        rhs: Box<CalculatorNode<'i>>,
+    },
+    /// `sqrt(x)` or `max(a, b)`
+    Call {
+        /// The name of the function.
+        function: &'i str,
+        /// The arguments.
+        arguments: Vec<CalculatorNode<'i>>,
+    },
+}
+
+impl<'i> CalculatorNode<'i> {
+    /// `expression := binary`
+    pub fn parse(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        Self::parse_binary(input, 0)
+    }
+    /// `binary := prefix (infix prefix | '!' | prefix)*`, by precedence climbing
+    fn parse_binary(input: ParseState<'i>, min: u8) -> ParseResult<'i, CalculatorNode<'i>> {
+        let (mut state, mut lhs) = Self::parse_prefix(input)?;
+        loop {
+            let next = state.skip(whitespace);
+            if let ParseResult::Pending(after, _) = Self::parse_factorial_mark(next) {
+                if POSTFIX < min {
+                    break;
+                }
+                lhs = CalculatorNode::Factorial { operand: Box::new(lhs) };
+                state = after;
+                continue;
+            }
+            let (after, operator) = match CalculatorOperator::parse(next) {
+                ParseResult::Pending(after, operator) => (after, operator),
+                // `2x`, `2(x + 1)`
+                ParseResult::Stop(_) if starts_operand(next.rest_text) => (next, CalculatorOperator::ImplicitTimes),
+                ParseResult::Stop(_) => break,
+            };
+            let (lbp, rbp) = operator.binding_power();
+            if lbp < min {
+                break;
+            }
+            let (after, rhs) = Self::parse_binary(after.skip(whitespace), rbp)?;
+            lhs = CalculatorNode::Binary { operator, lhs: Box::new(lhs), rhs: Box::new(rhs) };
+            state = after;
+        }
+        state.finish(lhs)
+    }
+    /// `'!'` not followed by `=`
+    fn parse_factorial_mark(input: ParseState<'i>) -> ParseResult<'i, ()> {
+        let (state, _) = input.match_char('!')?;
+        if state.rest_text.starts_with('=') {
+            StopBecause::missing_character('!', input.start_offset)?;
+        }
+        state.finish(())
+    }
+    /// `prefix := [-+] prefix | atomic`
+    fn parse_prefix(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        let sign = input
+            .begin_choice()
+            .or_else(|state| state.match_str("-", false).map_inner(|_| "-"))
+            .or_else(|state| state.match_str("−", false).map_inner(|_| "-"))
+            .or_else(|state| state.match_str("+", false))
+            .end_choice();
+        match sign {
+            ParseResult::Pending(state, operator) => {
+                let (state, operand) = Self::parse_binary(state.skip(whitespace), PREFIX)?;
+                state.finish(CalculatorNode::Prefix { operator, operand: Box::new(operand) })
+            }
+            ParseResult::Stop(_) => Self::parse_atomic(input),
+        }
+    }
+    fn parse_atomic(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        input
+            .begin_choice()
+            .or_else(Self::parse_group)
+            .or_else(Self::parse_number)
+            .or_else(Self::parse_call)
+            .or_else(Self::parse_identifier)
+            .end_choice()
+    }
+    /// `group := '(' expression ')'`
+    fn parse_group(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        let (state, _) = input.match_char('(')?;
+        let (state, node) = Self::parse(state.skip(whitespace))?;
+        let (state, _) = state.skip(whitespace).match_char(')')?;
+        state.finish(node)
+    }
+    /// `number := decimal ([eE] [-+]? [0-9]+)?`
+    fn parse_number(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        let (state, number) = dec_str(input)?;
+        if !number.contains(|c: char| c.is_ascii_digit()) {
+            StopBecause::missing_string("NUMBER", input.start_offset)?;
+        }
+        let (state, exponent) = state.match_optional(Self::parse_exponent)?;
+        state.finish(CalculatorNode::Number { number, exponent })
+    }
+    fn parse_exponent(input: ParseState<'i>) -> ParseResult<'i, &'i str> {
+        let (state, _) =
+            input.begin_choice().or_else(|state| state.match_char('e')).or_else(|state| state.match_char('E')).end_choice()?;
+        let (state, _) = state.match_optional(|state| {
+            state.begin_choice().or_else(|state| state.match_char('-')).or_else(|state| state.match_char('+')).end_choice()
+        })?;
+        let (state, _) = state.match_str_if(|c| c.is_ascii_digit(), "DIGIT")?;
+        let length = state.start_offset - input.start_offset;
+        state.finish(&input.rest_text[1..length])
+    }
+    /// `identifier := [\p{L}_] [\p{L}\p{N}_]*`
+    fn parse_identifier(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        let (state, name) = parse_name(input)?;
+        state.finish(CalculatorNode::Identifier { name })
+    }
+    /// `call := identifier '(' (expression (',' expression)*)? ')'`, for known functions only
+    fn parse_call(input: ParseState<'i>) -> ParseResult<'i, CalculatorNode<'i>> {
+        let (state, function) = parse_name(input)?;
+        if !is_function(function) {
+            StopBecause::must_be("FUNCTION", input.start_offset)?;
+        }
+        let (state, _) = state.skip(whitespace).match_char('(')?;
+        let (state, first) = state.skip(whitespace).match_optional(Self::parse)?;
+        let (state, rest) = state.match_repeats(|state| {
+            let (state, _) = state.skip(whitespace).match_char(',')?;
+            Self::parse(state.skip(whitespace))
+        })?;
+        let (state, _) = state.skip(whitespace).match_char(')')?;
+        let arguments = first.into_iter().chain(rest).collect();
+        state.finish(CalculatorNode::Call { function, arguments })
+    }
+}
+
+fn parse_name(input: ParseState<'_>) -> ParseResult<'_, &str> {
+    if !input.rest_text.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        StopBecause::missing_string("IDENTIFIER", input.start_offset)?;
+    }
+    input.match_str_if(|c| c.is_alphanumeric() || c == '_', "IDENTIFIER")
+}
+
+/// Whether an operand starts here, multiplied by juxtaposition.
+fn starts_operand(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '(')
+}
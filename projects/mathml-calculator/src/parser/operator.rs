@@ -0,0 +1,76 @@
+use super::*;
+use crate::definitions::{ADDITIVE, COMPARISON, MULTIPLICATIVE, POWER};
+
+/// Infix operators of calculator expressions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CalculatorOperator {
+    /// `=` or `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEqual,
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `*` or `×`
+    Times,
+    /// Juxtaposition, as in `2x` or `2(x + 1)`
+    ImplicitTimes,
+    /// `/` or `÷`
+    Divide,
+    /// `//`
+    FloorDivide,
+    /// `%`
+    Modulo,
+    /// `**` or `^`
+    Power,
+}
+
+impl CalculatorOperator {
+    /// Left and right binding power, `**` is right associative.
+    pub(crate) fn binding_power(&self) -> (u8, u8) {
+        match self {
+            Self::Equal | Self::NotEqual | Self::Less | Self::LessEqual | Self::Greater | Self::GreaterEqual => {
+                (COMPARISON, COMPARISON + 1)
+            }
+            Self::Plus | Self::Minus => (ADDITIVE, ADDITIVE + 1),
+            Self::Times | Self::ImplicitTimes | Self::Divide | Self::FloorDivide | Self::Modulo => {
+                (MULTIPLICATIVE, MULTIPLICATIVE + 1)
+            }
+            Self::Power => (POWER, POWER - 1),
+        }
+    }
+    /// `infix := '**' | '//' | '==' | '!=' | '<=' | '>=' | [-+*/%^<>=×÷]`
+    pub(super) fn parse(input: ParseState<'_>) -> ParseResult<'_, CalculatorOperator> {
+        let (state, operator) = input
+            .begin_choice()
+            .or_else(|state| state.match_str("**", false).map_inner(|_| Self::Power))
+            .or_else(|state| state.match_str("//", false).map_inner(|_| Self::FloorDivide))
+            .or_else(|state| state.match_str("==", false).map_inner(|_| Self::Equal))
+            .or_else(|state| state.match_str("!=", false).map_inner(|_| Self::NotEqual))
+            .or_else(|state| state.match_str("<=", false).map_inner(|_| Self::LessEqual))
+            .or_else(|state| state.match_str(">=", false).map_inner(|_| Self::GreaterEqual))
+            .or_else(|state| state.match_char('+').map_inner(|_| Self::Plus))
+            .or_else(|state| state.match_char('-').map_inner(|_| Self::Minus))
+            .or_else(|state| state.match_char('−').map_inner(|_| Self::Minus))
+            .or_else(|state| state.match_char('*').map_inner(|_| Self::Times))
+            .or_else(|state| state.match_char('×').map_inner(|_| Self::Times))
+            .or_else(|state| state.match_char('/').map_inner(|_| Self::Divide))
+            .or_else(|state| state.match_char('÷').map_inner(|_| Self::Divide))
+            .or_else(|state| state.match_char('%').map_inner(|_| Self::Modulo))
+            .or_else(|state| state.match_char('^').map_inner(|_| Self::Power))
+            .or_else(|state| state.match_char('<').map_inner(|_| Self::Less))
+            .or_else(|state| state.match_char('>').map_inner(|_| Self::Greater))
+            .or_else(|state| state.match_char('=').map_inner(|_| Self::Equal))
+            .end_choice()?;
+        state.finish(operator)
+    }
+}
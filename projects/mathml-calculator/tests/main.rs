@@ -0,0 +1,44 @@
+use mathml_calculator::parse_calculator;
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+#[track_caller]
+fn assert_mathml(input: &str, mathml: &str) {
+    assert_eq!(parse_calculator(input).unwrap().as_mathml().to_string(), mathml);
+}
+
+#[test]
+fn precedence() {
+    assert_mathml("(a + b) + (c - d)", "<mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi><mo>−</mo><mi>d</mi></mrow>");
+    assert_mathml(
+        "a - (b - c)",
+        r#"<mrow><mi>a</mi><mo>−</mo><mrow><mo stretchy="true" form="prefix">(</mo><mrow><mi>b</mi><mo>−</mo><mi>c</mi></mrow><mo stretchy="true" form="postfix">)</mo></mrow></mrow>"#,
+    );
+    assert_mathml("-x**2", "<mrow><mo>−</mo><msup><mi>x</mi><mn>2</mn></msup></mrow>");
+    assert_mathml(
+        "2*pi*r",
+        "<mrow><mn>2</mn><mo>\u{2062}</mo><mi mathvariant=\"normal\">π</mi><mo>\u{2062}</mo><mi>r</mi></mrow>",
+    );
+    assert_mathml("3*4", "<mrow><mn>3</mn><mo>×</mo><mn>4</mn></mrow>");
+}
+
+#[test]
+fn structures() {
+    assert_mathml(
+        "x**2 + sin(x)/2",
+        r#"<mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mfrac><mrow><mi mathvariant="normal">sin</mi><mspace width="0.167rem"/><mi>x</mi></mrow><mn>2</mn></mfrac></mrow>"#,
+    );
+    assert_mathml(
+        "abs(x_1 - 1)",
+        r#"<mrow><mo stretchy="true" form="prefix">|</mo><mrow><msub><mi>x</mi><mn>1</mn></msub><mo>−</mo><mn>1</mn></mrow><mo stretchy="true" form="postfix">|</mo></mrow>"#,
+    );
+    assert_mathml(
+        "(a/b)^(n+1)",
+        r#"<msup><mrow><mo stretchy="true" form="prefix">(</mo><mfrac><mi>a</mi><mi>b</mi></mfrac><mo stretchy="true" form="postfix">)</mo></mrow><mrow><mi>n</mi><mo>+</mo><mn>1</mn></mrow></msup>"#,
+    );
+    assert_mathml("n! != 1.5e-3", "<mrow><mi>n</mi><mo>!</mo><mo>≠</mo><mn>1.5</mn><mo>×</mo><msup><mn>10</mn><mrow><mo>−</mo><mn>3</mn></mrow></msup></mrow>");
+    assert!(parse_calculator("sin(x").is_err());
+}
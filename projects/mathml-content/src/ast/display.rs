@@ -0,0 +1,69 @@
+use super::*;
+use crate::definitions::CONTENT_ELEMENTS;
+use mathml_core::helpers::safe_html_str;
+use std::fmt::{Display, Formatter};
+
+impl Display for ContentNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentNode::Number(n) => write_leaf(f, "cn", n),
+            ContentNode::Identifier(n) => write_leaf(f, "ci", n),
+            ContentNode::Text(n) => write_leaf(f, "cs", n),
+            ContentNode::Symbol(n) if CONTENT_ELEMENTS.contains(&n.as_str()) => write!(f, "<{}/>", n),
+            ContentNode::Symbol(n) => write_leaf(f, "csymbol", n),
+            ContentNode::Apply(v) => Display::fmt(v, f),
+            ContentNode::Container(v) => Display::fmt(v, f),
+        }
+    }
+}
+
+fn write_leaf(f: &mut Formatter<'_>, tag: &str, text: &str) -> std::fmt::Result {
+    write!(f, "<{}>", tag)?;
+    safe_html_str(f, text)?;
+    write!(f, "</{}>", tag)
+}
+
+impl Display for ContentApply {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<apply>{}", self.head)?;
+        for variable in &self.bound_variables {
+            // the order of a derivative lives in its bound variable
+            match &self.degree {
+                Some(degree) => write!(f, "<bvar>{}<degree>{}</degree></bvar>", variable, degree)?,
+                None => write!(f, "<bvar>{}</bvar>", variable)?,
+            }
+        }
+        if let Some(s) = &self.lower_limit {
+            write!(f, "<lowlimit>{}</lowlimit>", s)?
+        }
+        if let Some(s) = &self.upper_limit {
+            write!(f, "<uplimit>{}</uplimit>", s)?
+        }
+        if let (Some(s), true) = (&self.degree, self.bound_variables.is_empty()) {
+            write!(f, "<degree>{}</degree>", s)?
+        }
+        if let Some(s) = &self.condition {
+            write!(f, "<condition>{}</condition>", s)?
+        }
+        for argument in &self.arguments {
+            write!(f, "{}", argument)?
+        }
+        f.write_str("</apply>")
+    }
+}
+
+impl Display for ContentContainer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}", self.tag)?;
+        for (key, value) in &self.attributes {
+            write!(f, " {}=\"", key)?;
+            safe_html_str(f, value)?;
+            f.write_str("\"")?;
+        }
+        f.write_str(">")?;
+        for item in &self.items {
+            write!(f, "{}", item)?
+        }
+        write!(f, "</{}>", self.tag)
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+mod display;
+
+/// Represent the [Content MathML](https://www.w3.org/TR/MathML3/chapter4.html) tree, the meaning of a formula rather than
+/// its layout.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentNode {
+    /// [`<cn>`](https://www.w3.org/TR/MathML3/chapter4.html#contm.cn)
+    Number(String),
+    /// [`<ci>`](https://www.w3.org/TR/MathML3/chapter4.html#contm.ci)
+    Identifier(String),
+    /// [`<cs>`](https://www.w3.org/TR/MathML3/chapter4.html#contm.cs)
+    Text(String),
+    /// Predefined symbols such as `<plus/>`, `<sin/>` or `<pi/>`, other names are written as `<csymbol>`
+    Symbol(String),
+    /// [`<apply>`](https://www.w3.org/TR/MathML3/chapter4.html#contm.apply)
+    Apply(Box<ContentApply>),
+    /// Containers such as `<vector>`, `<set>`, `<matrix>` or `<piecewise>`
+    Container(Box<ContentContainer>),
+}
+
+/// The application of a function or operator, with optional bound variables and qualifiers.
+///
+/// ```xml
+/// <apply><sum/><bvar><ci>i</ci></bvar><lowlimit><cn>1</cn></lowlimit><uplimit><ci>n</ci></uplimit><ci>i</ci></apply>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentApply {
+    head: ContentNode,
+    bound_variables: Vec<ContentNode>,
+    lower_limit: Option<ContentNode>,
+    upper_limit: Option<ContentNode>,
+    degree: Option<ContentNode>,
+    condition: Option<ContentNode>,
+    arguments: Vec<ContentNode>,
+}
+
+/// A container element, whose children are not arguments of a function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentContainer {
+    tag: String,
+    items: Vec<ContentNode>,
+    attributes: BTreeMap<String, String>,
+}
+
+impl ContentNode {
+    /// Creates a new `<cn>` node.
+    pub fn number<S>(number: S) -> Self
+    where
+        S: ToString,
+    {
+        ContentNode::Number(number.to_string())
+    }
+    /// Creates a new `<ci>` node.
+    pub fn identifier<S>(name: S) -> Self
+    where
+        S: ToString,
+    {
+        ContentNode::Identifier(name.to_string())
+    }
+    /// Creates a new `<cs>` node.
+    pub fn text<S>(text: S) -> Self
+    where
+        S: ToString,
+    {
+        ContentNode::Text(text.to_string())
+    }
+    /// Creates a new symbol such as `plus` or `pi`.
+    pub fn symbol<S>(name: S) -> Self
+    where
+        S: ToString,
+    {
+        ContentNode::Symbol(name.to_string())
+    }
+    /// Creates the application of a symbol to the given arguments.
+    pub fn apply<S, I>(symbol: S, arguments: I) -> Self
+    where
+        S: ToString,
+        I: IntoIterator<Item = ContentNode>,
+    {
+        ContentApply::new(ContentNode::symbol(symbol), arguments).into()
+    }
+    /// Check if the node is the given symbol.
+    pub fn is_symbol(&self, name: &str) -> bool {
+        matches!(self, ContentNode::Symbol(s) if s == name)
+    }
+    /// Get the application if the node is an `<apply>` with the given head symbol.
+    pub fn as_apply_of(&self, name: &str) -> Option<&ContentApply> {
+        match self {
+            ContentNode::Apply(apply) if apply.head.is_symbol(name) => Some(apply),
+            _ => None,
+        }
+    }
+}
+
+impl ContentApply {
+    /// Create a new application of `head` to the given arguments.
+    pub fn new<I>(head: ContentNode, arguments: I) -> Self
+    where
+        I: IntoIterator<Item = ContentNode>,
+    {
+        Self {
+            head,
+            bound_variables: vec![],
+            lower_limit: None,
+            upper_limit: None,
+            degree: None,
+            condition: None,
+            arguments: arguments.into_iter().collect(),
+        }
+    }
+    /// Add a `<bvar>`, such as the index of a sum or the variable of an integral.
+    pub fn with_bound_variable(mut self, variable: ContentNode) -> Self {
+        self.bound_variables.push(variable);
+        self
+    }
+    /// Set the `<lowlimit>` qualifier.
+    pub fn with_lower_limit(mut self, limit: ContentNode) -> Self {
+        self.lower_limit = Some(limit);
+        self
+    }
+    /// Set the `<uplimit>` qualifier.
+    pub fn with_upper_limit(mut self, limit: ContentNode) -> Self {
+        self.upper_limit = Some(limit);
+        self
+    }
+    /// Set the `<degree>` qualifier, used by roots and derivatives.
+    pub fn with_degree(mut self, degree: ContentNode) -> Self {
+        self.degree = Some(degree);
+        self
+    }
+    /// Set the `<condition>` qualifier.
+    pub fn with_condition(mut self, condition: ContentNode) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+    /// Gets the applied function or operator.
+    pub fn get_head(&self) -> &ContentNode {
+        &self.head
+    }
+    /// Gets the bound variables.
+    pub fn get_bound_variables(&self) -> &[ContentNode] {
+        &self.bound_variables
+    }
+    /// Gets the `<lowlimit>` qualifier.
+    pub fn get_lower_limit(&self) -> Option<&ContentNode> {
+        self.lower_limit.as_ref()
+    }
+    /// Gets the `<uplimit>` qualifier.
+    pub fn get_upper_limit(&self) -> Option<&ContentNode> {
+        self.upper_limit.as_ref()
+    }
+    /// Gets the `<degree>` qualifier.
+    pub fn get_degree(&self) -> Option<&ContentNode> {
+        self.degree.as_ref()
+    }
+    /// Gets the `<condition>` qualifier.
+    pub fn get_condition(&self) -> Option<&ContentNode> {
+        self.condition.as_ref()
+    }
+    /// Gets the arguments.
+    pub fn get_arguments(&self) -> &[ContentNode] {
+        &self.arguments
+    }
+    /// Gets the mutable arguments.
+    pub fn mut_arguments(&mut self) -> &mut Vec<ContentNode> {
+        &mut self.arguments
+    }
+    /// Check if the application has no bound variable nor qualifier.
+    pub fn is_plain(&self) -> bool {
+        self.bound_variables.is_empty()
+            && self.lower_limit.is_none()
+            && self.upper_limit.is_none()
+            && self.degree.is_none()
+            && self.condition.is_none()
+    }
+}
+
+impl ContentContainer {
+    /// Create a new container with the given tag name.
+    pub fn new<S, I>(tag: S, items: I) -> Self
+    where
+        S: ToString,
+        I: IntoIterator<Item = ContentNode>,
+    {
+        Self { tag: tag.to_string(), items: items.into_iter().collect(), attributes: BTreeMap::new() }
+    }
+    /// Add an attribute such as `closure="open"` for intervals.
+    pub fn with_attribute<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: ToString,
+        V: ToString,
+    {
+        self.attributes.insert(key.to_string(), value.to_string());
+        self
+    }
+    /// Gets the tag name of the container.
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+    /// Gets the items of the container.
+    pub fn get_items(&self) -> &[ContentNode] {
+        &self.items
+    }
+    /// Gets the attributes of the container.
+    pub fn get_attributes(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+}
+
+impl From<ContentApply> for ContentNode {
+    fn from(value: ContentApply) -> Self {
+        ContentNode::Apply(Box::new(value))
+    }
+}
+
+impl From<ContentContainer> for ContentNode {
+    fn from(value: ContentContainer) -> Self {
+        ContentNode::Container(Box::new(value))
+    }
+}
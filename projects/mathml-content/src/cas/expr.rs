@@ -0,0 +1,107 @@
+pub(super) const OR: u8 = 1;
+pub(super) const AND: u8 = 2;
+pub(super) const NOT: u8 = 3;
+pub(super) const RELATION: u8 = 4;
+pub(super) const ADD: u8 = 5;
+pub(super) const MULTIPLY: u8 = 6;
+pub(super) const NEGATE: u8 = 7;
+pub(super) const POWER: u8 = 8;
+const ATOM: u8 = 10;
+
+/// An expression in the target syntax, only aware of precedence.
+#[derive(Debug, Clone)]
+pub(super) enum Expr {
+    /// Written as is.
+    Atom(String),
+    /// `head(args)`, or `head[args]` in the Wolfram Language.
+    Call(String, Vec<Expr>),
+    /// `open args close`, such as a Python tuple or a Wolfram list.
+    List(&'static str, &'static str, Vec<Expr>),
+    /// Left associative operator with its binding power.
+    Infix(&'static str, u8, Vec<Expr>),
+    /// Right associative operator with its binding power.
+    InfixRight(&'static str, u8, Box<Expr>, Box<Expr>),
+    /// Prefix operator with its binding power.
+    Prefix(&'static str, u8, Box<Expr>),
+}
+
+impl Expr {
+    pub(super) fn atom<S: ToString>(text: S) -> Self {
+        Expr::Atom(text.to_string())
+    }
+    pub(super) fn call<S: ToString>(head: S, args: Vec<Expr>) -> Self {
+        Expr::Call(head.to_string(), args)
+    }
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Atom(_) | Expr::Call(..) | Expr::List(..) => ATOM,
+            Expr::Infix(_, p, _) | Expr::InfixRight(_, p, _, _) | Expr::Prefix(_, p, _) => *p,
+        }
+    }
+    /// Render with `[]` as call brackets when `square` is set.
+    pub(super) fn render(&self, square: bool) -> String {
+        let mut out = String::new();
+        self.write(square, &mut out);
+        out
+    }
+    fn write(&self, square: bool, out: &mut String) {
+        match self {
+            Expr::Atom(text) => out.push_str(text),
+            Expr::Call(head, args) => {
+                let (open, close) = if square { ("[", "]") } else { ("(", ")") };
+                out.push_str(head);
+                write_list(open, close, args, square, out);
+            }
+            // a Python tuple of one item
+            Expr::List("(", ")", items) if items.len() == 1 => write_list("(", ",)", items, square, out),
+            Expr::List(open, close, items) => write_list(open, close, items, square, out),
+            Expr::Infix(op, p, args) => {
+                for (i, arg) in args.iter().enumerate() {
+                    if i != 0 {
+                        out.push_str(op);
+                    }
+                    // `a - (b - c)`
+                    let min = if i == 0 { *p } else { p + 1 };
+                    arg.write_operand(min, square, out);
+                }
+            }
+            Expr::InfixRight(op, p, lhs, rhs) => {
+                lhs.write_operand(p + 1, square, out);
+                out.push_str(op);
+                rhs.write_operand(*p, square, out);
+            }
+            Expr::Prefix(op, p, arg) => {
+                out.push_str(op);
+                arg.write_operand(*p, square, out);
+            }
+        }
+    }
+    fn write_operand(&self, min: u8, square: bool, out: &mut String) {
+        match self.precedence() < min {
+            true => {
+                out.push('(');
+                self.write(square, out);
+                out.push(')');
+            }
+            false => self.write(square, out),
+        }
+    }
+}
+
+fn write_list(open: &str, close: &str, items: &[Expr], square: bool, out: &mut String) {
+    out.push_str(open);
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 {
+            out.push_str(", ");
+        }
+        item.write(square, out);
+    }
+    out.push_str(close);
+}
+
+/// Render an operand of a postfix construct such as `x[i]` in Maxima.
+pub(super) fn operand(expr: &Expr, square: bool) -> String {
+    let mut out = String::new();
+    expr.write_operand(ATOM, square, &mut out);
+    out
+}
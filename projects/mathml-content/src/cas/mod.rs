@@ -0,0 +1,524 @@
+use self::expr::{operand, Expr, ADD, AND, MULTIPLY, NEGATE, NOT, OR, POWER, RELATION};
+use crate::{
+    definitions::{CAS_CONSTANTS, CAS_FUNCTIONS, GREEK},
+    infer_content, ContentApply, ContentContainer, ContentNode, ExportError, InferenceConfig,
+};
+use mathml_core::MathML;
+use std::fmt::{Display, Formatter};
+
+mod expr;
+
+type Result<T> = std::result::Result<T, ExportError>;
+
+/// The syntax of a computer algebra system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CasSyntax {
+    /// A Python expression accepted by `sympy.sympify`, such as `Sum(a_i, (i, 1, n))`.
+    SymPy,
+    /// The Wolfram Language `FullForm`, such as `Sum[Subscript[a, i], List[i, 1, n]]`.
+    WolframFullForm,
+    /// The Wolfram Language `InputForm`, such as `Sum[Subscript[a, i], {i, 1, n}]`.
+    WolframInputForm,
+    /// Maxima, such as `sum(a[i], i, 1, n)`.
+    Maxima,
+}
+
+impl Display for CasSyntax {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CasSyntax::SymPy => f.write_str("SymPy"),
+            CasSyntax::WolframFullForm => f.write_str("Wolfram FullForm"),
+            CasSyntax::WolframInputForm => f.write_str("Wolfram InputForm"),
+            CasSyntax::Maxima => f.write_str("Maxima"),
+        }
+    }
+}
+
+/// Export a formula to the syntax of a computer algebra system.
+///
+/// The meaning of the formula is inferred with the default [`InferenceConfig`], use [`export_cas_content`] for other
+/// heuristics. Subscripted identifiers such as `a_i` are indexed symbols, superscripts are powers.
+pub fn export_cas(math: &MathML, syntax: CasSyntax) -> Result<String> {
+    let content = infer_content(math, &InferenceConfig::default())?;
+    export_cas_content(&content, syntax)
+}
+
+/// Export a Content MathML tree to the syntax of a computer algebra system.
+pub fn export_cas_content(node: &ContentNode, syntax: CasSyntax) -> Result<String> {
+    let writer = CasWriter { syntax };
+    Ok(writer.expr(node)?.render(writer.is_wolfram()))
+}
+
+fn is_integer(node: &ContentNode) -> bool {
+    matches!(node, ContentNode::Number(n) if n.chars().all(|c| c.is_ascii_digit()))
+}
+
+struct CasWriter {
+    syntax: CasSyntax,
+}
+
+impl CasWriter {
+    fn is_wolfram(&self) -> bool {
+        matches!(self.syntax, CasSyntax::WolframFullForm | CasSyntax::WolframInputForm)
+    }
+    fn is_full_form(&self) -> bool {
+        self.syntax == CasSyntax::WolframFullForm
+    }
+    fn unsupported<S: ToString>(&self, node: S) -> ExportError {
+        ExportError::Unsupported { node: node.to_string(), syntax: self.syntax }
+    }
+    /// Pick the name for the target syntax from a `(symbol, SymPy, Wolfram Language, Maxima)` table.
+    fn lookup(&self, table: &'static [(&str, &str, &str, &str)], symbol: &str) -> Option<&'static str> {
+        let (_, sympy, wolfram, maxima) = table.iter().find(|(key, ..)| *key == symbol)?;
+        let name = match self.syntax {
+            CasSyntax::SymPy => sympy,
+            CasSyntax::WolframFullForm | CasSyntax::WolframInputForm => wolfram,
+            CasSyntax::Maxima => maxima,
+        };
+        Some(*name).filter(|name| !name.is_empty())
+    }
+    fn exprs(&self, nodes: &[ContentNode]) -> Result<Vec<Expr>> {
+        nodes.iter().map(|node| self.expr(node)).collect()
+    }
+    /// `{a, b}` in the Wolfram Language, `[a, b]` otherwise.
+    fn list(&self, items: Vec<Expr>) -> Expr {
+        match self.syntax {
+            CasSyntax::WolframFullForm => Expr::call("List", items),
+            CasSyntax::WolframInputForm => Expr::List("{", "}", items),
+            CasSyntax::SymPy | CasSyntax::Maxima => Expr::List("[", "]", items),
+        }
+    }
+    fn infix(&self, op: &'static str, precedence: u8, head: &str, args: Vec<Expr>) -> Expr {
+        match self.is_full_form() {
+            true => Expr::call(head, args),
+            false => Expr::Infix(op, precedence, args),
+        }
+    }
+    fn power(&self, base: Expr, exponent: Expr) -> Expr {
+        match self.syntax {
+            CasSyntax::SymPy => Expr::InfixRight("**", POWER, Box::new(base), Box::new(exponent)),
+            CasSyntax::WolframFullForm => Expr::call("Power", vec![base, exponent]),
+            CasSyntax::WolframInputForm | CasSyntax::Maxima => Expr::InfixRight("^", POWER, Box::new(base), Box::new(exponent)),
+        }
+    }
+    fn negate(&self, arg: Expr) -> Expr {
+        match self.is_full_form() {
+            true => Expr::call("Times", vec![Expr::atom("-1"), arg]),
+            false => Expr::Prefix("-", NEGATE, Box::new(arg)),
+        }
+    }
+    fn divide(&self, numerator: &ContentNode, denominator: &ContentNode) -> Result<Expr> {
+        let rational = is_integer(numerator) && is_integer(denominator);
+        let (n, d) = (self.expr(numerator)?, self.expr(denominator)?);
+        let expr = match self.syntax {
+            // `1/2` is a float in Python
+            CasSyntax::SymPy if rational => Expr::call("Rational", vec![n, d]),
+            CasSyntax::WolframFullForm if rational => Expr::call("Rational", vec![n, d]),
+            CasSyntax::WolframFullForm => Expr::call("Times", vec![n, Expr::call("Power", vec![d, Expr::atom("-1")])]),
+            _ => Expr::Infix("/", MULTIPLY, vec![n, d]),
+        };
+        Ok(expr)
+    }
+
+    fn expr(&self, node: &ContentNode) -> Result<Expr> {
+        match node {
+            ContentNode::Number(number) => Ok(Expr::atom(number)),
+            ContentNode::Identifier(name) => self.identifier(name, false),
+            ContentNode::Text(_) => Err(self.unsupported("cs")),
+            ContentNode::Symbol(symbol) => match self.lookup(CAS_CONSTANTS, symbol) {
+                Some(constant) => Ok(Expr::atom(constant)),
+                None => Err(self.unsupported(symbol)),
+            },
+            ContentNode::Apply(apply) => self.apply(apply),
+            ContentNode::Container(container) => self.container(container),
+        }
+    }
+
+    /// Spell Greek letters and write `a_i` as an indexed symbol.
+    fn identifier(&self, name: &str, function: bool) -> Result<Expr> {
+        let mut greek = false;
+        let mut spell = |part: &str| -> Result<String> {
+            let mut out = String::new();
+            for c in part.chars() {
+                match GREEK.iter().find(|(letter, _)| *letter == c) {
+                    Some((_, name)) if self.is_wolfram() => {
+                        let capital = if name.starts_with(char::is_uppercase) { "Capital" } else { "" };
+                        let mut chars = name.chars();
+                        let head = chars.next().map(|c| c.to_ascii_uppercase()).unwrap_or_default();
+                        out.push_str(&format!("\\[{}{}{}]", capital, head, chars.as_str()));
+                    }
+                    Some((_, name)) => {
+                        greek = true;
+                        out.push_str(name)
+                    }
+                    None if c.is_ascii_alphanumeric() => out.push(c),
+                    None => return Err(self.unsupported(name)),
+                }
+            }
+            Ok(out)
+        };
+        let (base, index) = match name.split_once('_') {
+            Some((base, index)) => (spell(base)?, Some(spell(index)?)),
+            None => (spell(name)?, None),
+        };
+        let expr = match (self.syntax, index) {
+            (CasSyntax::SymPy, index) => {
+                let name = match index {
+                    Some(index) => format!("{}_{}", base, index),
+                    None => base,
+                };
+                // names of SymPy objects, and Greek letters that are functions such as `gamma` or keywords as `lambda`
+                let reserved = greek || ["E", "I", "N", "O", "Q", "S"].contains(&name.as_str());
+                match (reserved, function) {
+                    (true, true) => Expr::atom(format!("Function('{}')", name)),
+                    (true, false) => Expr::atom(format!("Symbol('{}')", name)),
+                    (false, _) => Expr::atom(name),
+                }
+            }
+            (_, None) => Expr::atom(base),
+            (CasSyntax::WolframFullForm | CasSyntax::WolframInputForm, Some(index)) => {
+                Expr::call("Subscript", vec![Expr::atom(base), Expr::atom(index)])
+            }
+            (CasSyntax::Maxima, Some(index)) => Expr::atom(format!("{}[{}]", base, index)),
+        };
+        Ok(expr)
+    }
+
+    fn apply(&self, apply: &ContentApply) -> Result<Expr> {
+        let head = match apply.get_head() {
+            ContentNode::Symbol(symbol) => symbol.as_str(),
+            ContentNode::Identifier(name) if apply.is_plain() => {
+                let head = operand(&self.identifier(name, true)?, self.is_wolfram());
+                return Ok(Expr::call(head, self.exprs(apply.get_arguments())?));
+            }
+            ContentNode::Apply(derivative) if apply.is_plain() && derivative.get_head().is_symbol("diff") => {
+                return self.derivative_of(derivative, apply.get_arguments());
+            }
+            head => return Err(self.unsupported(head)),
+        };
+        match head {
+            "sum" | "product" => return self.iterate(head, apply),
+            "int" => return self.integral(apply),
+            "limit" => return self.limit(apply),
+            "diff" | "partialdiff" if !apply.get_bound_variables().is_empty() => return self.derivative(apply),
+            "diff" => return self.derivative_of(apply, &[]),
+            "root" => {
+                return match apply.get_arguments() {
+                    [base] => self.root(base, apply.get_degree()),
+                    _ => Err(self.unsupported(head)),
+                };
+            }
+            _ if !apply.is_plain() => return Err(self.unsupported(head)),
+            _ => {}
+        }
+        let nodes = apply.get_arguments();
+        let args = self.exprs(nodes)?;
+        let expr = match (head, nodes) {
+            ("plus", [_, _, ..]) => self.infix(" + ", ADD, "Plus", args),
+            ("times", [_, _, ..]) => self.infix("*", MULTIPLY, "Times", args),
+            ("minus", [_]) => self.negate(args.into_iter().next().unwrap()),
+            ("minus", [_, _]) if self.is_full_form() => {
+                let mut args = args.into_iter();
+                let (lhs, rhs) = (args.next().unwrap(), args.next().unwrap());
+                Expr::call("Plus", vec![lhs, self.negate(rhs)])
+            }
+            ("minus", [_, _]) => Expr::Infix(" - ", ADD, args),
+            ("divide", [numerator, denominator]) => self.divide(numerator, denominator)?,
+            ("power", [_, _]) => {
+                let mut args = args.into_iter();
+                self.power(args.next().unwrap(), args.next().unwrap())
+            }
+            // common and binary logarithms
+            ("log" | "lg" | "lb", [_]) => {
+                let base = Expr::atom(if head == "lb" { "2" } else { "10" });
+                let x = args.into_iter().next().unwrap();
+                match self.syntax {
+                    CasSyntax::SymPy => Expr::call("log", vec![x, base]),
+                    CasSyntax::WolframFullForm | CasSyntax::WolframInputForm => Expr::call("Log", vec![base, x]),
+                    CasSyntax::Maxima => {
+                        Expr::Infix("/", MULTIPLY, vec![Expr::call("log", vec![x]), Expr::call("log", vec![base])])
+                    }
+                }
+            }
+            ("eq" | "neq" | "lt" | "gt" | "leq" | "geq", [_, _, ..]) => self.relation(head, args),
+            ("and" | "or", [_, _, ..]) => match self.syntax {
+                CasSyntax::SymPy => Expr::call(if head == "and" { "And" } else { "Or" }, args),
+                CasSyntax::WolframFullForm => Expr::call(if head == "and" { "And" } else { "Or" }, args),
+                CasSyntax::WolframInputForm if head == "and" => Expr::Infix(" && ", AND, args),
+                CasSyntax::WolframInputForm => Expr::Infix(" || ", OR, args),
+                CasSyntax::Maxima if head == "and" => Expr::Infix(" and ", AND, args),
+                CasSyntax::Maxima => Expr::Infix(" or ", OR, args),
+            },
+            ("not", [_]) => match self.syntax {
+                CasSyntax::SymPy | CasSyntax::WolframFullForm => Expr::call("Not", args),
+                CasSyntax::WolframInputForm => Expr::Prefix("!", NOT, Box::new(args.into_iter().next().unwrap())),
+                CasSyntax::Maxima => Expr::Prefix("not ", NOT, Box::new(args.into_iter().next().unwrap())),
+            },
+            ("implies", [_, _]) if self.syntax != CasSyntax::Maxima => Expr::call("Implies", args),
+            ("selector", [_, _]) => {
+                let mut args = args.into_iter();
+                let (base, index) = (args.next().unwrap(), args.next().unwrap());
+                match self.syntax {
+                    CasSyntax::SymPy => Expr::call("Indexed", vec![base, index]),
+                    CasSyntax::WolframFullForm | CasSyntax::WolframInputForm => Expr::call("Subscript", vec![base, index]),
+                    CasSyntax::Maxima => Expr::atom(format!("{}[{}]", operand(&base, false), index.render(false))),
+                }
+            }
+            _ => match self.lookup(CAS_FUNCTIONS, head) {
+                Some(function) => Expr::call(function, args),
+                None => return Err(self.unsupported(head)),
+            },
+        };
+        Ok(expr)
+    }
+
+    /// Chained relations, native in the Wolfram Language, pairwise conjunctions otherwise.
+    fn relation(&self, head: &str, args: Vec<Expr>) -> Expr {
+        let (sympy, wolfram, full_form, maxima) = match head {
+            "eq" => ("Eq", " == ", "Equal", " = "),
+            "neq" => ("Ne", " != ", "Unequal", " # "),
+            "lt" => ("Lt", " < ", "Less", " < "),
+            "gt" => ("Gt", " > ", "Greater", " > "),
+            "leq" => ("Le", " <= ", "LessEqual", " <= "),
+            _ => ("Ge", " >= ", "GreaterEqual", " >= "),
+        };
+        match self.syntax {
+            CasSyntax::WolframFullForm => return Expr::call(full_form, args),
+            CasSyntax::WolframInputForm => return Expr::Infix(wolfram, RELATION, args),
+            _ => {}
+        }
+        let mut pairs: Vec<Expr> = args
+            .windows(2)
+            .map(|pair| match self.syntax {
+                CasSyntax::SymPy => Expr::call(sympy, pair.to_vec()),
+                _ => Expr::Infix(maxima, RELATION, pair.to_vec()),
+            })
+            .collect();
+        match (pairs.len(), self.syntax) {
+            (1, _) => pairs.remove(0),
+            (_, CasSyntax::SymPy) => Expr::call("And", pairs),
+            _ => Expr::Infix(" and ", AND, pairs),
+        }
+    }
+
+    fn root(&self, base: &ContentNode, degree: Option<&ContentNode>) -> Result<Expr> {
+        let x = self.expr(base)?;
+        let expr = match (self.syntax, degree) {
+            (CasSyntax::SymPy, None) => Expr::call("sqrt", vec![x]),
+            (CasSyntax::SymPy, Some(n)) => Expr::call("root", vec![x, self.expr(n)?]),
+            (CasSyntax::WolframInputForm, None) => Expr::call("Sqrt", vec![x]),
+            (CasSyntax::Maxima, None) => Expr::call("sqrt", vec![x]),
+            (_, None) => self.power(x, Expr::call("Rational", vec![Expr::atom("1"), Expr::atom("2")])),
+            (_, Some(n)) => self.power(x, self.divide(&ContentNode::number(1), n)?),
+        };
+        Ok(expr)
+    }
+
+    /// `∑` and `∏` with a variable and both bounds.
+    fn iterate(&self, head: &str, apply: &ContentApply) -> Result<Expr> {
+        let (variable, lower, upper, body) =
+            match (apply.get_bound_variables(), apply.get_lower_limit(), apply.get_upper_limit(), apply.get_arguments()) {
+                ([variable], Some(lower), Some(upper), [body]) if apply.get_condition().is_none() => {
+                    (variable, lower, upper, body)
+                }
+                _ => return Err(self.unsupported(head)),
+            };
+        let range = vec![self.expr(variable)?, self.expr(lower)?, self.expr(upper)?];
+        let body = self.expr(body)?;
+        let name = match (self.syntax, head) {
+            (CasSyntax::Maxima, _) => head,
+            (_, "sum") => "Sum",
+            _ => "Product",
+        };
+        Ok(self.scoped(name, body, range))
+    }
+
+    fn integral(&self, apply: &ContentApply) -> Result<Expr> {
+        let (variable, body) = match (apply.get_bound_variables(), apply.get_arguments()) {
+            ([variable], [body]) if apply.get_condition().is_none() => (self.expr(variable)?, self.expr(body)?),
+            _ => return Err(self.unsupported("int")),
+        };
+        let name = match self.syntax {
+            CasSyntax::SymPy => "Integral",
+            CasSyntax::WolframFullForm | CasSyntax::WolframInputForm => "Integrate",
+            CasSyntax::Maxima => "integrate",
+        };
+        match (apply.get_lower_limit(), apply.get_upper_limit()) {
+            (None, None) => Ok(Expr::call(name, vec![body, variable])),
+            (Some(lower), Some(upper)) => Ok(self.scoped(name, body, vec![variable, self.expr(lower)?, self.expr(upper)?])),
+            _ => Err(self.unsupported("int")),
+        }
+    }
+
+    /// `Sum(body, (i, 1, n))`, `Sum[body, {i, 1, n}]` or `sum(body, i, 1, n)`.
+    fn scoped(&self, name: &str, body: Expr, range: Vec<Expr>) -> Expr {
+        match self.syntax {
+            CasSyntax::SymPy => Expr::call(name, vec![body, Expr::List("(", ")", range)]),
+            CasSyntax::WolframFullForm | CasSyntax::WolframInputForm => Expr::call(name, vec![body, self.list(range)]),
+            CasSyntax::Maxima => Expr::call(name, std::iter::once(body).chain(range).collect()),
+        }
+    }
+
+    fn limit(&self, apply: &ContentApply) -> Result<Expr> {
+        let (variable, point, body) = match (apply.get_bound_variables(), apply.get_lower_limit(), apply.get_arguments()) {
+            ([variable], Some(point), [body]) if apply.get_condition().is_none() => {
+                (self.expr(variable)?, self.expr(point)?, self.expr(body)?)
+            }
+            _ => return Err(self.unsupported("limit")),
+        };
+        let expr = match self.syntax {
+            CasSyntax::SymPy => Expr::call("Limit", vec![body, variable, point]),
+            CasSyntax::WolframFullForm => Expr::call("Limit", vec![body, Expr::call("Rule", vec![variable, point])]),
+            CasSyntax::WolframInputForm => {
+                Expr::call("Limit", vec![body, Expr::Infix(" -> ", RELATION, vec![variable, point])])
+            }
+            CasSyntax::Maxima => Expr::call("limit", vec![body, variable, point]),
+        };
+        Ok(expr)
+    }
+
+    /// `\frac{d^n y}{dx^n}` and partial derivatives.
+    fn derivative(&self, apply: &ContentApply) -> Result<Expr> {
+        let head = if apply.get_head().is_symbol("diff") { "diff" } else { "partialdiff" };
+        let (variable, body) = match (apply.get_bound_variables(), apply.get_arguments()) {
+            ([variable], [body]) => (self.expr(variable)?, self.expr(body)?),
+            _ => return Err(self.unsupported(head)),
+        };
+        let degree = apply.get_degree().map(|degree| self.expr(degree)).transpose()?;
+        let expr = match (self.syntax, degree) {
+            (CasSyntax::SymPy, None) => Expr::call("Derivative", vec![body, variable]),
+            (CasSyntax::SymPy, Some(n)) => Expr::call("Derivative", vec![body, Expr::List("(", ")", vec![variable, n])]),
+            (CasSyntax::Maxima, degree) => Expr::call("diff", [body, variable].into_iter().chain(degree).collect()),
+            (_, None) => Expr::call("D", vec![body, variable]),
+            (_, Some(n)) => Expr::call("D", vec![body, self.list(vec![variable, n])]),
+        };
+        Ok(expr)
+    }
+
+    /// `f'` or `f''(x)`, the derivative of a function.
+    fn derivative_of(&self, derivative: &ContentApply, args: &[ContentNode]) -> Result<Expr> {
+        let function = match derivative.get_arguments() {
+            [ContentNode::Identifier(function)] if derivative.get_bound_variables().is_empty() => function,
+            _ => return Err(self.unsupported("diff")),
+        };
+        let degree = derivative.get_degree().map(|degree| self.expr(degree)).transpose()?;
+        if self.is_wolfram() {
+            let head = operand(&self.identifier(function, true)?, true);
+            let order = degree.map(|n| n.render(true)).unwrap_or_else(|| "1".to_string());
+            let derivative = format!("Derivative[{}][{}]", order, head);
+            return match args {
+                [] => Ok(Expr::atom(derivative)),
+                _ => Ok(Expr::call(derivative, self.exprs(args)?)),
+            };
+        }
+        let variable = match args {
+            [variable @ ContentNode::Identifier(_)] => self.expr(variable)?,
+            _ => return Err(self.unsupported("diff")),
+        };
+        let head = operand(&self.identifier(function, true)?, false);
+        let body = Expr::call(head, vec![variable.clone()]);
+        let expr = match (self.syntax, degree) {
+            (CasSyntax::SymPy, None) => Expr::call("Derivative", vec![body, variable]),
+            (CasSyntax::SymPy, Some(n)) => Expr::call("Derivative", vec![body, Expr::List("(", ")", vec![variable, n])]),
+            (_, degree) => Expr::call("diff", [body, variable].into_iter().chain(degree).collect()),
+        };
+        Ok(expr)
+    }
+
+    fn container(&self, container: &ContentContainer) -> Result<Expr> {
+        let tag = container.get_tag();
+        let items = container.get_items();
+        let expr = match tag {
+            "list" => self.list(self.exprs(items)?),
+            "vector" => match self.syntax {
+                CasSyntax::SymPy => Expr::call("Matrix", vec![self.list(self.exprs(items)?)]),
+                _ => self.list(self.exprs(items)?),
+            },
+            "set" => match self.syntax {
+                CasSyntax::SymPy => Expr::call("FiniteSet", self.exprs(items)?),
+                CasSyntax::Maxima => Expr::List("{", "}", self.exprs(items)?),
+                _ => return Err(self.unsupported(tag)),
+            },
+            "interval" => self.interval(container)?,
+            "matrix" => {
+                let rows = items
+                    .iter()
+                    .map(|row| match row {
+                        ContentNode::Container(row) if row.get_tag() == "matrixrow" => {
+                            Ok(self.list(self.exprs(row.get_items())?))
+                        }
+                        _ => Err(self.unsupported(tag)),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                match self.syntax {
+                    CasSyntax::SymPy => Expr::call("Matrix", vec![self.list(rows)]),
+                    CasSyntax::Maxima => Expr::call("matrix", rows),
+                    _ => self.list(rows),
+                }
+            }
+            "piecewise" => self.piecewise(container)?,
+            _ => return Err(self.unsupported(tag)),
+        };
+        Ok(expr)
+    }
+
+    fn interval(&self, interval: &ContentContainer) -> Result<Expr> {
+        let bounds = match interval.get_items() {
+            [lower, upper] => vec![self.expr(lower)?, self.expr(upper)?],
+            _ => return Err(self.unsupported("interval")),
+        };
+        let closure = interval.get_attributes().get("closure").map(|s| s.as_str()).unwrap_or("closed");
+        let expr = match (self.syntax, closure) {
+            (CasSyntax::SymPy, "closed") => Expr::call("Interval", bounds),
+            (CasSyntax::SymPy, "open") => Expr::call("Interval.open", bounds),
+            (CasSyntax::SymPy, "open-closed") => Expr::call("Interval.Lopen", bounds),
+            (CasSyntax::SymPy, "closed-open") => Expr::call("Interval.Ropen", bounds),
+            (CasSyntax::WolframFullForm | CasSyntax::WolframInputForm, "closed") => {
+                Expr::call("Interval", vec![self.list(bounds)])
+            }
+            _ => return Err(self.unsupported(format!("interval {}", closure))),
+        };
+        Ok(expr)
+    }
+
+    fn piecewise(&self, piecewise: &ContentContainer) -> Result<Expr> {
+        let mut pieces = vec![];
+        let mut otherwise = None;
+        for piece in piecewise.get_items() {
+            match piece {
+                ContentNode::Container(piece) => match (piece.get_tag(), piece.get_items()) {
+                    ("piece", [value, condition]) => pieces.push((self.expr(value)?, self.expr(condition)?)),
+                    ("otherwise", [value]) => otherwise = Some(self.expr(value)?),
+                    _ => return Err(self.unsupported("piecewise")),
+                },
+                _ => return Err(self.unsupported("piecewise")),
+            }
+        }
+        let expr = match self.syntax {
+            CasSyntax::SymPy => {
+                let otherwise = otherwise.map(|value| Expr::List("(", ")", vec![value, Expr::atom("True")]));
+                let pieces = pieces.into_iter().map(|(value, condition)| Expr::List("(", ")", vec![value, condition]));
+                Expr::call("Piecewise", pieces.chain(otherwise).collect())
+            }
+            CasSyntax::WolframFullForm | CasSyntax::WolframInputForm => {
+                let pieces = pieces.into_iter().map(|(value, condition)| self.list(vec![value, condition])).collect();
+                Expr::call("Piecewise", std::iter::once(self.list(pieces)).chain(otherwise).collect())
+            }
+            CasSyntax::Maxima => {
+                let mut out = String::new();
+                for (i, (value, condition)) in pieces.iter().enumerate() {
+                    let keyword = if i == 0 { "if" } else { " elseif" };
+                    out.push_str(&format!("{} {} then {}", keyword, condition.render(false), value.render(false)));
+                }
+                if let Some(value) = otherwise {
+                    match out.is_empty() {
+                        true => return Ok(value),
+                        false => out.push_str(&format!(" else {}", value.render(false))),
+                    }
+                }
+                Expr::atom(format!("({})", out))
+            }
+        };
+        Ok(expr)
+    }
+}
@@ -0,0 +1,121 @@
+/// Symbols written as their own element, such as `<plus/>`, everything else becomes a `<csymbol>`.
+#[rustfmt::skip]
+pub(crate) const CONTENT_ELEMENTS: &[&str] = &[
+    "plus", "minus", "times", "divide", "power", "root", "factorial", "abs", "floor", "ceiling", "compose",
+    "eq", "neq", "lt", "gt", "leq", "geq", "approx", "equivalent", "factorof", "tendsto",
+    "and", "or", "not", "implies", "in", "notin", "subset", "prsubset", "union", "intersect",
+    "sum", "product", "int", "limit", "diff", "partialdiff", "selector", "determinant", "scalarproduct",
+    "sin", "cos", "tan", "sec", "csc", "cot", "sinh", "cosh", "tanh", "sech", "csch", "coth",
+    "arcsin", "arccos", "arctan", "arcsec", "arccsc", "arccot", "arcsinh", "arccosh", "arctanh",
+    "exp", "ln", "log", "max", "min", "gcd", "lcm", "real", "imaginary", "arg",
+    "pi", "exponentiale", "imaginaryi", "infinity", "emptyset", "true", "false",
+    "naturalnumbers", "integers", "rationals", "reals", "complexes",
+];
+
+/// Function names in presentation markup and the symbol they apply.
+#[rustfmt::skip]
+pub(crate) const FUNCTIONS: &[(&str, &str)] = &[
+    ("sin", "sin"), ("cos", "cos"), ("tan", "tan"), ("sec", "sec"), ("csc", "csc"), ("cot", "cot"),
+    ("sinh", "sinh"), ("cosh", "cosh"), ("tanh", "tanh"), ("sech", "sech"), ("csch", "csch"), ("coth", "coth"),
+    ("arcsin", "arcsin"), ("arccos", "arccos"), ("arctan", "arctan"), ("arcsec", "arcsec"), ("arccsc", "arccsc"),
+    ("arccot", "arccot"), ("arsinh", "arcsinh"), ("arcosh", "arccosh"), ("artanh", "arctanh"),
+    ("exp", "exp"), ("ln", "ln"), ("log", "log"), ("lg", "lg"), ("erf", "erf"), ("erfc", "erfc"),
+    ("max", "max"), ("min", "min"), ("gcd", "gcd"), ("lcm", "lcm"), ("det", "determinant"),
+    ("Re", "real"), ("Im", "imaginary"), ("arg", "arg"), ("sgn", "sgn"),
+];
+
+/// Operators written before their body, with limits as scripts.
+pub(crate) const BIG_OPERATORS: &[(&str, &str)] =
+    &[("∑", "sum"), ("∏", "product"), ("∫", "int"), ("∮", "int"), ("⋃", "union"), ("⋂", "intersect"), ("lim", "limit")];
+
+/// Identifiers with a predefined meaning.
+#[rustfmt::skip]
+pub(crate) const CONSTANTS: &[(&str, &str)] = &[
+    ("π", "pi"), ("∞", "infinity"), ("∅", "emptyset"), ("ℯ", "exponentiale"), ("ⅇ", "exponentiale"), ("ⅈ", "imaginaryi"),
+    ("ℕ", "naturalnumbers"), ("ℤ", "integers"), ("ℚ", "rationals"), ("ℝ", "reals"), ("ℂ", "complexes"),
+];
+
+/// Binding power of juxtaposition and invisible times.
+pub(crate) const TIMES: u8 = 30;
+/// Binding power of prefix `−`, `+` and `¬`.
+pub(crate) const PREFIX: u8 = 25;
+/// Binding power of postfix `!`.
+pub(crate) const POSTFIX: u8 = 50;
+
+/// Infix operators as `(operator, symbol, left binding power, right binding power)`.
+#[rustfmt::skip]
+pub(crate) const INFIX: &[(&str, &str, u8, u8)] = &[
+    ("⇔", "equivalent", 1, 2), ("⟺", "equivalent", 1, 2), ("⇒", "implies", 4, 3), ("⟹", "implies", 4, 3),
+    ("∨", "or", 5, 6), ("∧", "and", 7, 8),
+    ("=", "eq", 10, 11), ("≠", "neq", 10, 11), ("<", "lt", 10, 11), (">", "gt", 10, 11), ("≤", "leq", 10, 11),
+    ("≥", "geq", 10, 11), ("≈", "approx", 10, 11), ("≡", "equivalent", 10, 11), ("∈", "in", 10, 11),
+    ("∉", "notin", 10, 11), ("⊂", "prsubset", 10, 11), ("⊆", "subset", 10, 11), ("→", "tendsto", 10, 11),
+    ("∣", "factorof", 10, 11),
+    ("∪", "union", 15, 16), ("∩", "intersect", 17, 18),
+    ("+", "plus", 20, 21), ("−", "minus", 20, 21), ("-", "minus", 20, 21), ("±", "plusminus", 20, 21),
+    ("×", "times", 30, 31), ("⋅", "times", 30, 31), ("·", "times", 30, 31), ("*", "times", 30, 31),
+    ("∗", "times", 30, 31), ("\u{2062}", "times", 30, 31), ("\u{2064}", "plus", 20, 21),
+    ("/", "divide", 30, 31), ("÷", "divide", 30, 31), ("∘", "compose", 40, 41),
+];
+
+/// Operators that chain, `a < b < c` is a single application.
+pub(crate) const RELATIONS: &[&str] = &["eq", "neq", "lt", "gt", "leq", "geq", "approx", "equivalent", "in", "notin"];
+
+/// Opening fences and the closing fences accepted for them.
+pub(crate) const FENCES: &[(&str, &[&str])] = &[
+    ("(", &[")", "]"]),
+    ("[", &["]", ")"]),
+    ("{", &["}"]),
+    ("|", &["|"]),
+    ("‖", &["‖"]),
+    ("⌊", &["⌋"]),
+    ("⌈", &["⌉"]),
+    ("⟨", &["⟩"]),
+];
+
+/// Content symbols and their names as `(symbol, SymPy, Wolfram Language, Maxima)`, empty without an equivalent.
+#[rustfmt::skip]
+pub(crate) const CAS_FUNCTIONS: &[(&str, &str, &str, &str)] = &[
+    ("sin", "sin", "Sin", "sin"), ("cos", "cos", "Cos", "cos"), ("tan", "tan", "Tan", "tan"),
+    ("sec", "sec", "Sec", "sec"), ("csc", "csc", "Csc", "csc"), ("cot", "cot", "Cot", "cot"),
+    ("sinh", "sinh", "Sinh", "sinh"), ("cosh", "cosh", "Cosh", "cosh"), ("tanh", "tanh", "Tanh", "tanh"),
+    ("sech", "sech", "Sech", "sech"), ("csch", "csch", "Csch", "csch"), ("coth", "coth", "Coth", "coth"),
+    ("arcsin", "asin", "ArcSin", "asin"), ("arccos", "acos", "ArcCos", "acos"), ("arctan", "atan", "ArcTan", "atan"),
+    ("arcsec", "asec", "ArcSec", "asec"), ("arccsc", "acsc", "ArcCsc", "acsc"), ("arccot", "acot", "ArcCot", "acot"),
+    ("arcsinh", "asinh", "ArcSinh", "asinh"), ("arccosh", "acosh", "ArcCosh", "acosh"),
+    ("arctanh", "atanh", "ArcTanh", "atanh"),
+    ("exp", "exp", "Exp", "exp"), ("ln", "log", "Log", "log"), ("erf", "erf", "Erf", "erf"),
+    ("erfc", "erfc", "Erfc", "erfc"), ("abs", "Abs", "Abs", "abs"), ("floor", "floor", "Floor", "floor"),
+    ("ceiling", "ceiling", "Ceiling", "ceiling"), ("factorial", "factorial", "Factorial", "factorial"),
+    ("binomial", "binomial", "Binomial", "binomial"), ("max", "Max", "Max", "max"), ("min", "Min", "Min", "min"),
+    ("gcd", "gcd", "GCD", "gcd"), ("lcm", "lcm", "LCM", "lcm"), ("real", "re", "Re", "realpart"),
+    ("imaginary", "im", "Im", "imagpart"), ("arg", "arg", "Arg", "carg"), ("sgn", "sign", "Sign", "signum"),
+    ("determinant", "det", "Det", "determinant"), ("norm", "", "Norm", ""),
+    ("union", "Union", "Union", "union"), ("intersect", "Intersection", "Intersection", "intersection"),
+    ("in", "Contains", "Element", "elementp"),
+];
+
+/// Content constants as `(symbol, SymPy, Wolfram Language, Maxima)`, empty without an equivalent.
+#[rustfmt::skip]
+pub(crate) const CAS_CONSTANTS: &[(&str, &str, &str, &str)] = &[
+    ("pi", "pi", "Pi", "%pi"), ("exponentiale", "E", "E", "%e"), ("imaginaryi", "I", "I", "%i"),
+    ("infinity", "oo", "Infinity", "inf"), ("true", "True", "True", "true"), ("false", "False", "False", "false"),
+    ("emptyset", "EmptySet", "", "{}"), ("naturalnumbers", "Naturals", "PositiveIntegers", ""),
+    ("integers", "Integers", "Integers", ""), ("rationals", "Rationals", "Rationals", ""),
+    ("reals", "Reals", "Reals", ""), ("complexes", "Complexes", "Complexes", ""),
+];
+
+/// Greek letters and their names, as written in computer algebra systems.
+#[rustfmt::skip]
+pub(crate) const GREEK: &[(char, &str)] = &[
+    ('α', "alpha"), ('β', "beta"), ('γ', "gamma"), ('δ', "delta"), ('ε', "epsilon"), ('ϵ', "epsilon"),
+    ('ζ', "zeta"), ('η', "eta"), ('θ', "theta"), ('ϑ', "theta"), ('ι', "iota"), ('κ', "kappa"), ('λ', "lambda"),
+    ('μ', "mu"), ('ν', "nu"), ('ξ', "xi"), ('ο', "omicron"), ('π', "pi"), ('ρ', "rho"), ('ϱ', "rho"),
+    ('σ', "sigma"), ('ς', "sigma"), ('τ', "tau"), ('υ', "upsilon"), ('φ', "phi"), ('ϕ', "phi"), ('χ', "chi"),
+    ('ψ', "psi"), ('ω', "omega"), ('Γ', "Gamma"), ('Δ', "Delta"), ('Θ', "Theta"), ('Λ', "Lambda"), ('Ξ', "Xi"),
+    ('Π', "Pi"), ('Σ', "Sigma"), ('Υ', "Upsilon"), ('Φ', "Phi"), ('Ψ', "Psi"), ('Ω', "Omega"),
+];
+
+pub(crate) fn find(table: &'static [(&str, &str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
@@ -0,0 +1,121 @@
+use crate::CasSyntax;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// Errors raised while inferring the meaning of a presentation tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferenceError {
+    /// The node has no semantic interpretation, such as `<mmultiscripts>` with prescripts.
+    Unsupported {
+        /// The tag name of the node.
+        node: String,
+    },
+    /// An operator appears where an operand is expected.
+    UnexpectedOperator {
+        /// The text of the operator.
+        operator: String,
+    },
+    /// An operator is missing its operand, as in `a+`.
+    MissingOperand,
+    /// A fence is never closed, or closed by a wrong fence.
+    UnbalancedFence {
+        /// The opening fence.
+        open: String,
+    },
+}
+
+impl Error for InferenceError {}
+
+impl Display for InferenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InferenceError::Unsupported { node } => write!(f, "Can't infer the meaning of `{}`", node),
+            InferenceError::UnexpectedOperator { operator } => write!(f, "Unexpected operator `{}`", operator),
+            InferenceError::MissingOperand => f.write_str("Missing operand"),
+            InferenceError::UnbalancedFence { open } => write!(f, "Unbalanced fence `{}`", open),
+        }
+    }
+}
+
+/// Errors raised while evaluating a formula.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// The meaning of the formula can't be inferred.
+    Inference(InferenceError),
+    /// The node can't be evaluated numerically, such as an integral or a matrix.
+    Unsupported {
+        /// The name of the node, such as `int` or `matrix`.
+        node: String,
+    },
+    /// A variable has no value.
+    UnboundVariable {
+        /// The name of the variable.
+        name: String,
+    },
+    /// A number can't be read.
+    InvalidNumber {
+        /// The text of the number.
+        number: String,
+    },
+    /// The argument is outside the domain of the function, such as the factorial of `1.5`.
+    Domain {
+        /// The name of the function.
+        function: String,
+    },
+    /// No condition of a piecewise function holds.
+    NoMatchingCase,
+}
+
+impl Error for EvalError {}
+
+impl Display for EvalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalError::Inference(e) => Display::fmt(e, f),
+            EvalError::Unsupported { node } => write!(f, "Can't evaluate `{}`", node),
+            EvalError::UnboundVariable { name } => write!(f, "Variable `{}` has no value", name),
+            EvalError::InvalidNumber { number } => write!(f, "Invalid number `{}`", number),
+            EvalError::Domain { function } => write!(f, "Argument out of the domain of `{}`", function),
+            EvalError::NoMatchingCase => f.write_str("No case of the piecewise function applies"),
+        }
+    }
+}
+
+impl From<InferenceError> for EvalError {
+    fn from(value: InferenceError) -> Self {
+        EvalError::Inference(value)
+    }
+}
+
+/// Errors raised while exporting a formula to a computer algebra system.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportError {
+    /// The meaning of the formula can't be inferred.
+    Inference(InferenceError),
+    /// The node has no equivalent in the target syntax, such as an open interval in Maxima.
+    Unsupported {
+        /// The name of the node, such as `interval` or `plusminus`.
+        node: String,
+        /// The target syntax.
+        syntax: CasSyntax,
+    },
+}
+
+impl Error for ExportError {}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Inference(e) => Display::fmt(e, f),
+            ExportError::Unsupported { node, syntax } => write!(f, "`{}` has no equivalent in {}", node, syntax),
+        }
+    }
+}
+
+impl From<InferenceError> for ExportError {
+    fn from(value: InferenceError) -> Self {
+        ExportError::Inference(value)
+    }
+}
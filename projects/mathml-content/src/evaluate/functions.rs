@@ -0,0 +1,178 @@
+use super::EvalValue;
+use crate::EvalError;
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+type Unary = (fn(f64) -> f64, fn(Complex64) -> Complex64);
+
+/// Functions defined on complex numbers, a real argument stays real unless the result leaves the real domain.
+#[rustfmt::skip]
+fn unary(name: &str) -> Option<Unary> {
+    let pair: Unary = match name {
+        "sin" => (f64::sin, Complex64::sin),
+        "cos" => (f64::cos, Complex64::cos),
+        "tan" => (f64::tan, Complex64::tan),
+        "sec" => (|x| x.cos().recip(), |z| z.cos().inv()),
+        "csc" => (|x| x.sin().recip(), |z| z.sin().inv()),
+        "cot" => (|x| x.tan().recip(), |z| z.tan().inv()),
+        "sinh" => (f64::sinh, Complex64::sinh),
+        "cosh" => (f64::cosh, Complex64::cosh),
+        "tanh" => (f64::tanh, Complex64::tanh),
+        "sech" => (|x| x.cosh().recip(), |z| z.cosh().inv()),
+        "csch" => (|x| x.sinh().recip(), |z| z.sinh().inv()),
+        "coth" => (|x| x.tanh().recip(), |z| z.tanh().inv()),
+        "arcsin" => (f64::asin, Complex64::asin),
+        "arccos" => (f64::acos, Complex64::acos),
+        "arctan" => (f64::atan, Complex64::atan),
+        "arcsec" => (|x| x.recip().acos(), |z| z.inv().acos()),
+        "arccsc" => (|x| x.recip().asin(), |z| z.inv().asin()),
+        "arccot" => (|x| x.recip().atan(), |z| z.inv().atan()),
+        "arcsinh" => (f64::asinh, Complex64::asinh),
+        "arccosh" => (f64::acosh, Complex64::acosh),
+        "arctanh" => (f64::atanh, Complex64::atanh),
+        "exp" => (f64::exp, Complex64::exp),
+        "ln" => (f64::ln, Complex64::ln),
+        // `log` without base is the common logarithm, as in Content MathML
+        "log" | "lg" => (f64::log10, |z| z.log(10.0)),
+        "lb" => (f64::log2, |z| z.log(2.0)),
+        _ => return None,
+    };
+    Some(pair)
+}
+
+/// Evaluate a real operation, or the complex one if an argument is complex or the real result is undefined.
+pub(super) fn real_or_complex<R, C>(args: &[EvalValue], real: R, complex: C) -> EvalValue
+where
+    R: Fn(&[f64]) -> f64,
+    C: Fn(&[Complex64]) -> Complex64,
+{
+    let reals: Option<Vec<f64>> = args.iter().map(|v| v.as_real()).collect();
+    if let Some(reals) = reals {
+        let value = real(&reals);
+        if !value.is_nan() || reals.iter().any(|v| v.is_nan()) {
+            return EvalValue::Real(value);
+        }
+    }
+    let complex_args: Vec<Complex64> = args.iter().map(|v| v.as_complex()).collect();
+    EvalValue::normalize(complex(&complex_args))
+}
+
+pub(super) fn apply_function(name: &str, args: &[EvalValue]) -> Result<EvalValue, EvalError> {
+    let domain = || EvalError::Domain { function: name.to_string() };
+    if let Some((real, complex)) = unary(name) {
+        return match args {
+            [_] => Ok(real_or_complex(args, |x| real(x[0]), |z| complex(z[0]))),
+            _ => Err(domain()),
+        };
+    }
+    match (name, args) {
+        ("abs", [EvalValue::Real(x)]) => return Ok(EvalValue::Real(x.abs())),
+        ("abs", [EvalValue::Complex(z)]) => return Ok(EvalValue::Real(z.norm())),
+        ("arg", [z]) => return Ok(EvalValue::Real(z.as_complex().arg())),
+        ("real", [z]) => return Ok(EvalValue::Real(z.as_complex().re)),
+        ("imaginary", [z]) => return Ok(EvalValue::Real(z.as_complex().im)),
+        _ => {}
+    }
+    let reals: Vec<f64> = args.iter().map(|v| v.as_real()).collect::<Option<_>>().ok_or_else(domain)?;
+    let value = match (name, reals.as_slice()) {
+        ("floor", [x]) => x.floor(),
+        ("ceiling", [x]) => x.ceil(),
+        ("sgn", [x]) if *x == 0.0 => 0.0,
+        ("sgn", [x]) => x.signum(),
+        ("erf", [x]) => erf(*x),
+        ("erfc", [x]) => erfc(*x),
+        ("factorial", [x]) => factorial(*x).ok_or_else(domain)?,
+        ("binomial", [n, k]) => binomial(*n, *k).ok_or_else(domain)?,
+        ("max", [_, ..]) => reals.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        ("min", [_, ..]) => reals.iter().copied().fold(f64::INFINITY, f64::min),
+        ("gcd", [_, ..]) => integers(&reals).ok_or_else(domain)?.into_iter().fold(0, gcd) as f64,
+        ("lcm", [_, ..]) => integers(&reals).ok_or_else(domain)?.into_iter().fold(1, |a, b| a / gcd(a, b) * b) as f64,
+        _ => return Err(EvalError::Unsupported { node: name.to_string() }),
+    };
+    Ok(EvalValue::Real(value))
+}
+
+/// The real `n`-th root of negative numbers for odd `n`, the principal root otherwise.
+pub(super) fn root(base: EvalValue, degree: EvalValue) -> EvalValue {
+    if let (EvalValue::Real(x), EvalValue::Real(n)) = (base, degree) {
+        if x < 0.0 && n.fract() == 0.0 && n % 2.0 != 0.0 {
+            return EvalValue::Real(-(-x).powf(n.recip()));
+        }
+        if n == 2.0 {
+            return match x >= 0.0 {
+                true => EvalValue::Real(x.sqrt()),
+                false => EvalValue::Complex(Complex64::new(0.0, (-x).sqrt())),
+            };
+        }
+    }
+    real_or_complex(&[base, degree], |x| x[0].powf(x[1].recip()), |z| z[0].powc(z[1].inv()))
+}
+
+fn integers(values: &[f64]) -> Option<Vec<u64>> {
+    values
+        .iter()
+        .map(|v| {
+            if v.fract() == 0.0 && v.abs() < 2f64.powi(53) {
+                Some(v.abs() as u64)
+            }
+            else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+fn factorial(n: f64) -> Option<f64> {
+    if n < 0.0 || n.fract() != 0.0 {
+        return None;
+    }
+    Some((1..=n.min(171.0) as u64).fold(1.0, |acc, k| acc * k as f64))
+}
+
+fn binomial(n: f64, k: f64) -> Option<f64> {
+    if k.fract() != 0.0 || n.fract() != 0.0 {
+        return None;
+    }
+    if k < 0.0 || k > n {
+        return Some(0.0);
+    }
+    let k = k.min(n - k);
+    Some((0..k as u64).fold(1.0, |acc, i| acc * (n - i as f64) / (i + 1) as f64))
+}
+
+fn erf(x: f64) -> f64 {
+    if x.abs() >= 3.0 {
+        return x.signum() * (1.0 - erfc(x.abs()));
+    }
+    // Maclaurin series, the terms alternate and shrink fast enough below 3
+    let mut sum = x;
+    let mut term = x;
+    for n in 1..100 {
+        term *= -x * x / n as f64;
+        let delta = term / (2 * n + 1) as f64;
+        sum += delta;
+        if delta.abs() < f64::EPSILON * sum.abs() {
+            break;
+        }
+    }
+    sum * 2.0 / PI.sqrt()
+}
+
+fn erfc(x: f64) -> f64 {
+    if x < 3.0 {
+        return 1.0 - erf(x);
+    }
+    // continued fraction of Laplace
+    let mut t = x;
+    for n in (1..60).rev() {
+        t = x + (n as f64 / 2.0) / t;
+    }
+    (-x * x).exp() / (PI.sqrt() * t)
+}
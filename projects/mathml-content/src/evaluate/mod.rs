@@ -0,0 +1,226 @@
+use crate::{infer_content, ContentApply, ContentContainer, ContentNode, EvalError, InferenceConfig};
+use mathml_core::MathML;
+use num_complex::Complex64;
+use std::f64::consts::{E, PI};
+
+mod functions;
+mod value;
+
+pub use self::value::{Bindings, EvalValue};
+
+/// The largest number of terms of a sum or a product.
+const MAX_TERMS: f64 = 1e7;
+
+/// Evaluate a formula numerically.
+///
+/// The meaning of the formula is inferred with the default [`InferenceConfig`], use [`evaluate_content`] for other
+/// heuristics. Unbound `e` and `i` are Euler's number and the imaginary unit.
+pub fn evaluate(math: &MathML, bindings: &Bindings) -> Result<EvalValue, EvalError> {
+    let content = infer_content(math, &InferenceConfig::default())?;
+    evaluate_content(&content, bindings)
+}
+
+/// Evaluate a Content MathML tree numerically.
+pub fn evaluate_content(node: &ContentNode, bindings: &Bindings) -> Result<EvalValue, EvalError> {
+    Evaluator { bindings, locals: vec![] }.value(node)
+}
+
+/// The name used in errors, the head symbol of an application or the tag of an element.
+fn node_name(node: &ContentNode) -> String {
+    match node {
+        ContentNode::Number(_) => "cn".to_string(),
+        ContentNode::Identifier(name) | ContentNode::Symbol(name) => name.to_string(),
+        ContentNode::Text(_) => "cs".to_string(),
+        ContentNode::Apply(apply) => node_name(apply.get_head()),
+        ContentNode::Container(container) => container.get_tag().to_string(),
+    }
+}
+
+fn unsupported(node: &ContentNode) -> EvalError {
+    EvalError::Unsupported { node: node_name(node) }
+}
+
+fn add(lhs: EvalValue, rhs: EvalValue) -> EvalValue {
+    functions::real_or_complex(&[lhs, rhs], |x| x[0] + x[1], |z| z[0] + z[1])
+}
+
+fn multiply(lhs: EvalValue, rhs: EvalValue) -> EvalValue {
+    functions::real_or_complex(&[lhs, rhs], |x| x[0] * x[1], |z| z[0] * z[1])
+}
+
+struct Evaluator<'b> {
+    bindings: &'b Bindings,
+    /// Variables bound by sums and products, the innermost last.
+    locals: Vec<(String, EvalValue)>,
+}
+
+impl<'b> Evaluator<'b> {
+    fn lookup(&self, name: &str) -> Result<EvalValue, EvalError> {
+        if let Some((_, value)) = self.locals.iter().rev().find(|(local, _)| local == name) {
+            return Ok(*value);
+        }
+        if let Some(value) = self.bindings.get(name) {
+            return Ok(value);
+        }
+        match name {
+            "e" => Ok(EvalValue::Real(E)),
+            "i" => Ok(EvalValue::Complex(Complex64::i())),
+            _ => Err(EvalError::UnboundVariable { name: name.to_string() }),
+        }
+    }
+
+    fn value(&mut self, node: &ContentNode) -> Result<EvalValue, EvalError> {
+        match node {
+            ContentNode::Number(number) => match number.trim().parse::<f64>() {
+                Ok(value) => Ok(EvalValue::Real(value)),
+                Err(_) => Err(EvalError::InvalidNumber { number: number.to_string() }),
+            },
+            ContentNode::Identifier(name) => self.lookup(name),
+            ContentNode::Symbol(symbol) => match symbol.as_str() {
+                "pi" => Ok(EvalValue::Real(PI)),
+                "exponentiale" => Ok(EvalValue::Real(E)),
+                "imaginaryi" => Ok(EvalValue::Complex(Complex64::i())),
+                "infinity" => Ok(EvalValue::Real(f64::INFINITY)),
+                _ => Err(unsupported(node)),
+            },
+            ContentNode::Apply(apply) => self.apply(apply),
+            ContentNode::Container(container) if container.get_tag() == "piecewise" => self.piecewise(container),
+            _ => Err(unsupported(node)),
+        }
+    }
+
+    fn apply(&mut self, apply: &ContentApply) -> Result<EvalValue, EvalError> {
+        let head = match apply.get_head() {
+            ContentNode::Symbol(symbol) => symbol.as_str(),
+            // user defined functions and derivatives
+            head => return Err(unsupported(head)),
+        };
+        match head {
+            "sum" | "product" => return self.iterate(head, apply),
+            "root" => {
+                let degree = match apply.get_degree() {
+                    Some(degree) => self.value(degree)?,
+                    None => EvalValue::Real(2.0),
+                };
+                return match apply.get_arguments() {
+                    [base] => Ok(functions::root(self.value(base)?, degree)),
+                    _ => Err(EvalError::Domain { function: head.to_string() }),
+                };
+            }
+            _ if !apply.is_plain() => return Err(EvalError::Unsupported { node: head.to_string() }),
+            _ => {}
+        }
+        let args = apply.get_arguments().iter().map(|arg| self.value(arg)).collect::<Result<Vec<_>, _>>()?;
+        let value = match (head, args.as_slice()) {
+            ("plus", [first, rest @ ..]) => rest.iter().fold(*first, |acc, v| add(acc, *v)),
+            ("times", [first, rest @ ..]) => rest.iter().fold(*first, |acc, v| multiply(acc, *v)),
+            ("minus", [x]) => multiply(EvalValue::Real(-1.0), *x),
+            ("minus", [x, y]) => add(*x, multiply(EvalValue::Real(-1.0), *y)),
+            ("divide", [_, _]) => functions::real_or_complex(&args, |v| v[0] / v[1], |z| z[0] / z[1]),
+            ("power", [x, y]) => match (x, y) {
+                (EvalValue::Real(x), EvalValue::Real(y)) if y.fract() == 0.0 && y.abs() < 2f64.powi(31) => {
+                    EvalValue::Real(x.powi(*y as i32))
+                }
+                _ => functions::real_or_complex(&args, |v| v[0].powf(v[1]), |z| z[0].powc(z[1])),
+            },
+            ("plus" | "times" | "minus" | "divide" | "power", _) => {
+                return Err(EvalError::Domain { function: head.to_string() });
+            }
+            _ => return functions::apply_function(head, &args),
+        };
+        Ok(value)
+    }
+
+    /// `∑` and `∏` over integer bounds.
+    fn iterate(&mut self, head: &str, apply: &ContentApply) -> Result<EvalValue, EvalError> {
+        let unsupported = || EvalError::Unsupported { node: head.to_string() };
+        let variable = match apply.get_bound_variables() {
+            [ContentNode::Identifier(name)] => name.to_string(),
+            _ => return Err(unsupported()),
+        };
+        let (lower, upper, body) = match (apply.get_lower_limit(), apply.get_upper_limit(), apply.get_arguments()) {
+            (Some(lower), Some(upper), [body]) if apply.get_condition().is_none() => (lower, upper, body),
+            _ => return Err(unsupported()),
+        };
+        let lower = self.bound(head, lower)?;
+        let upper = self.bound(head, upper)?;
+        if upper - lower > MAX_TERMS {
+            return Err(EvalError::Domain { function: head.to_string() });
+        }
+        let mut result = EvalValue::Real(if head == "sum" { 0.0 } else { 1.0 });
+        let mut index = lower;
+        while index <= upper {
+            self.locals.push((variable.clone(), EvalValue::Real(index)));
+            let term = self.value(body);
+            self.locals.pop();
+            result = match head {
+                "sum" => add(result, term?),
+                _ => multiply(result, term?),
+            };
+            index += 1.0;
+        }
+        Ok(result)
+    }
+
+    fn bound(&mut self, head: &str, node: &ContentNode) -> Result<f64, EvalError> {
+        match self.value(node)?.as_real() {
+            Some(value) if value.is_finite() && value.fract() == 0.0 => Ok(value),
+            _ => Err(EvalError::Domain { function: head.to_string() }),
+        }
+    }
+
+    fn piecewise(&mut self, piecewise: &ContentContainer) -> Result<EvalValue, EvalError> {
+        for piece in piecewise.get_items() {
+            let (tag, items) = match piece {
+                ContentNode::Container(piece) => (piece.get_tag(), piece.get_items()),
+                _ => return Err(unsupported(piece)),
+            };
+            match (tag, items) {
+                ("piece", [value, condition]) => {
+                    if self.condition(condition)? {
+                        return self.value(value);
+                    }
+                }
+                ("otherwise", [value]) => return self.value(value),
+                _ => return Err(unsupported(piece)),
+            }
+        }
+        Err(EvalError::NoMatchingCase)
+    }
+
+    fn condition(&mut self, node: &ContentNode) -> Result<bool, EvalError> {
+        let apply = match node {
+            ContentNode::Symbol(symbol) if symbol == "true" => return Ok(true),
+            ContentNode::Symbol(symbol) if symbol == "false" => return Ok(false),
+            ContentNode::Apply(apply) if apply.is_plain() => apply,
+            _ => return Err(unsupported(node)),
+        };
+        let head = node_name(node);
+        match head.as_str() {
+            "and" => return apply.get_arguments().iter().try_fold(true, |acc, arg| Ok(acc && self.condition(arg)?)),
+            "or" => return apply.get_arguments().iter().try_fold(false, |acc, arg| Ok(acc || self.condition(arg)?)),
+            "not" => {
+                return match apply.get_arguments() {
+                    [arg] => Ok(!self.condition(arg)?),
+                    _ => Err(unsupported(node)),
+                };
+            }
+            "eq" | "neq" | "lt" | "gt" | "leq" | "geq" => {}
+            _ => return Err(unsupported(node)),
+        }
+        let values = apply.get_arguments().iter().map(|arg| self.value(arg)).collect::<Result<Vec<_>, _>>()?;
+        let mut holds = true;
+        for pair in values.windows(2) {
+            holds &= match (head.as_str(), pair[0].as_real(), pair[1].as_real()) {
+                ("eq", ..) => pair[0].as_complex() == pair[1].as_complex(),
+                ("neq", ..) => pair[0].as_complex() != pair[1].as_complex(),
+                ("lt", Some(x), Some(y)) => x < y,
+                ("gt", Some(x), Some(y)) => x > y,
+                ("leq", Some(x), Some(y)) => x <= y,
+                ("geq", Some(x), Some(y)) => x >= y,
+                _ => return Err(EvalError::Domain { function: head }),
+            };
+        }
+        Ok(holds)
+    }
+}
@@ -0,0 +1,93 @@
+use num_complex::Complex64;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+/// The result of a numeric evaluation, complex only when the imaginary part is not zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvalValue {
+    /// A real number.
+    Real(f64),
+    /// A complex number.
+    Complex(Complex64),
+}
+
+/// Values of the free variables of a formula.
+#[derive(Debug, Clone, Default)]
+pub struct Bindings {
+    variables: BTreeMap<String, EvalValue>,
+}
+
+impl EvalValue {
+    /// Gets the real value, `None` for a complex number.
+    pub fn as_real(&self) -> Option<f64> {
+        match self {
+            EvalValue::Real(v) => Some(*v),
+            EvalValue::Complex(_) => None,
+        }
+    }
+    /// Gets the value as a complex number.
+    pub fn as_complex(&self) -> Complex64 {
+        match self {
+            EvalValue::Real(v) => Complex64::new(*v, 0.0),
+            EvalValue::Complex(v) => *v,
+        }
+    }
+    /// Drop the imaginary part if it is zero.
+    pub(crate) fn normalize(value: Complex64) -> Self {
+        match value.im == 0.0 {
+            true => EvalValue::Real(value.re),
+            false => EvalValue::Complex(value),
+        }
+    }
+}
+
+impl From<f64> for EvalValue {
+    fn from(value: f64) -> Self {
+        EvalValue::Real(value)
+    }
+}
+
+impl From<Complex64> for EvalValue {
+    fn from(value: Complex64) -> Self {
+        EvalValue::normalize(value)
+    }
+}
+
+impl Display for EvalValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvalValue::Real(v) => Display::fmt(v, f),
+            EvalValue::Complex(v) => Display::fmt(v, f),
+        }
+    }
+}
+
+impl Bindings {
+    /// Create empty bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Bind a variable, subscripted variables are named as `x_1`.
+    pub fn with_variable<S, V>(mut self, name: S, value: V) -> Self
+    where
+        S: ToString,
+        V: Into<EvalValue>,
+    {
+        self.insert(name, value);
+        self
+    }
+    /// Bind a variable, subscripted variables are named as `x_1`.
+    pub fn insert<S, V>(&mut self, name: S, value: V)
+    where
+        S: ToString,
+        V: Into<EvalValue>,
+    {
+        self.variables.insert(name.to_string(), value.into());
+    }
+    /// Gets the value of a variable.
+    pub fn get(&self, name: &str) -> Option<EvalValue> {
+        self.variables.get(name).copied()
+    }
+}
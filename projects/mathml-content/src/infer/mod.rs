@@ -0,0 +1,87 @@
+use crate::{ContentNode, InferenceError};
+use mathml_core::{FontVariant, MathIdentifier, MathML};
+use std::collections::BTreeSet;
+
+mod parser;
+
+/// How an identifier directly followed by parentheses, such as `f(x)`, is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionApplication {
+    /// Always a product, unless written with the invisible function application operator `U+2061`.
+    Never,
+    /// An application for the identifiers registered by [`InferenceConfig::with_function`], a product otherwise.
+    Declared,
+    /// Always an application.
+    Always,
+}
+
+/// Heuristics used when inferring the meaning of a presentation tree.
+///
+/// Elementary functions such as `sin` or `log` are always applied, with or without parentheses.
+#[derive(Debug, Clone)]
+pub struct InferenceConfig {
+    application: FunctionApplication,
+    functions: BTreeSet<String>,
+    euler_number: bool,
+    imaginary_unit: bool,
+}
+
+impl Default for InferenceConfig {
+    fn default() -> Self {
+        Self {
+            application: FunctionApplication::Declared,
+            functions: ["f", "g", "h"].iter().map(|s| s.to_string()).collect(),
+            euler_number: false,
+            imaginary_unit: false,
+        }
+    }
+}
+
+impl InferenceConfig {
+    /// Set how `f(x)` is read, [`FunctionApplication::Declared`] by default.
+    pub fn with_application(mut self, application: FunctionApplication) -> Self {
+        self.application = application;
+        self
+    }
+    /// Register an identifier as a function name, `f`, `g` and `h` are registered by default.
+    pub fn with_function<S>(mut self, name: S) -> Self
+    where
+        S: ToString,
+    {
+        self.functions.insert(name.to_string());
+        self
+    }
+    /// Read an italic `e` as Euler's number, an upright `e` always is.
+    pub fn with_euler_number(mut self, enable: bool) -> Self {
+        self.euler_number = enable;
+        self
+    }
+    /// Read an italic `i` as the imaginary unit, an upright `i` always is.
+    pub fn with_imaginary_unit(mut self, enable: bool) -> Self {
+        self.imaginary_unit = enable;
+        self
+    }
+    fn is_function(&self, name: &str) -> bool {
+        match self.application {
+            FunctionApplication::Never => false,
+            FunctionApplication::Declared => self.functions.contains(name),
+            FunctionApplication::Always => true,
+        }
+    }
+    fn constant(&self, identifier: &MathIdentifier) -> Option<&'static str> {
+        let upright = identifier.get_variant() == FontVariant::Normal;
+        match identifier.get_identifier() {
+            "e" if upright || self.euler_number => Some("exponentiale"),
+            "i" if upright || self.imaginary_unit => Some("imaginaryi"),
+            _ => None,
+        }
+    }
+}
+
+/// Infer the [Content MathML](https://www.w3.org/TR/MathML3/chapter4.html) tree of a presentation tree.
+///
+/// Flat rows are parsed with operator precedence, fences, invisible operators, function application, big operators with
+/// their limits, and derivatives or integrals with their differentials.
+pub fn infer_content(math: &MathML, config: &InferenceConfig) -> Result<ContentNode, InferenceError> {
+    parser::infer_node(math, config)
+}
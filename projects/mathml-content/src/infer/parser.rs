@@ -0,0 +1,612 @@
+use super::InferenceConfig;
+use crate::{
+    definitions::{find, BIG_OPERATORS, CONSTANTS, FENCES, FUNCTIONS, INFIX, POSTFIX, PREFIX, RELATIONS, TIMES},
+    ContentApply, ContentContainer, ContentNode, InferenceError,
+};
+use mathml_core::{
+    LineThickness, MathElement, MathFraction, MathIdentifier, MathML, MathMultiScript, MathTable, MathUnderOver,
+};
+
+type Result<T> = std::result::Result<T, InferenceError>;
+
+pub(super) fn infer_node(node: &MathML, config: &InferenceConfig) -> Result<ContentNode> {
+    match node {
+        MathML::Root(root) => infer_row(root.get_children(), config),
+        MathML::Row(row) => infer_row(row.get_items(), config),
+        MathML::Style(style) => infer_node(style.get_base(), config),
+        _ => infer_row(std::slice::from_ref(node), config),
+    }
+}
+
+fn infer_row(items: &[MathML], config: &InferenceConfig) -> Result<ContentNode> {
+    let tokens: Vec<&MathML> = items.iter().filter(|node| !is_invisible(node)).collect();
+    if let [open, MathML::Table(table)] = tokens.as_slice() {
+        if operator_text(open) == Some("{") {
+            return piecewise(table, config);
+        }
+    }
+    infer_tokens(tokens, config)
+}
+
+fn infer_tokens(tokens: Vec<&MathML>, config: &InferenceConfig) -> Result<ContentNode> {
+    let mut parser = Parser { config, tokens, position: 0, closers: vec![] };
+    let node = parser.expression(0)?;
+    match parser.peek() {
+        Some(token) => Err(unexpected(token)),
+        None => Ok(node),
+    }
+}
+
+fn is_invisible(node: &MathML) -> bool {
+    matches!(node, MathML::Space(_) | MathML::Phantom(_) | MathML::Nothing)
+}
+
+fn operator_text(node: &MathML) -> Option<&str> {
+    match node {
+        MathML::Operator(operator) => Some(operator.get_operator()),
+        _ => None,
+    }
+}
+
+fn identifier_text(node: &MathML) -> Option<&str> {
+    match node {
+        MathML::Identifier(identifier) => Some(identifier.get_identifier()),
+        _ => None,
+    }
+}
+
+fn row_items(node: &MathML) -> Vec<&MathML> {
+    match node {
+        MathML::Row(row) => row.get_items().iter().filter(|node| !is_invisible(node)).collect(),
+        _ => vec![node],
+    }
+}
+
+fn tag_name(node: &MathML) -> String {
+    let tag = match node {
+        MathML::Root(_) => "math",
+        MathML::Row(_) => "mrow",
+        MathML::Space(v) => v.tag_name(),
+        MathML::Number(_) => "mn",
+        MathML::Identifier(_) | MathML::Function(_) => "mi",
+        MathML::Text(_) => "mtext",
+        MathML::Operator(v) => v.tag_name(),
+        MathML::MultiScripts(v) => v.tag_name(),
+        MathML::UnderOver(v) => v.tag_name(),
+        MathML::Sqrt(v) if v.get_surd().is_some() => "mroot",
+        MathML::Sqrt(_) => "msqrt",
+        MathML::Frac(_) => "mfrac",
+        MathML::Phantom(_) => "mphantom",
+        MathML::Style(_) => "mstyle",
+        MathML::Fenced(_) => "mfenced",
+        MathML::Table(_) => "mtable",
+        MathML::Undefined(_) => "merror",
+        MathML::Ampersand => "&",
+        MathML::NewLine => "\\\\",
+        MathML::Nothing => "nothing",
+    };
+    tag.to_string()
+}
+
+fn unexpected(node: &MathML) -> InferenceError {
+    match operator_text(node) {
+        Some(operator) => InferenceError::UnexpectedOperator { operator: operator.to_string() },
+        None => InferenceError::Unsupported { node: tag_name(node) },
+    }
+}
+
+fn infix(operator: &str) -> Option<(&'static str, u8, u8)> {
+    INFIX.iter().find(|(text, ..)| *text == operator).map(|(_, symbol, lhs, rhs)| (*symbol, *lhs, *rhs))
+}
+
+fn closers_of(open: &str) -> Option<&'static [&'static str]> {
+    FENCES.iter().find(|(text, _)| *text == open).map(|(_, closers)| *closers)
+}
+
+/// Operators which may start an operand right after another one, as in `2(x+1)` or `2∑i`.
+fn starts_operand(operator: &str) -> bool {
+    closers_of(operator).is_some() || find(BIG_OPERATORS, operator).is_some() || find(CONSTANTS, operator).is_some()
+}
+
+/// The symbol of `∑`, `∫`, `lim`, ... used as the base of limits.
+fn big_operator_of(node: &MathML) -> Option<&'static str> {
+    match node {
+        MathML::Operator(operator) => find(BIG_OPERATORS, operator.get_operator()),
+        MathML::Identifier(identifier) => find(BIG_OPERATORS, identifier.get_identifier()),
+        MathML::Function(function) if function.get_arguments().is_empty() => find(BIG_OPERATORS, function.get_name()),
+        _ => None,
+    }
+}
+
+/// Elementary functions, whose argument doesn't need parentheses.
+fn is_elementary(node: &MathML) -> bool {
+    match node {
+        MathML::Identifier(identifier) => find(FUNCTIONS, identifier.get_identifier()).is_some(),
+        MathML::Function(function) => function.get_arguments().is_empty(),
+        MathML::MultiScripts(scripts) => is_elementary(scripts.get_base()),
+        _ => false,
+    }
+}
+
+/// `d`, `ⅆ` or `∂`, possibly with an order as superscript.
+fn differential(node: &MathML) -> Option<(&'static str, Option<&MathML>)> {
+    let symbol = |text: &str| match text {
+        "d" | "ⅆ" => Some("diff"),
+        "∂" => Some("partialdiff"),
+        _ => None,
+    };
+    match node {
+        MathML::Identifier(_) | MathML::Operator(_) => {
+            symbol(identifier_text(node).or(operator_text(node))?).map(|s| (s, None))
+        }
+        MathML::MultiScripts(scripts) if scripts.is_super_script() => {
+            let (s, _) = differential(scripts.get_base())?;
+            Some((s, scripts.get_super_scripts().first()))
+        }
+        _ => None,
+    }
+}
+
+/// The order of a derivative written with primes, `f′` or `f''`.
+fn prime_order(node: &MathML) -> Option<usize> {
+    let text = identifier_text(node).or(operator_text(node))?;
+    let order = text.chars().map(|c| match c {
+        '′' | '\'' => Some(1),
+        '″' => Some(2),
+        '‴' => Some(3),
+        _ => None,
+    });
+    order.sum::<Option<usize>>().filter(|n| *n > 0)
+}
+
+/// Merge associative and chained operators into one application.
+fn combine(symbol: &str, lhs: ContentNode, rhs: ContentNode) -> ContentNode {
+    let flat = matches!(symbol, "plus" | "times" | "and" | "or" | "union" | "intersect") || RELATIONS.contains(&symbol);
+    let lhs = match lhs {
+        ContentNode::Apply(mut apply) if flat && apply.get_head().is_symbol(symbol) && apply.is_plain() => {
+            apply.mut_arguments().push(rhs);
+            return ContentNode::Apply(apply);
+        }
+        lhs => lhs,
+    };
+    if let ContentNode::Apply(apply) = &lhs {
+        // `a < b ≤ c` means `a < b ∧ b ≤ c`
+        let chained = RELATIONS.iter().any(|relation| apply.get_head().is_symbol(relation));
+        if let (true, true, Some(last)) = (chained, RELATIONS.contains(&symbol), apply.get_arguments().last()) {
+            let next = ContentNode::apply(symbol, vec![last.clone(), rhs]);
+            return ContentNode::apply("and", vec![lhs, next]);
+        }
+    }
+    ContentNode::apply(symbol, vec![lhs, rhs])
+}
+
+fn fence(open: &str, close: &str, mut items: Vec<ContentNode>) -> Result<ContentNode> {
+    let node = match (open, close, items.len()) {
+        ("(", ")", 1) | ("[", "]", 1) => items.remove(0),
+        ("(", ")", _) => ContentContainer::new("vector", items).into(),
+        ("[", "]", 2) => ContentContainer::new("interval", items).with_attribute("closure", "closed").into(),
+        ("(", "]", 2) => ContentContainer::new("interval", items).with_attribute("closure", "open-closed").into(),
+        ("[", ")", 2) => ContentContainer::new("interval", items).with_attribute("closure", "closed-open").into(),
+        ("[", "]", _) => ContentContainer::new("list", items).into(),
+        ("{", "}", _) => ContentContainer::new("set", items).into(),
+        ("|", "|", 1) => match &items[0] {
+            ContentNode::Container(matrix) if matrix.get_tag() == "matrix" => ContentNode::apply("determinant", items),
+            _ => ContentNode::apply("abs", items),
+        },
+        ("‖", "‖", 1) => ContentNode::apply("norm", items),
+        ("⌊", "⌋", 1) => ContentNode::apply("floor", items),
+        ("⌈", "⌉", 1) => ContentNode::apply("ceiling", items),
+        ("⟨", "⟩", 2) => ContentNode::apply("scalarproduct", items),
+        ("⟨", "⟩", _) => ContentContainer::new("vector", items).into(),
+        _ => return Err(InferenceError::UnbalancedFence { open: open.to_string() }),
+    };
+    Ok(node)
+}
+
+fn matrix(table: &MathTable, config: &InferenceConfig) -> Result<ContentNode> {
+    let mut rows = vec![];
+    for cells in table.get_rows() {
+        let cells = cells.iter().map(|cell| infer_node(cell, config)).collect::<Result<Vec<_>>>()?;
+        rows.push(ContentContainer::new("matrixrow", cells).into());
+    }
+    Ok(ContentContainer::new("matrix", rows).into())
+}
+
+/// `\begin{cases} x & x > 0 \\ -x & \text{otherwise} \end{cases}`
+fn piecewise(table: &MathTable, config: &InferenceConfig) -> Result<ContentNode> {
+    let mut pieces = vec![];
+    for cells in table.get_rows() {
+        let (value, condition) = match cells.as_slice() {
+            [value] => (value, None),
+            [value, condition] => (value, Some(condition)),
+            _ => return Err(InferenceError::Unsupported { node: "mtable".to_string() }),
+        };
+        let value = strip(row_items(value));
+        let condition = condition.map(|condition| strip(row_items(condition))).filter(|condition| !condition.is_empty());
+        let value = infer_tokens(value, config)?;
+        let piece = match condition {
+            Some(condition) => ContentContainer::new("piece", vec![value, infer_tokens(condition, config)?]),
+            None => ContentContainer::new("otherwise", vec![value]),
+        };
+        pieces.push(piece.into());
+    }
+    Ok(ContentContainer::new("piecewise", pieces).into())
+}
+
+/// Drop the words such as `if` or `otherwise` and the commas around a piece.
+fn strip(items: Vec<&MathML>) -> Vec<&MathML> {
+    let mut items: Vec<&MathML> = items.into_iter().filter(|node| !matches!(node, MathML::Text(_))).collect();
+    while items.last().and_then(|node| operator_text(node)) == Some(",") {
+        items.pop();
+    }
+    while items.first().and_then(|node| operator_text(node)) == Some(",") {
+        items.remove(0);
+    }
+    items
+}
+
+struct Parser<'a, 'c> {
+    config: &'c InferenceConfig,
+    tokens: Vec<&'a MathML>,
+    position: usize,
+    closers: Vec<&'static [&'static str]>,
+}
+
+impl<'a, 'c> Parser<'a, 'c> {
+    fn peek(&self) -> Option<&'a MathML> {
+        self.tokens.get(self.position).copied()
+    }
+    fn next(&mut self) -> Option<&'a MathML> {
+        let token = self.peek()?;
+        self.position += 1;
+        Some(token)
+    }
+    fn peek_operator(&self) -> Option<&'a str> {
+        operator_text(self.peek()?)
+    }
+    fn is_closer(&self, operator: &str) -> bool {
+        self.closers.iter().any(|closers| closers.contains(&operator))
+    }
+    fn infer(&self, node: &MathML) -> Result<ContentNode> {
+        infer_node(node, self.config)
+    }
+
+    fn expression(&mut self, min: u8) -> Result<ContentNode> {
+        let mut lhs = self.prefix()?;
+        while let Some(token) = self.peek() {
+            let operator = operator_text(token);
+            if let Some(operator) = operator {
+                if self.is_closer(operator) || matches!(operator, "," | "\u{2063}") {
+                    break;
+                }
+                if operator == "!" {
+                    if POSTFIX < min {
+                        break;
+                    }
+                    self.position += 1;
+                    lhs = ContentNode::apply("factorial", vec![lhs]);
+                    continue;
+                }
+                if let Some((symbol, left, right)) = infix(operator) {
+                    if left < min {
+                        break;
+                    }
+                    self.position += 1;
+                    let rhs = self.expression(right)?;
+                    lhs = combine(symbol, lhs, rhs);
+                    continue;
+                }
+                if !starts_operand(operator) {
+                    return Err(unexpected(token));
+                }
+            }
+            // juxtaposition is an invisible times
+            if TIMES < min {
+                break;
+            }
+            let rhs = self.expression(TIMES + 1)?;
+            lhs = combine("times", lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn prefix(&mut self) -> Result<ContentNode> {
+        let token = self.next().ok_or(InferenceError::MissingOperand)?;
+        let operator = match operator_text(token) {
+            Some(operator) => operator,
+            None => return self.atom(token),
+        };
+        match operator {
+            "−" | "-" => Ok(ContentNode::apply("minus", vec![self.expression(PREFIX)?])),
+            "+" => self.expression(PREFIX),
+            "±" => Ok(ContentNode::apply("plusminus", vec![self.expression(PREFIX)?])),
+            "¬" => Ok(ContentNode::apply("not", vec![self.expression(PREFIX)?])),
+            _ => {
+                if let Some(closers) = closers_of(operator) {
+                    let (items, close) = self.items(operator, closers)?;
+                    return fence(operator, close, items);
+                }
+                if let Some(symbol) = find(BIG_OPERATORS, operator) {
+                    return self.big_operator(symbol, None, None);
+                }
+                match find(CONSTANTS, operator) {
+                    Some(symbol) => Ok(ContentNode::symbol(symbol)),
+                    None => Err(unexpected(token)),
+                }
+            }
+        }
+    }
+
+    /// Parse comma separated items up to the closing fence, the opening fence is already consumed.
+    fn items(&mut self, open: &str, closers: &'static [&'static str]) -> Result<(Vec<ContentNode>, &'static str)> {
+        self.closers.push(closers);
+        let mut items = vec![];
+        let close = loop {
+            items.push(self.expression(0)?);
+            match self.next().and_then(operator_text) {
+                Some("," | "\u{2063}") => continue,
+                Some(operator) => match closers.iter().find(|close| **close == operator) {
+                    Some(close) => break *close,
+                    None => return Err(InferenceError::UnbalancedFence { open: open.to_string() }),
+                },
+                None => return Err(InferenceError::UnbalancedFence { open: open.to_string() }),
+            }
+        };
+        self.closers.pop();
+        Ok((items, close))
+    }
+
+    fn atom(&mut self, node: &'a MathML) -> Result<ContentNode> {
+        match node {
+            MathML::Identifier(identifier) => self.identifier(identifier),
+            MathML::Function(function) => {
+                if let Some(symbol) = big_operator_of(node) {
+                    return self.big_operator(symbol, None, None);
+                }
+                let head = match find(FUNCTIONS, function.get_name()) {
+                    Some(symbol) => ContentNode::symbol(symbol),
+                    None => ContentNode::identifier(function.get_name()),
+                };
+                match function.get_arguments() {
+                    [] => self.application(head, true),
+                    [MathML::Fenced(fenced)] if fenced.get_open() == '(' => {
+                        let arguments = fenced.get_items().iter().map(|item| self.infer(item));
+                        Ok(ContentApply::new(head, arguments.collect::<Result<Vec<_>>>()?).into())
+                    }
+                    arguments => {
+                        let argument = infer_row(arguments, self.config)?;
+                        Ok(ContentApply::new(head, vec![argument]).into())
+                    }
+                }
+            }
+            MathML::MultiScripts(scripts) => self.scripts(scripts),
+            MathML::UnderOver(under_over) => self.under_over(node, under_over),
+            MathML::Frac(fraction) => self.fraction(fraction),
+            MathML::Sqrt(sqrt) => {
+                let base = self.infer(sqrt.get_base())?;
+                match sqrt.get_surd() {
+                    Some(surd) => {
+                        Ok(ContentApply::new(ContentNode::symbol("root"), vec![base]).with_degree(self.infer(surd)?).into())
+                    }
+                    None => Ok(ContentNode::apply("root", vec![base])),
+                }
+            }
+            MathML::Fenced(fenced) => {
+                let items = fenced.get_items().iter().map(|item| self.infer(item)).collect::<Result<Vec<_>>>()?;
+                fence(&fenced.get_open().to_string(), &fenced.get_close().to_string(), items)
+            }
+            MathML::Number(number) => Ok(ContentNode::number(number.get_number())),
+            MathML::Text(text) => Ok(ContentNode::text(text.get_text())),
+            MathML::Table(table) => matrix(table, self.config),
+            MathML::Root(_) | MathML::Row(_) | MathML::Style(_) => self.infer(node),
+            _ => Err(unexpected(node)),
+        }
+    }
+
+    fn identifier(&mut self, identifier: &MathIdentifier) -> Result<ContentNode> {
+        let name = identifier.get_identifier();
+        if let Some(symbol) = find(BIG_OPERATORS, name) {
+            return self.big_operator(symbol, None, None);
+        }
+        if let Some(symbol) = find(FUNCTIONS, name) {
+            return self.application(ContentNode::symbol(symbol), true);
+        }
+        if self.peek_operator() == Some("\u{2061}") || (self.config.is_function(name) && self.next_is_parenthesis()) {
+            return self.application(ContentNode::identifier(name), false);
+        }
+        if let Some(symbol) = find(CONSTANTS, name).or_else(|| self.config.constant(identifier)) {
+            return Ok(ContentNode::symbol(symbol));
+        }
+        Ok(ContentNode::identifier(name))
+    }
+
+    fn next_is_parenthesis(&self) -> bool {
+        match self.peek() {
+            Some(MathML::Fenced(fenced)) => fenced.get_open() == '(',
+            Some(token) => operator_text(token) == Some("("),
+            None => false,
+        }
+    }
+
+    /// Apply `head` to the parenthesized arguments, or to the next operand if `juxtaposed` is allowed.
+    fn application(&mut self, head: ContentNode, juxtaposed: bool) -> Result<ContentNode> {
+        if self.peek_operator() == Some("\u{2061}") {
+            self.position += 1;
+        }
+        let arguments = match self.peek() {
+            Some(MathML::Fenced(fenced)) if fenced.get_open() == '(' => {
+                self.position += 1;
+                fenced.get_items().iter().map(|item| self.infer(item)).collect::<Result<Vec<_>>>()?
+            }
+            Some(token) if operator_text(token) == Some("(") => {
+                self.position += 1;
+                self.items("(", &[")"])?.0
+            }
+            Some(token) if juxtaposed && operator_text(token).is_none_or(|s| starts_operand(s) || s == "−" || s == "-") => {
+                vec![self.argument()?]
+            }
+            // a bare function name
+            _ => return Ok(head),
+        };
+        Ok(ContentApply::new(head, arguments).into())
+    }
+
+    /// The argument of `sin 2x`, a product which stops before the next function as in `sin x cos y`.
+    fn argument(&mut self) -> Result<ContentNode> {
+        let mut node = self.prefix()?;
+        while let Some(token) = self.peek() {
+            if operator_text(token).is_some() || is_elementary(token) {
+                break;
+            }
+            let rhs = self.prefix()?;
+            node = combine("times", node, rhs);
+        }
+        Ok(node)
+    }
+
+    fn scripts(&mut self, scripts: &'a MathMultiScript) -> Result<ContentNode> {
+        if !scripts.get_pre_sub_scripts().is_empty() || !scripts.get_pre_super_scripts().is_empty() {
+            return Err(InferenceError::Unsupported { node: "mmultiscripts".to_string() });
+        }
+        let (sub, sup) = match (scripts.get_sub_scripts(), scripts.get_super_scripts()) {
+            ([], []) => (None, None),
+            ([sub], []) => (Some(sub), None),
+            ([], [sup]) => (None, Some(sup)),
+            ([sub], [sup]) => (Some(sub), Some(sup)),
+            _ => return Err(InferenceError::Unsupported { node: "mmultiscripts".to_string() }),
+        };
+        let base = scripts.get_base();
+        if let Some(symbol) = big_operator_of(base) {
+            return self.big_operator(symbol, sub, sup);
+        }
+        if let Some(order) = sup.and_then(prime_order) {
+            let mut derivative = ContentApply::new(ContentNode::symbol("diff"), vec![self.infer(base)?]);
+            if order > 1 {
+                derivative = derivative.with_degree(ContentNode::number(order));
+            }
+            return self.application(derivative.into(), false);
+        }
+        // `\sin^2 x` is the square of `\sin x`
+        if let (true, None, Some(sup)) = (is_elementary(base), sub, sup) {
+            let applied = self.atom(base)?;
+            return Ok(ContentNode::apply("power", vec![applied, self.infer(sup)?]));
+        }
+        let indexed = match (base, sub) {
+            (MathML::Identifier(name), Some(MathML::Identifier(index))) => {
+                ContentNode::identifier(format!("{}_{}", name.get_identifier(), index.get_identifier()))
+            }
+            (MathML::Identifier(name), Some(MathML::Number(index))) => {
+                ContentNode::identifier(format!("{}_{}", name.get_identifier(), index.get_number()))
+            }
+            (_, Some(sub)) => ContentNode::apply("selector", vec![self.infer(base)?, self.infer(sub)?]),
+            (_, None) => self.infer(base)?,
+        };
+        // `f_n(x)`
+        let indexed = match (identifier_text(base), &indexed) {
+            (Some(name), ContentNode::Identifier(_)) if self.config.is_function(name) && self.next_is_parenthesis() => {
+                self.application(indexed, false)?
+            }
+            _ => indexed,
+        };
+        match sup {
+            Some(sup) => Ok(ContentNode::apply("power", vec![indexed, self.infer(sup)?])),
+            None => Ok(indexed),
+        }
+    }
+
+    fn under_over(&mut self, node: &MathML, under_over: &'a MathUnderOver) -> Result<ContentNode> {
+        match big_operator_of(under_over.get_base()) {
+            Some(symbol) => self.big_operator(symbol, under_over.get_under(), under_over.get_over()),
+            None => Err(InferenceError::Unsupported { node: tag_name(node) }),
+        }
+    }
+
+    /// `∑`, `∏`, `∫` or `lim` with optional limits, followed by their body.
+    fn big_operator(&mut self, symbol: &str, lower: Option<&MathML>, upper: Option<&MathML>) -> Result<ContentNode> {
+        let mut apply = ContentApply::new(ContentNode::symbol(symbol), vec![]);
+        if let Some(upper) = upper {
+            apply = apply.with_upper_limit(self.infer(upper)?);
+        }
+        if symbol == "int" {
+            if let Some(lower) = lower {
+                apply = apply.with_lower_limit(self.infer(lower)?);
+            }
+            let body = match self.find_differential() {
+                Some(index) => {
+                    let body = self.tokens[self.position..index].to_vec();
+                    let variable = self.infer(self.tokens[index + 1])?;
+                    self.position = index + 2;
+                    apply = apply.with_bound_variable(variable);
+                    match body.is_empty() {
+                        true => ContentNode::number(1),
+                        false => infer_tokens(body, self.config)?,
+                    }
+                }
+                None => self.expression(TIMES)?,
+            };
+            apply.mut_arguments().push(body);
+            return Ok(apply.into());
+        }
+        if let Some(lower) = lower {
+            let lower = self.infer(lower)?;
+            // `i = 1` or `x → 0` bind the variable, anything else is a condition such as `x ∈ S`
+            let binding = ["eq", "tendsto"].iter().find_map(|s| lower.as_apply_of(s)).map(|s| s.get_arguments());
+            apply = match binding {
+                Some([variable @ ContentNode::Identifier(_), limit]) => {
+                    apply.with_bound_variable(variable.clone()).with_lower_limit(limit.clone())
+                }
+                _ => apply.with_condition(lower),
+            };
+        }
+        apply.mut_arguments().push(self.expression(TIMES)?);
+        Ok(apply.into())
+    }
+
+    /// Find `d x` after the integrand, at the current level.
+    fn find_differential(&self) -> Option<usize> {
+        (self.position..self.tokens.len().saturating_sub(1)).find(|i| {
+            matches!(differential(self.tokens[*i]), Some(("diff", None))) && identifier_text(self.tokens[i + 1]).is_some()
+        })
+    }
+
+    fn fraction(&mut self, fraction: &'a MathFraction) -> Result<ContentNode> {
+        if let Some(derivative) = self.derivative(fraction)? {
+            return Ok(derivative);
+        }
+        let numerator = self.infer(fraction.get_numerator())?;
+        let denominator = self.infer(fraction.get_denominator())?;
+        match fraction.get_thickness() {
+            LineThickness::Length(0) => Ok(ContentNode::apply("binomial", vec![numerator, denominator])),
+            _ => Ok(ContentNode::apply("divide", vec![numerator, denominator])),
+        }
+    }
+
+    /// Leibniz notation, `\frac{dy}{dx}`, `\frac{d^2 y}{dx^2}` or `\frac{∂}{∂x} f`.
+    fn derivative(&mut self, fraction: &'a MathFraction) -> Result<Option<ContentNode>> {
+        let numerator = row_items(fraction.get_numerator());
+        let denominator = row_items(fraction.get_denominator());
+        let (symbol, degree) = match numerator.first().and_then(|node| differential(node)) {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+        let variable = match denominator.as_slice() {
+            [d, variable] if differential(d).is_some_and(|(s, _)| s == symbol) => *variable,
+            _ => return Ok(None),
+        };
+        let variable = match variable {
+            MathML::MultiScripts(scripts) if scripts.is_super_script() => scripts.get_base(),
+            _ => variable,
+        };
+        let mut apply = ContentApply::new(ContentNode::symbol(symbol), vec![]).with_bound_variable(self.infer(variable)?);
+        if let Some(degree) = degree {
+            apply = apply.with_degree(self.infer(degree)?);
+        }
+        let body = match numerator.len() {
+            // operator form, applied to what follows
+            1 => self.expression(TIMES)?,
+            _ => infer_tokens(numerator[1..].to_vec(), self.config)?,
+        };
+        apply.mut_arguments().push(body);
+        Ok(Some(apply.into()))
+    }
+}
@@ -0,0 +1,20 @@
+#![deny(missing_debug_implementations, missing_copy_implementations)]
+#![warn(missing_docs, rustdoc::missing_crate_level_docs)]
+#![doc = include_str!("../readme.md")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/oovm/shape-rs/dev/projects/images/Trapezohedron.svg")]
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/oovm/shape-rs/dev/projects/images/Trapezohedron.svg")]
+
+mod ast;
+mod cas;
+mod definitions;
+mod errors;
+mod evaluate;
+mod infer;
+
+pub use crate::{
+    ast::{ContentApply, ContentContainer, ContentNode},
+    cas::{export_cas, export_cas_content, CasSyntax},
+    errors::{EvalError, ExportError, InferenceError},
+    evaluate::{evaluate, evaluate_content, Bindings, EvalValue},
+    infer::{infer_content, FunctionApplication, InferenceConfig},
+};
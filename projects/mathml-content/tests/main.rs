@@ -0,0 +1,269 @@
+use mathml_content::{
+    evaluate, export_cas, infer_content, Bindings, CasSyntax, EvalError, EvalValue, ExportError, FunctionApplication,
+    InferenceConfig, InferenceError,
+};
+use mathml_core::{
+    helpers::{bmatrix, cases, vmatrix},
+    MathFenced, MathFunction, MathIdentifier, MathML, MathMultiScript, MathOperator, MathRow, MathSpace, MathSqrt,
+    MathUnderOver,
+};
+use mathml_latex::{parse_latex, LaTeXEngine};
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+fn row(items: Vec<MathML>) -> MathML {
+    MathRow::new(items).into()
+}
+
+fn op(s: &str) -> MathML {
+    MathML::operation(s)
+}
+
+fn id(s: &str) -> MathML {
+    MathML::identifier(s)
+}
+
+#[track_caller]
+fn assert_infer(math: MathML, content: &str) {
+    assert_eq!(infer_content(&math, &InferenceConfig::default()).unwrap().to_string(), content);
+}
+
+#[test]
+fn precedence() {
+    // -2(x+1)^2 - y! = 0
+    let math = row(vec![
+        op("−"),
+        MathML::number(2),
+        MathMultiScript::super_script(
+            MathFenced::parentheses(vec![row(vec![id("x"), op("+"), MathML::number(1)])]).into(),
+            2.into(),
+        )
+        .into(),
+        op("−"),
+        id("y"),
+        op("!"),
+        op("="),
+        MathML::number(0),
+    ]);
+    assert_infer(
+        math,
+        "<apply><eq/><apply><minus/><apply><minus/><apply><times/><cn>2</cn><apply><power/><apply><plus/><ci>x</ci><cn>1</cn></apply><cn>2</cn></apply></apply></apply><apply><factorial/><ci>y</ci></apply></apply><cn>0</cn></apply>",
+    );
+    // 0 < x ≤ 1
+    assert_infer(
+        row(vec![MathML::number(0), op("<"), id("x"), op("≤"), MathML::number(1)]),
+        "<apply><and/><apply><lt/><cn>0</cn><ci>x</ci></apply><apply><leq/><ci>x</ci><cn>1</cn></apply></apply>",
+    );
+}
+
+#[test]
+fn application() {
+    let math = row(vec![id("f"), op("("), id("x"), op(")"), op("+"), id("a"), op("("), id("b"), op(")")]);
+    assert_infer(
+        math.clone(),
+        "<apply><plus/><apply><ci>f</ci><ci>x</ci></apply><apply><times/><ci>a</ci><ci>b</ci></apply></apply>",
+    );
+    let never = InferenceConfig::default().with_application(FunctionApplication::Never);
+    assert_eq!(
+        infer_content(&math, &never).unwrap().to_string(),
+        "<apply><plus/><apply><times/><ci>f</ci><ci>x</ci></apply><apply><times/><ci>a</ci><ci>b</ci></apply></apply>"
+    );
+    // sin 2x cos x
+    let math = row(vec![
+        MathFunction::new("sin", vec![]).into(),
+        MathML::number(2),
+        id("x"),
+        MathIdentifier::normal("cos").into(),
+        id("x"),
+    ]);
+    assert_infer(
+        math,
+        "<apply><times/><apply><sin/><apply><times/><cn>2</cn><ci>x</ci></apply></apply><apply><cos/><ci>x</ci></apply></apply>",
+    );
+}
+
+#[test]
+fn big_operators() {
+    let sum = MathUnderOver::under_over(
+        MathOperator::new("∑").mark_large_operator().into(),
+        row(vec![id("i"), op("="), MathML::number(1)]),
+        id("n"),
+    );
+    let math = row(vec![sum.into(), MathMultiScript::sub_script(id("a"), id("i")).into(), op("+"), MathML::number(1)]);
+    assert_infer(
+        math,
+        "<apply><plus/><apply><sum/><bvar><ci>i</ci></bvar><lowlimit><cn>1</cn></lowlimit><uplimit><ci>n</ci></uplimit><ci>a_i</ci></apply><cn>1</cn></apply>",
+    );
+    let integral = MathMultiScript::sub_super_script(op("∫"), MathML::number(0), id("π"));
+    let math = row(vec![
+        integral.into(),
+        MathFunction::new("sin", vec![]).into(),
+        id("x"),
+        MathSpace::new(0.167).into(),
+        MathIdentifier::normal("d").into(),
+        id("x"),
+    ]);
+    assert_infer(
+        math,
+        "<apply><int/><bvar><ci>x</ci></bvar><lowlimit><cn>0</cn></lowlimit><uplimit><pi/></uplimit><apply><sin/><ci>x</ci></apply></apply>",
+    );
+}
+
+#[test]
+fn derivatives() {
+    let d = || MathIdentifier::normal("d").into();
+    let math = row(vec![
+        MathML::fraction(
+            MathMultiScript::super_script(d(), 2.into()),
+            row(vec![d(), MathMultiScript::super_script(id("x"), 2.into()).into()]),
+        ),
+        id("y"),
+    ]);
+    assert_infer(math, "<apply><diff/><bvar><ci>x</ci><degree><cn>2</cn></degree></bvar><ci>y</ci></apply>");
+    let math = row(vec![MathMultiScript::super_script(id("f"), op("′")).into(), op("("), id("x"), op(")")]);
+    assert_infer(math, "<apply><apply><diff/><ci>f</ci></apply><ci>x</ci></apply>");
+}
+
+#[test]
+fn structures() {
+    let piecewise = cases(vec![
+        id("x"),
+        MathML::Ampersand,
+        row(vec![id("x"), op(">"), MathML::number(0)]),
+        MathML::NewLine,
+        row(vec![op("−"), id("x")]),
+        MathML::Ampersand,
+        MathML::text("otherwise"),
+    ]);
+    assert_infer(
+        piecewise,
+        "<piecewise><piece><ci>x</ci><apply><gt/><ci>x</ci><cn>0</cn></apply></piece><otherwise><apply><minus/><ci>x</ci></apply></otherwise></piecewise>",
+    );
+    let determinant = vmatrix(vec![id("a"), MathML::Ampersand, id("b")]);
+    assert_infer(determinant, "<apply><determinant/><matrix><matrixrow><ci>a</ci><ci>b</ci></matrixrow></matrix></apply>");
+    assert_infer(
+        row(vec![op("["), MathML::number(0), op(","), MathML::number(1), op(")")]),
+        r#"<interval closure="closed-open"><cn>0</cn><cn>1</cn></interval>"#,
+    );
+}
+
+#[test]
+fn errors() {
+    let config = InferenceConfig::default();
+    assert_eq!(infer_content(&row(vec![id("a"), op("+")]), &config), Err(InferenceError::MissingOperand));
+    assert_eq!(
+        infer_content(&row(vec![op("("), id("a")]), &config),
+        Err(InferenceError::UnbalancedFence { open: "(".to_string() })
+    );
+    let isotope = MathMultiScript::new(id("C"), vec![MathML::number(14)], vec![], vec![], vec![]);
+    assert_eq!(infer_content(&isotope.into(), &config), Err(InferenceError::Unsupported { node: "mmultiscripts".to_string() }));
+}
+
+#[test]
+fn evaluation() {
+    let bindings = Bindings::new().with_variable("x", 2.0).with_variable("n", 4.0);
+    // √(x + 7) ⋅ 3 / 2
+    let math = row(vec![
+        MathSqrt::sqrt(row(vec![id("x"), op("+"), MathML::number(7)])).into(),
+        op("⋅"),
+        MathML::fraction(MathML::number(3), MathML::number(2)),
+    ]);
+    assert_eq!(evaluate(&math, &bindings), Ok(EvalValue::Real(4.5)));
+    // ∑_{i=1}^{n} i^2
+    let sum = MathUnderOver::under_over(
+        MathOperator::new("∑").mark_large_operator().into(),
+        row(vec![id("i"), op("="), MathML::number(1)]),
+        id("n"),
+    );
+    let math = row(vec![sum.into(), MathMultiScript::super_script(id("i"), 2.into()).into()]);
+    assert_eq!(evaluate(&math, &bindings), Ok(EvalValue::Real(30.0)));
+    // sin(π/2) - x
+    let math = row(vec![
+        MathFunction::new("sin", vec![]).into(),
+        MathFenced::parentheses(vec![MathML::fraction(id("π"), MathML::number(2))]).into(),
+        op("−"),
+        id("x"),
+    ]);
+    assert_eq!(evaluate(&math, &bindings), Ok(EvalValue::Real(-1.0)));
+    let piecewise = cases(vec![
+        id("x"),
+        MathML::Ampersand,
+        row(vec![id("x"), op(">"), MathML::number(0)]),
+        MathML::NewLine,
+        row(vec![op("−"), id("x")]),
+        MathML::Ampersand,
+        MathML::text("otherwise"),
+    ]);
+    assert_eq!(evaluate(&piecewise, &Bindings::new().with_variable("x", -3.0)), Ok(EvalValue::Real(3.0)));
+    // the same function written in LaTeX
+    let context = LaTeXEngine::builtin();
+    let piecewise = parse_latex(r"\begin{cases} -x & x<0 \\ x & x\ge 0\end{cases}").unwrap().as_mathml(&context);
+    assert_eq!(evaluate(&piecewise, &Bindings::new().with_variable("x", -3.0)), Ok(EvalValue::Real(3.0)));
+    assert_eq!(evaluate(&piecewise, &Bindings::new().with_variable("x", 2.0)), Ok(EvalValue::Real(2.0)));
+    // √(−4) = 2i
+    let math = MathSqrt::sqrt(row(vec![op("−"), MathML::number(4)])).into();
+    assert_eq!(evaluate(&math, &bindings).unwrap().as_complex(), num_complex::Complex64::new(0.0, 2.0));
+    assert_eq!(evaluate(&id("y"), &bindings), Err(EvalError::UnboundVariable { name: "y".to_string() }));
+    let integral = row(vec![op("∫"), id("x"), MathIdentifier::normal("d").into(), id("x")]);
+    assert_eq!(evaluate(&integral, &bindings), Err(EvalError::Unsupported { node: "int".to_string() }));
+}
+
+#[track_caller]
+fn assert_cas(math: &MathML, expected: [&str; 4]) {
+    let syntaxes = [CasSyntax::SymPy, CasSyntax::WolframFullForm, CasSyntax::WolframInputForm, CasSyntax::Maxima];
+    for (syntax, expected) in syntaxes.into_iter().zip(expected) {
+        assert_eq!(export_cas(math, syntax).unwrap(), expected);
+    }
+}
+
+#[test]
+fn cas_export() {
+    // 1/2 x^2 - √(x+1)
+    let math = row(vec![
+        MathML::fraction(MathML::number(1), MathML::number(2)),
+        MathMultiScript::super_script(id("x"), 2.into()).into(),
+        op("−"),
+        MathSqrt::sqrt(row(vec![id("x"), op("+"), MathML::number(1)])).into(),
+    ]);
+    assert_cas(
+        &math,
+        [
+            "Rational(1, 2)*x**2 - sqrt(x + 1)",
+            "Plus[Times[Rational[1, 2], Power[x, 2]], Times[-1, Power[Plus[x, 1], Rational[1, 2]]]]",
+            "1/2*x^2 - Sqrt[x + 1]",
+            "1/2*x^2 - sqrt(x + 1)",
+        ],
+    );
+    // ∑_{i=1}^{n} sin α_i
+    let sum = MathUnderOver::under_over(
+        MathOperator::new("∑").mark_large_operator().into(),
+        row(vec![id("i"), op("="), MathML::number(1)]),
+        id("n"),
+    );
+    let math =
+        row(vec![sum.into(), MathFunction::new("sin", vec![]).into(), MathMultiScript::sub_script(id("α"), id("i")).into()]);
+    assert_cas(
+        &math,
+        [
+            "Sum(sin(Symbol('alpha_i')), (i, 1, n))",
+            "Sum[Sin[Subscript[\\[Alpha], i]], List[i, 1, n]]",
+            "Sum[Sin[Subscript[\\[Alpha], i]], {i, 1, n}]",
+            "sum(sin(alpha[i]), i, 1, n)",
+        ],
+    );
+    let matrix =
+        bmatrix(vec![id("a"), MathML::Ampersand, id("b"), MathML::NewLine, MathML::number(0), MathML::Ampersand, id("c")]);
+    assert_cas(
+        &matrix,
+        ["Matrix([[a, b], [0, c]])", "List[List[a, b], List[0, c]]", "{{a, b}, {0, c}}", "matrix([a, b], [0, c])"],
+    );
+    let interval = row(vec![op("("), MathML::number(0), op(","), MathML::number(1), op("]")]);
+    assert_eq!(export_cas(&interval, CasSyntax::SymPy).unwrap(), "Interval.Lopen(0, 1)");
+    assert_eq!(
+        export_cas(&interval, CasSyntax::Maxima),
+        Err(ExportError::Unsupported { node: "interval open-closed".to_string(), syntax: CasSyntax::Maxima })
+    );
+}
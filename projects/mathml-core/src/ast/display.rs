@@ -21,9 +21,7 @@ impl Display for MathML {
             MathML::Style(v) => Display::fmt(v, f),
             MathML::Ampersand => Ok(()),
             MathML::NewLine => Ok(()),
-            MathML::Undefined(_) => {
-                todo!()
-            }
+            MathML::Undefined(v) => Display::fmt(v, f),
             MathML::Nothing => Ok(()),
         }
     }
@@ -37,6 +37,14 @@ impl MathRoot {
     pub fn with_namespace(self) -> Self {
         self.with_attribute("xmlns", "http://www.w3.org/1998/Math/MathML")
     }
+    /// Gets the children of the root element.
+    pub fn get_children(&self) -> &[MathML] {
+        &self.children
+    }
+    /// Checks whether the root element is rendered in display (block) mode.
+    pub fn is_display_style(&self) -> bool {
+        self.attributes.get("display").map(|s| s.as_str()) == Some("block")
+    }
 }
 
 impl MathRow {
@@ -62,6 +70,10 @@ impl MathRow {
     pub fn mut_items(&mut self) -> &mut Vec<MathML> {
         &mut self.children
     }
+    /// Checks whether the row is an explicit group, such as `{a + b}` in LaTeX.
+    pub fn is_grouped(&self) -> bool {
+        self.grouped
+    }
 }
 
 // noinspection SpellCheckingInspection
@@ -95,6 +107,10 @@ impl MathStyle {
     {
         Self { base: base.into(), attributes: Default::default() }.with_attribute("displaystyle", "false")
     }
+    /// Gets the styled element.
+    pub fn get_base(&self) -> &MathML {
+        &self.base
+    }
 }
 impl MathPhantom {
     /// Create a simple math space without any attributes, the unit is `rem`.
@@ -120,6 +136,10 @@ impl MathFunction {
     {
         Self { name: name.to_string(), body: body.into_iter().collect() }
     }
+    /// Gets the name of the function.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
     /// Create a simple math space without any attributes, the unit is `rem`.
     pub fn add_argument(&mut self, argument: MathML) {
         self.body.push(argument);
@@ -155,6 +175,75 @@ impl MathTable {
     where
         I: IntoIterator<Item = MathML>,
     {
-        Self { stream: stream.into_iter().collect(), attributes: BTreeMap::new() }
+        Self {
+            stream: stream.into_iter().collect(),
+            labels: BTreeMap::new(),
+            row_attributes: BTreeMap::new(),
+            attributes: BTreeMap::new(),
+        }
+    }
+    /// Attach a label to the row, such as an equation number, the row is written as [`<mlabeledtr>`](https://www.w3.org/TR/MathML3/chapter3.html#presm.mlabeledtr).
+    pub fn add_row_label(&mut self, row: usize, label: MathML) {
+        self.labels.insert(row, label);
+    }
+    /// Attach a label to the row, such as an equation number.
+    pub fn with_row_label(mut self, row: usize, label: MathML) -> Self {
+        self.add_row_label(row, label);
+        self
+    }
+    /// Gets the label of the row, if any.
+    pub fn get_row_label(&self, row: usize) -> Option<&MathML> {
+        self.labels.get(&row)
+    }
+    /// Add an attribute to the row, such as `columnalign` for the cells of a single row.
+    pub fn add_row_attribute<K, V>(&mut self, row: usize, key: K, value: V)
+    where
+        K: ToString,
+        V: ToString,
+    {
+        self.row_attributes.entry(row).or_default().insert(key.to_string(), value.to_string());
+    }
+    /// Add an attribute to the row.
+    pub fn with_row_attribute<K, V>(mut self, row: usize, key: K, value: V) -> Self
+    where
+        K: ToString,
+        V: ToString,
+    {
+        self.add_row_attribute(row, key, value);
+        self
+    }
+    /// Gets the raw stream of the table, rows are separated by [`MathML::NewLine`] and cells by [`MathML::Ampersand`].
+    pub fn get_stream(&self) -> &[MathML] {
+        &self.stream
+    }
+    /// Splits the stream into rows of cells, a cell with multiple items is wrapped in a [`MathRow`].
+    ///
+    /// A trailing empty row, as produced by a final `\\` in LaTeX, is dropped.
+    pub fn get_rows(&self) -> Vec<Vec<MathML>> {
+        let mut rows = vec![];
+        let mut cells = vec![];
+        let mut cell = vec![];
+        for node in &self.stream {
+            match node {
+                MathML::NewLine => {
+                    cells.push(make_cell(std::mem::take(&mut cell)));
+                    rows.push(std::mem::take(&mut cells));
+                }
+                MathML::Ampersand => cells.push(make_cell(std::mem::take(&mut cell))),
+                _ => cell.push(node.clone()),
+            }
+        }
+        if !cell.is_empty() || !cells.is_empty() {
+            cells.push(make_cell(cell));
+            rows.push(cells);
+        }
+        rows
+    }
+}
+
+fn make_cell(mut items: Vec<MathML>) -> MathML {
+    match items.len() {
+        1 => items.remove(0),
+        _ => MathRow::new(items).into(),
     }
 }
@@ -55,13 +55,15 @@ impl Display for MathFunction {
 impl Display for MathTable {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write_tag_start(f, self)?;
-        f.write_str("<mtr><mtd>")?;
+        let mut row = 0;
+        self.write_row_start(f, row)?;
         for (i, node) in self.stream.iter().enumerate() {
             match node {
                 MathML::NewLine => {
-                    f.write_str("</mtd></mtr>")?;
+                    self.write_row_close(f, row)?;
+                    row += 1;
                     if i < self.stream.len() {
-                        f.write_str("<mtr><mtd>")?;
+                        self.write_row_start(f, row)?;
                     }
                 }
                 MathML::Ampersand => {
@@ -75,7 +77,30 @@ impl Display for MathTable {
                 }
             }
         }
-        f.write_str("</mtd></mtr>")?;
+        self.write_row_close(f, row)?;
         write_tag_close(f, self)
     }
 }
+
+impl MathTable {
+    fn write_row_start(&self, f: &mut Formatter<'_>, row: usize) -> std::fmt::Result {
+        let tag = match self.labels.contains_key(&row) {
+            true => "mlabeledtr",
+            false => "mtr",
+        };
+        write!(f, "<{}", tag)?;
+        for (key, value) in self.row_attributes.get(&row).into_iter().flatten() {
+            write!(f, " {}=\"{}\"", key, value)?;
+        }
+        match self.labels.get(&row) {
+            Some(label) => write!(f, "><mtd>{}</mtd><mtd>", label),
+            None => f.write_str("><mtd>"),
+        }
+    }
+    fn write_row_close(&self, f: &mut Formatter<'_>, row: usize) -> std::fmt::Result {
+        match self.labels.contains_key(&row) {
+            true => f.write_str("</mtd></mlabeledtr>"),
+            false => f.write_str("</mtd></mtr>"),
+        }
+    }
+}
@@ -59,5 +59,7 @@ pub struct MathStyle {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathTable {
     stream: Vec<MathML>,
+    labels: BTreeMap<usize, MathML>,
+    row_attributes: BTreeMap<usize, BTreeMap<String, String>>,
     attributes: BTreeMap<String, String>,
 }
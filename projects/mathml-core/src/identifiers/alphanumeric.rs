@@ -0,0 +1,75 @@
+use super::*;
+
+/// Letters which were encoded before the Mathematical Alphanumeric Symbols block, such as `ℝ`.
+#[rustfmt::skip]
+const LETTERLIKE: &[(FontVariant, char, char)] = &[
+    (FontVariant::Italic,       'h', 'ℎ'),
+    (FontVariant::Script,       'B', 'ℬ'),
+    (FontVariant::Script,       'E', 'ℰ'),
+    (FontVariant::Script,       'F', 'ℱ'),
+    (FontVariant::Script,       'H', 'ℋ'),
+    (FontVariant::Script,       'I', 'ℐ'),
+    (FontVariant::Script,       'L', 'ℒ'),
+    (FontVariant::Script,       'M', 'ℳ'),
+    (FontVariant::Script,       'R', 'ℛ'),
+    (FontVariant::Script,       'e', 'ℯ'),
+    (FontVariant::Script,       'g', 'ℊ'),
+    (FontVariant::Script,       'o', 'ℴ'),
+    (FontVariant::Fraktur,      'C', 'ℭ'),
+    (FontVariant::Fraktur,      'H', 'ℌ'),
+    (FontVariant::Fraktur,      'I', 'ℑ'),
+    (FontVariant::Fraktur,      'R', 'ℜ'),
+    (FontVariant::Fraktur,      'Z', 'ℨ'),
+    (FontVariant::DoubleStruck, 'C', 'ℂ'),
+    (FontVariant::DoubleStruck, 'H', 'ℍ'),
+    (FontVariant::DoubleStruck, 'N', 'ℕ'),
+    (FontVariant::DoubleStruck, 'P', 'ℙ'),
+    (FontVariant::DoubleStruck, 'Q', 'ℚ'),
+    (FontVariant::DoubleStruck, 'R', 'ℝ'),
+    (FontVariant::DoubleStruck, 'Z', 'ℤ'),
+];
+
+/// The Greek letters of the alphanumeric blocks, in order.
+const GREEK: &str = "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡϴΣΤΥΦΧΨΩ∇αβγδεζηθικλμνξοπρςστυφχψω∂ϵϑϰϕϱϖ";
+
+impl FontVariant {
+    /// The first code point of the latin, digit and greek blocks of the variant.
+    #[rustfmt::skip]
+    fn alphanumeric_blocks(&self) -> (Option<u32>, Option<u32>, Option<u32>) {
+        match self {
+            FontVariant::Normal              => (None, None, None),
+            FontVariant::Italic              => (Some(0x1D434), None, Some(0x1D6E2)),
+            FontVariant::Bold                => (Some(0x1D400), Some(0x1D7CE), Some(0x1D6A8)),
+            FontVariant::BoldItalic          => (Some(0x1D468), None, Some(0x1D71C)),
+            FontVariant::DoubleStruck        => (Some(0x1D538), Some(0x1D7D8), None),
+            FontVariant::BoldFraktur         => (Some(0x1D56C), None, None),
+            FontVariant::Script              => (Some(0x1D49C), None, None),
+            FontVariant::BoldScript          => (Some(0x1D4D0), None, None),
+            FontVariant::Fraktur             => (Some(0x1D504), None, None),
+            FontVariant::SansSerif           => (Some(0x1D5A0), Some(0x1D7E2), None),
+            FontVariant::BoldSansSerif       => (Some(0x1D5D4), Some(0x1D7EC), Some(0x1D756)),
+            FontVariant::SansSerifItalic     => (Some(0x1D608), None, None),
+            FontVariant::SansSerifBoldItalic => (Some(0x1D63C), None, Some(0x1D790)),
+            FontVariant::Monospace           => (Some(0x1D670), Some(0x1D7F6), None),
+        }
+    }
+    /// The [Mathematical Alphanumeric Symbols](https://www.unicode.org/charts/PDF/U1D400.pdf) character of the variant,
+    /// such as `𝐱` for a bold `x`, characters without a styled form are returned as is.
+    pub fn map_char(&self, c: char) -> char {
+        if let Some((_, _, styled)) = LETTERLIKE.iter().find(|(variant, plain, _)| variant == self && *plain == c) {
+            return *styled;
+        }
+        let (latin, digit, greek) = self.alphanumeric_blocks();
+        let code = match c {
+            'A'..='Z' => latin.map(|start| start + (c as u32 - 'A' as u32)),
+            'a'..='z' => latin.map(|start| start + 26 + (c as u32 - 'a' as u32)),
+            '0'..='9' => digit.map(|start| start + (c as u32 - '0' as u32)),
+            _ => greek.zip(GREEK.chars().position(|x| x == c)).map(|(start, index)| start + index as u32),
+        };
+        code.and_then(char::from_u32).unwrap_or(c)
+    }
+    /// Map all characters of the text, see [`FontVariant::map_char`].
+    pub fn map_str(&self, text: &str) -> String {
+        text.chars().map(|c| self.map_char(c)).collect()
+    }
+}
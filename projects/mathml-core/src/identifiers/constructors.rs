@@ -38,14 +38,31 @@ impl MathText {
     where
         S: ToString,
     {
-        Self { text: text.to_string(), is_string: false }
+        Self { text: text.to_string(), is_string: false, variant: FontVariant::Normal }
     }
     /// Creates a new [`MathText`] with the [`FontVariant::Normal`] variant.
     pub fn string<S>(text: S) -> Self
     where
         S: ToString,
     {
-        Self { text: text.to_string(), is_string: true }
+        Self { text: text.to_string(), is_string: true, variant: FontVariant::Normal }
+    }
+    /// Set the font variant of the text, such as the bold text of `\textbf{..}`.
+    pub fn with_variant(mut self, variant: FontVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+    /// Gets the text content.
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+    /// Gets the font variant of the text.
+    pub fn get_variant(&self) -> FontVariant {
+        self.variant
+    }
+    /// Checks whether the text is a string literal, rendered as [`<ms>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/ms).
+    pub fn is_string(&self) -> bool {
+        self.is_string
     }
 }
 
@@ -17,7 +17,10 @@ impl Display for MathIdentifier {
 impl Display for MathText {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let tag = if self.is_string { "ms" } else { "mtext" };
-        write!(f, "<{}>", tag)?;
+        match self.variant {
+            FontVariant::Normal => write!(f, "<{}>", tag)?,
+            variant => write!(f, "<{} mathvariant=\"{}\">", tag, variant)?,
+        }
         safe_html_str(f, &self.text)?;
         write!(f, "</{}>", tag)
     }
@@ -1,6 +1,7 @@
 use crate::{helpers::safe_html_str, MathML};
 use std::fmt::{Display, Formatter};
 
+mod alphanumeric;
 mod constructors;
 mod display;
 
@@ -21,6 +22,7 @@ pub struct MathIdentifier {
 pub struct MathText {
     is_string: bool,
     text: String,
+    variant: FontVariant,
 }
 
 // noinspection SpellCheckingInspection
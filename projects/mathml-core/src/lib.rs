@@ -7,6 +7,7 @@
 mod ast;
 mod blocks;
 mod identifiers;
+mod linebreak;
 mod numbers;
 mod operators;
 mod traits;
@@ -17,6 +18,7 @@ pub use crate::{
     ast::MathML,
     blocks::{MathFunction, MathPhantom, MathRoot, MathRow, MathStyle, MathTable},
     identifiers::{FontVariant, MathIdentifier, MathText},
+    linebreak::{CharacterCount, LineBreakOutput, LineBreaker, MathMetrics},
     numbers::{LineThickness, MathError, MathFraction, MathNumber},
     operators::{MathFenced, MathMultiScript, MathOperator, MathSpace, MathSqrt, MathUnderOver},
     traits::MathElement,
@@ -0,0 +1,85 @@
+use super::*;
+
+/// Measures the width of nodes in `em`, used to decide where long formulas are broken.
+pub trait MathMetrics {
+    /// The width of a run of text, such as the content of `<mi>`, `<mn>` or `<mo>`.
+    fn text_width(&self, text: &str) -> f32;
+    /// The width of the node, by default composed from the width of its texts.
+    fn node_width(&self, node: &MathML) -> f32 {
+        estimate_width(self, node)
+    }
+}
+
+/// Estimates the width by counting characters, each character is about half an `em` wide.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CharacterCount;
+
+impl MathMetrics for CharacterCount {
+    fn text_width(&self, text: &str) -> f32 {
+        text.chars().count() as f32 * 0.5
+    }
+}
+
+/// Scale of scripts, limits and indexes.
+const SCRIPT_SCALE: f32 = 0.7;
+
+fn estimate_width<M>(metrics: &M, node: &MathML) -> f32
+where
+    M: MathMetrics + ?Sized,
+{
+    let sum = |items: &[MathML]| items.iter().map(|item| metrics.node_width(item)).sum::<f32>();
+    let max = |items: &[MathML]| items.iter().map(|item| metrics.node_width(item)).fold(0.0, f32::max);
+    match node {
+        MathML::Root(root) => sum(root.get_children()),
+        MathML::Row(row) => sum(row.get_items()),
+        MathML::Space(space) => match space.get_attributes().get("width") {
+            Some(width) => width.trim_end_matches(char::is_alphabetic).parse().unwrap_or(0.0),
+            None => 0.0,
+        },
+        MathML::Number(number) => metrics.text_width(number.get_number()),
+        MathML::Identifier(identifier) => metrics.text_width(identifier.get_identifier()),
+        MathML::Text(text) => metrics.text_width(text.get_text()),
+        MathML::Operator(operator) => {
+            let spacing = match break_class(operator.get_operator()) {
+                Some(BreakClass::Relation) => 2.0 * 5.0 / 18.0,
+                Some(BreakClass::Binary) => 2.0 * 4.0 / 18.0,
+                None => 0.0,
+            };
+            metrics.text_width(operator.get_operator()) + spacing
+        }
+        MathML::MultiScripts(scripts) => {
+            let right = max(scripts.get_super_scripts()).max(max(scripts.get_sub_scripts()));
+            let left = max(scripts.get_pre_super_scripts()).max(max(scripts.get_pre_sub_scripts()));
+            metrics.node_width(scripts.get_base()) + SCRIPT_SCALE * (left + right)
+        }
+        MathML::UnderOver(under_over) => {
+            let limits = under_over.get_under().into_iter().chain(under_over.get_over());
+            let limits = limits.map(|limit| SCRIPT_SCALE * metrics.node_width(limit));
+            limits.fold(metrics.node_width(under_over.get_base()), f32::max)
+        }
+        MathML::Function(function) => metrics.text_width(function.get_name()) + 0.167 + sum(function.get_arguments()),
+        MathML::Sqrt(sqrt) => {
+            let surd = sqrt.get_surd().map(|surd| SCRIPT_SCALE * metrics.node_width(surd)).unwrap_or(0.0);
+            metrics.text_width("√") + surd + metrics.node_width(sqrt.get_base())
+        }
+        MathML::Frac(frac) => 0.2 + metrics.node_width(frac.get_numerator()).max(metrics.node_width(frac.get_denominator())),
+        MathML::Phantom(phantom) => metrics.node_width(phantom.get_inner()),
+        MathML::Style(style) => metrics.node_width(style.get_base()),
+        MathML::Fenced(fenced) => {
+            let separators = fenced.get_items().len().saturating_sub(1) as f32 * metrics.text_width(",");
+            let fences =
+                metrics.text_width(&fenced.get_open().to_string()) + metrics.text_width(&fenced.get_close().to_string());
+            fences + separators + sum(fenced.get_items())
+        }
+        MathML::Table(table) => {
+            let rows = table.get_rows();
+            let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+            let widths = (0..columns).map(|column| {
+                rows.iter().filter_map(|row| row.get(column)).map(|cell| metrics.node_width(cell)).fold(0.0, f32::max)
+            });
+            widths.sum::<f32>() + columns.saturating_sub(1) as f32
+        }
+        MathML::Undefined(error) => metrics.text_width(error.get_message()),
+        MathML::Ampersand | MathML::NewLine | MathML::Nothing => 0.0,
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{MathElement, MathML, MathRoot, MathRow, MathSpace, MathTable};
+
+mod metrics;
+
+pub use self::metrics::{CharacterCount, MathMetrics};
+
+/// How the lines of a broken formula are written.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LineBreakOutput {
+    /// A single row, the operators starting a new line get [`linebreak="newline"`](https://www.w3.org/TR/MathML3/chapter3.html#presm.lbattrs).
+    #[default]
+    Attributes,
+    /// An [`<mtable>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mtable) with one line per row,
+    /// continuation lines are aligned after the first relation.
+    Table,
+}
+
+/// Splits formulas wider than the target width, like `multline` in LaTeX.
+///
+/// Lines are broken before relations and binary operators, preferring relations, but never inside fences or groups.
+///
+/// ```
+/// # use mathml_core::{LineBreaker, MathML};
+/// let items =
+///     (1..=8).flat_map(|i| [MathML::operation("+"), MathML::identifier(format!("x_{}", i))]);
+/// let math = MathML::Row(Box::new(mathml_core::MathRow::new(items.skip(1))));
+/// let broken = LineBreaker::new(8.0).break_lines(&math);
+/// assert!(broken.to_string().contains(r#"linebreak="newline""#));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineBreaker<M = CharacterCount> {
+    width: f32,
+    indent: f32,
+    output: LineBreakOutput,
+    metrics: M,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum BreakClass {
+    Binary,
+    Relation,
+}
+
+/// Operators preferred as line starts.
+#[rustfmt::skip]
+const RELATIONS: &[&str] = &[
+    "=", "≠", "<", ">", "≤", "≥", "≦", "≧", "≪", "≫", "≈", "≃", "≅", "≡", "∼", "∝", "≺", "≻", "⪯", "⪰",
+    "∈", "∉", "∋", "⊂", "⊃", "⊆", "⊇", "→", "←", "↔", "⇒", "⇐", "⇔", "⟶", "⟹", "⟸", "⟺", "↦", "≔", ":=",
+];
+
+/// Operators where a line may start.
+#[rustfmt::skip]
+const BINARIES: &[&str] = &[
+    "+", "-", "−", "±", "∓", "×", "·", "⋅", "∗", "∘", "÷", "/", "∪", "∩", "∧", "∨", "⊕", "⊗", "⊖", "⊙", "∖",
+];
+
+const OPEN_FENCES: &[&str] = &["(", "[", "{", "⟨", "⌊", "⌈", "⟦"];
+
+const CLOSE_FENCES: &[&str] = &[")", "]", "}", "⟩", "⌋", "⌉", "⟧"];
+
+fn break_class(operator: &str) -> Option<BreakClass> {
+    if RELATIONS.contains(&operator) {
+        Some(BreakClass::Relation)
+    }
+    else if BINARIES.contains(&operator) {
+        Some(BreakClass::Binary)
+    }
+    else {
+        None
+    }
+}
+
+impl LineBreaker {
+    /// Create a line breaker for the given width in `em`, widths are estimated by [`CharacterCount`].
+    pub fn new(width: f32) -> Self {
+        Self { width, indent: 1.0, output: LineBreakOutput::default(), metrics: CharacterCount }
+    }
+}
+
+impl<M> LineBreaker<M>
+where
+    M: MathMetrics,
+{
+    /// Measure the nodes with the given metrics instead, such as widths from a font.
+    pub fn with_metrics<N>(self, metrics: N) -> LineBreaker<N>
+    where
+        N: MathMetrics,
+    {
+        LineBreaker { width: self.width, indent: self.indent, output: self.output, metrics }
+    }
+    /// The indentation of continuation lines in `em`, `1.0` by default.
+    pub fn with_indent(self, indent: f32) -> Self {
+        Self { indent, ..self }
+    }
+    /// Write the broken lines as attributes or as a table.
+    pub fn with_output(self, output: LineBreakOutput) -> Self {
+        Self { output, ..self }
+    }
+    /// Gets the metrics used to measure nodes.
+    pub fn get_metrics(&self) -> &M {
+        &self.metrics
+    }
+    /// Break the formula into lines, formulas which fit in the width are returned unchanged.
+    pub fn break_lines(&self, math: &MathML) -> MathML {
+        match math {
+            MathML::Root(root) => match self.break_row(root.get_children()) {
+                Some(broken) => {
+                    let mut out = MathRoot::new(vec![broken]);
+                    *out.mut_attributes() = root.get_attributes().clone();
+                    out.into()
+                }
+                None => math.clone(),
+            },
+            _ => self.break_row(std::slice::from_ref(math)).unwrap_or_else(|| math.clone()),
+        }
+    }
+    /// Find the indexes of the items which start a new line.
+    pub fn find_breaks(&self, items: &[MathML]) -> Vec<usize> {
+        let classes = break_classes(items);
+        let widths: Vec<f32> = items.iter().map(|item| self.metrics.node_width(item)).collect();
+        let mut breaks = vec![];
+        let mut start = 0;
+        let mut available = self.width;
+        loop {
+            let mut used = 0.0;
+            let mut end = start;
+            while end < items.len() && used + widths[end] <= available {
+                used += widths[end];
+                end += 1;
+            }
+            if end == items.len() {
+                break;
+            }
+            let mut fill = widths[start];
+            let mut relation = None;
+            let mut any = None;
+            for i in start + 1..=end {
+                match classes[i] {
+                    // a relation is only worth it if the line is at least half full
+                    Some(BreakClass::Relation) if fill >= available / 2.0 => relation = Some(i),
+                    Some(_) => any = Some(i),
+                    None => {}
+                }
+                fill += widths[i];
+            }
+            // nothing fits, overflow until the next break point
+            let chosen = relation.or(any).or_else(|| (end + 1..items.len()).find(|i| classes[*i].is_some()));
+            match chosen {
+                Some(i) => {
+                    breaks.push(i);
+                    start = i;
+                    available = self.width - self.indent;
+                }
+                None => break,
+            }
+        }
+        breaks
+    }
+    fn break_row(&self, items: &[MathML]) -> Option<MathML> {
+        let mut flat = vec![];
+        flatten(items, &mut flat);
+        let breaks = self.find_breaks(&flat);
+        if breaks.is_empty() {
+            return None;
+        }
+        let out = match self.output {
+            LineBreakOutput::Attributes => {
+                for i in breaks {
+                    if let MathML::Operator(operator) = &mut flat[i] {
+                        operator.add_attribute("linebreak", "newline");
+                        operator.add_attribute("indentshift", format!("{}em", self.indent));
+                    }
+                }
+                MathRow::new(flat).into()
+            }
+            LineBreakOutput::Table => self.as_table(flat, &breaks),
+        };
+        Some(out)
+    }
+    fn as_table(&self, mut items: Vec<MathML>, breaks: &[usize]) -> MathML {
+        let mut lines = vec![];
+        for i in breaks.iter().rev() {
+            lines.push(items.split_off(*i));
+        }
+        lines.push(items);
+        lines.reverse();
+        // `lhs &= a + b \\ & \quad + c`
+        let relation = break_classes(&lines[0]).iter().position(|class| *class == Some(BreakClass::Relation));
+        let mut stream = vec![];
+        let first = lines.remove(0);
+        let align = match relation {
+            Some(i) => {
+                stream.extend(first[..i].iter().cloned());
+                stream.push(MathML::Ampersand);
+                stream.extend(first[i..].iter().cloned());
+                "right left"
+            }
+            None => {
+                stream.extend(first);
+                "left"
+            }
+        };
+        for line in lines {
+            stream.push(MathML::NewLine);
+            if relation.is_some() {
+                stream.push(MathML::Ampersand);
+            }
+            if self.indent > 0.0 {
+                stream.push(MathSpace::new(self.indent).into());
+            }
+            stream.extend(line);
+        }
+        MathTable::matrix(stream).with_attribute("columnalign", align).into()
+    }
+}
+
+/// Inline the items of rows, explicit groups are kept whole.
+fn flatten(items: &[MathML], out: &mut Vec<MathML>) {
+    for item in items {
+        match item {
+            MathML::Row(row) if !row.is_grouped() => flatten(row.get_items(), out),
+            _ => out.push(item.clone()),
+        }
+    }
+}
+
+/// Whether a line may start at each item, not at prefix operators as in `= -x` or inside fences.
+fn break_classes(items: &[MathML]) -> Vec<Option<BreakClass>> {
+    let mut classes = vec![None; items.len()];
+    let mut depth = 0usize;
+    for (i, item) in items.iter().enumerate() {
+        let operator = match item {
+            MathML::Operator(operator) => operator.get_operator(),
+            _ => continue,
+        };
+        if OPEN_FENCES.contains(&operator) {
+            depth += 1;
+        }
+        else if CLOSE_FENCES.contains(&operator) {
+            depth = depth.saturating_sub(1);
+        }
+        else if depth == 0 && i > 0 && !is_prefix_position(&items[i - 1]) {
+            classes[i] = break_class(operator);
+        }
+    }
+    classes
+}
+
+fn is_prefix_position(previous: &MathML) -> bool {
+    match previous {
+        MathML::Operator(operator) => !CLOSE_FENCES.contains(&operator.get_operator()),
+        _ => false,
+    }
+}
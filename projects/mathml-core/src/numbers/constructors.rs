@@ -8,6 +8,10 @@ impl MathError {
     {
         Self { message: message.to_string() }
     }
+    /// Gets the error message.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
 }
 
 impl MathNumber {
@@ -16,7 +20,20 @@ impl MathNumber {
     where
         S: ToString,
     {
-        Self { number: text.to_string() }
+        Self { number: text.to_string(), variant: FontVariant::Normal }
+    }
+    /// Set the font variant of the number, such as the bold digits of `\mathbf{1}`.
+    pub fn with_variant(mut self, variant: FontVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+    /// Gets the literal text of the number.
+    pub fn get_number(&self) -> &str {
+        &self.number
+    }
+    /// Gets the font variant of the number.
+    pub fn get_variant(&self) -> FontVariant {
+        self.variant
     }
 }
 
@@ -68,6 +85,18 @@ impl MathFraction {
         self.line_thickness = line_thickness.into();
         self
     }
+    /// Gets the numerator of the fraction.
+    pub fn get_numerator(&self) -> &MathML {
+        &self.numerator
+    }
+    /// Gets the denominator of the fraction.
+    pub fn get_denominator(&self) -> &MathML {
+        &self.denominator
+    }
+    /// Gets the thickness of the fraction line.
+    pub fn get_thickness(&self) -> LineThickness {
+        self.line_thickness
+    }
 }
 
 impl Default for LineThickness {
@@ -2,7 +2,10 @@ use super::*;
 
 impl Display for MathNumber {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "<mn>{}</mn>", self.number)
+        match self.variant {
+            FontVariant::Normal => write!(f, "<mn>{}</mn>", self.number),
+            variant => write!(f, "<mn mathvariant=\"{}\">{}</mn>", variant, self.number),
+        }
     }
 }
 
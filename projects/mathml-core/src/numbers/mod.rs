@@ -1,4 +1,4 @@
-use crate::{MathElement, MathML};
+use crate::{FontVariant, MathElement, MathML};
 use std::{
     collections::BTreeMap,
     fmt::{Display, Formatter},
@@ -14,6 +14,7 @@ mod display;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MathNumber {
     number: String,
+    variant: FontVariant,
 }
 
 /// The [`<mfrac>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/mfrac) element is used to display fractions.
@@ -53,6 +53,14 @@ impl MathOperator {
     pub fn with_size(self, min: f32, max: f32) -> Self {
         self.mark_stretchy().with_attribute("minsize", min).with_attribute("maxsize", max)
     }
+    /// Gets the text of the operator.
+    pub fn get_operator(&self) -> &str {
+        &self.operator
+    }
+    /// Checks whether the operator is marked as a large operator.
+    pub fn is_large_operator(&self) -> bool {
+        self.attributes.get("largeop").map(|s| s.as_str()) == Some("true")
+    }
 }
 
 impl Default for MathSpace {
@@ -94,6 +102,14 @@ impl MathSqrt {
     pub fn surd(base: MathML, power: MathML) -> Self {
         Self { base, surd: Some(power) }
     }
+    /// Gets the radicand of the root.
+    pub fn get_base(&self) -> &MathML {
+        &self.base
+    }
+    /// Gets the index of the root, `None` for a square root.
+    pub fn get_surd(&self) -> Option<&MathML> {
+        self.surd.as_ref()
+    }
 }
 
 // noinspection SpellCheckingInspection
@@ -151,6 +167,26 @@ impl MathMultiScript {
     pub fn is_sub_super_script(&self) -> bool {
         self.lu.is_empty() && self.ld.is_empty() && self.ru.len() == 1 && self.rd.len() == 1
     }
+    /// Gets the base of the scripts.
+    pub fn get_base(&self) -> &MathML {
+        &self.base
+    }
+    /// Gets the superscripts on the right side.
+    pub fn get_super_scripts(&self) -> &[MathML] {
+        &self.ru
+    }
+    /// Gets the subscripts on the right side.
+    pub fn get_sub_scripts(&self) -> &[MathML] {
+        &self.rd
+    }
+    /// Gets the prescripts on the upper left side.
+    pub fn get_pre_super_scripts(&self) -> &[MathML] {
+        &self.lu
+    }
+    /// Gets the prescripts on the lower left side.
+    pub fn get_pre_sub_scripts(&self) -> &[MathML] {
+        &self.ld
+    }
 }
 
 impl MathFenced {
@@ -190,6 +226,22 @@ impl MathFenced {
         self.separators = separators.to_string();
         self
     }
+    /// Gets the fenced items.
+    pub fn get_items(&self) -> &[MathML] {
+        &self.base
+    }
+    /// Gets the opening fence character.
+    pub fn get_open(&self) -> char {
+        self.open
+    }
+    /// Gets the closing fence character.
+    pub fn get_close(&self) -> char {
+        self.close
+    }
+    /// Gets the separator characters.
+    pub fn get_separators(&self) -> &str {
+        &self.separators
+    }
 }
 
 // noinspection SpellCheckingInspection
@@ -234,6 +286,18 @@ impl MathUnderOver {
     pub fn with_accent_under(self) -> Self {
         self.with_attribute("accentunder", true)
     }
+    /// Gets the base of the element.
+    pub fn get_base(&self) -> &MathML {
+        &self.base
+    }
+    /// Gets the element attached under the base.
+    pub fn get_under(&self) -> Option<&MathML> {
+        self.under.as_ref()
+    }
+    /// Gets the element attached over the base.
+    pub fn get_over(&self) -> Option<&MathML> {
+        self.over.as_ref()
+    }
 }
 
 impl MathML {
@@ -3,7 +3,7 @@ fn ready() {
     println!("it works!")
 }
 
-use mathml_core::{MathFenced, MathIdentifier, MathML};
+use mathml_core::{MathFenced, MathIdentifier, MathML, MathRow, MathTable};
 
 #[test]
 fn node_display() {
@@ -26,3 +26,41 @@ fn test() {
     let math = MathFenced::new(vec![MathML::fraction(1, 2), 2.into(), 3.14.into()], '<', '>').with_separators("&#");
     println!("{}", math)
 }
+
+#[test]
+fn table_rows() {
+    let table = MathTable::matrix(vec![
+        MathML::identifier("a"),
+        MathML::Ampersand,
+        MathML::identifier("b"),
+        MathML::identifier("c"),
+        MathML::NewLine,
+        MathML::number(1),
+        MathML::NewLine,
+    ]);
+    let rows = vec![
+        vec![MathML::identifier("a"), MathRow::new(vec![MathML::identifier("b"), MathML::identifier("c")]).into()],
+        vec![MathML::number(1)],
+    ];
+    assert_eq!(table.get_rows(), rows);
+}
+
+#[test]
+fn line_breaks() {
+    use mathml_core::{LineBreakOutput, LineBreaker, MathMultiScript};
+    let mut items = vec![MathML::identifier("y"), MathML::operation("=")];
+    for i in 1..=6 {
+        if i > 1 {
+            items.push(MathML::operation("+"));
+        }
+        items.push(MathMultiScript::super_script(MathML::identifier("x"), MathML::number(i)).into());
+    }
+    items.push(MathML::operation("+"));
+    items.push(MathFenced::parentheses(vec![MathML::identifier("a"), MathML::operation("+"), MathML::identifier("b")]).into());
+    let breaker = LineBreaker::new(6.0);
+    assert_eq!(breaker.find_breaks(&items), vec![7, 11, 13]);
+    let table = breaker.with_output(LineBreakOutput::Table).break_lines(&MathRow::new(items).into());
+    let MathML::Table(table) = table else { panic!("expected a table") };
+    assert_eq!(table.get_rows().len(), 4);
+    assert_eq!(table.get_rows()[0][0], MathML::identifier("y"));
+}
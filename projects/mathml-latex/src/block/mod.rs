@@ -23,5 +23,54 @@ pub struct LaTeXFraction<'i> {
 #[derive(Clone, Debug)]
 pub struct LaTeXCommand<'i> {
     pub name: &'i str,
+    /// The argument in brackets, such as the index of `\sqrt[3]{x}`.
+    pub optional: Option<Box<LaTeXNode<'i>>>,
     pub children: Vec<LaTeXNode<'i>>,
 }
+
+/// `\left( a \middle| b \right)`
+#[derive(Clone, Debug)]
+pub struct LaTeXFenced<'i> {
+    /// The delimiters as written, such as `(`, `\langle` or `.` for none, between `\left` and `\right` are the `\middle` ones.
+    pub delimiters: Vec<&'i str>,
+    /// The content between two delimiters.
+    pub parts: Vec<LaTeXNode<'i>>,
+}
+
+/// `base_sub^sup`, primes are part of the superscript.
+#[derive(Clone, Debug)]
+pub struct LaTeXScript<'i> {
+    /// The base of the scripts, an empty row for `^2`.
+    pub base: Box<LaTeXNode<'i>>,
+    /// The subscript on the right side.
+    pub sub: Option<Box<LaTeXNode<'i>>>,
+    /// The superscript on the right side.
+    pub sup: Option<Box<LaTeXNode<'i>>>,
+    /// The subscript on the left side, from `{}_n C`.
+    pub pre_sub: Option<Box<LaTeXNode<'i>>>,
+    /// The superscript on the left side, from `{}^n C`.
+    pub pre_sup: Option<Box<LaTeXNode<'i>>>,
+    /// `\limits` or `\nolimits`, otherwise the scripts are limits if the base has them by default.
+    pub limits: Option<bool>,
+}
+
+impl<'i> LaTeXScript<'i> {
+    /// Create a script node without scripts.
+    pub fn new(base: LaTeXNode<'i>) -> Self {
+        Self { base: Box::new(base), sub: None, sup: None, pre_sub: None, pre_sup: None, limits: None }
+    }
+    /// Whether any script is set.
+    pub fn has_scripts(&self) -> bool {
+        self.sub.is_some() || self.sup.is_some() || self.pre_sub.is_some() || self.pre_sup.is_some()
+    }
+    /// Use the scripts of an empty group as the prescripts of the next node.
+    pub fn into_prescripts(self, next: LaTeXNode<'i>) -> Self {
+        let mut script = match next {
+            LaTeXNode::Script(script) if script.pre_sub.is_none() && script.pre_sup.is_none() => script,
+            node => Self::new(node),
+        };
+        script.pre_sub = self.sub;
+        script.pre_sup = self.sup;
+        script
+    }
+}
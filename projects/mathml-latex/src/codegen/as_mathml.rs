@@ -1,8 +1,13 @@
 use super::*;
-use crate::{block::LaTeXCommand, LaTeXBlock};
+use crate::{
+    block::{LaTeXCommand, LaTeXFenced, LaTeXScript},
+    definitions::{operator_name, EquationRow},
+    LaTeXBlock,
+};
 use mathml_core::{
     helpers::{binom, bmatrix, cases, dfrac, frac, matrix, pmatrix, vmatrix, Bmatrix, Vmatrix},
-    MathFunction, MathIdentifier, MathML, MathMultiScript, MathNumber, MathOperator, MathRoot, MathRow, MathSpace,
+    FontVariant, MathElement, MathFunction, MathML, MathMultiScript, MathOperator, MathRoot, MathRow, MathSpace, MathSqrt,
+    MathTable, MathUnderOver,
 };
 
 impl<'i> LaTeXNode<'i> {
@@ -13,24 +18,17 @@ impl<'i> LaTeXNode<'i> {
             LaTeXNode::Row { children } => MathRow::new(children.iter().map(|node| node.as_mathml(context))).into(),
             LaTeXNode::Block(block) => block.as_mathml(context),
             LaTeXNode::Command(cmd) => cmd.as_mathml(context),
-            LaTeXNode::MathText { .. } => {
-                todo!()
-            }
-            LaTeXNode::Number { number } => MathML::Number(Box::new(MathNumber::new(number))),
+            LaTeXNode::MathText { text } => MathML::text(text),
+            LaTeXNode::Number { number } => context.styled_number(number).into(),
 
-            LaTeXNode::Letter { identifier } => MathIdentifier::italic(identifier).into(),
+            LaTeXNode::Letter { identifier } => context.styled_identifier(identifier, FontVariant::Italic).into(),
             LaTeXNode::Operation { operator } => MathOperator::new(operator).into(),
-            LaTeXNode::Superscript { lhs, rhs } => {
-                MathMultiScript::super_script(lhs.as_mathml(context), rhs.as_mathml(context)).into()
-            }
+            LaTeXNode::Fenced(fenced) => fenced.as_mathml(context),
+            LaTeXNode::Script(script) => script.as_mathml(context),
             LaTeXNode::NewLine => MathML::NewLine,
             LaTeXNode::Ampersand => MathML::Ampersand,
-            LaTeXNode::ArticleRoot { .. } => {
-                todo!()
-            }
-            LaTeXNode::ArticleText { .. } => {
-                todo!()
-            }
+            LaTeXNode::ArticleRoot { children } => MathRow::new(children.iter().map(|node| node.as_mathml(context))).into(),
+            LaTeXNode::ArticleText { text } => MathML::text(text),
         }
     }
 }
@@ -46,7 +44,211 @@ impl<'i> LaTeXBlock<'i> {
             "Vmatrix" => Vmatrix(stream),
             "vmatrix" => vmatrix(stream),
             "cases" => cases(stream),
-            name => todo!("unknown block: {}", name),
+            "aligned" | "gathered" | "split" => self.as_aligned(context),
+            name => match name.trim_end_matches('*') {
+                "equation" | "align" | "alignat" | "gather" | "multline" => self.as_numbered(context),
+                _ => MathML::error(MathML::text(format!("Unknown environment `{}`", name))),
+            },
+        }
+    }
+    /// `aligned`, `gathered` and `split`, which are placed inside of a numbered environment.
+    fn as_aligned(&self, context: &LaTeXEngine) -> MathML {
+        let mut stream: Vec<MathML> = self.children.iter().map(|node| node.as_mathml(context)).collect();
+        trim_last_row(&mut stream);
+        align_columns(MathTable::matrix(stream), self.kind).with_attribute("displaystyle", true).into()
+    }
+    /// `equation`, `align`, `alignat`, `gather` and `multline`, the numbers are written as row labels.
+    ///
+    /// The starred environments are only numbered by `\tag`, `multline` gets a single number on its last line.
+    fn as_numbered(&self, context: &LaTeXEngine) -> MathML {
+        let kind = self.kind.trim_end_matches('*');
+        let starred = kind.len() != self.kind.len();
+        let mut stream = vec![];
+        let mut rows = vec![];
+        let mut row = EquationRow { hidden: starred, ..Default::default() };
+        let mut empty = true;
+        for child in &self.children {
+            match child {
+                LaTeXNode::Command(command) => match command.name {
+                    "tag" | "tag*" => row.tag = Some((command.as_raw_text(), command.name == "tag*")),
+                    "notag" | "nonumber" => row.hidden = true,
+                    "label" => row.labels.push(command.as_raw_text()),
+                    _ => {
+                        empty = false;
+                        stream.push(child.as_mathml(context));
+                    }
+                },
+                LaTeXNode::NewLine if kind == "equation" => {}
+                LaTeXNode::NewLine if kind == "multline" => {
+                    rows.push((EquationRow::default(), true));
+                    stream.push(MathML::NewLine);
+                }
+                LaTeXNode::NewLine => {
+                    rows.push((std::mem::replace(&mut row, EquationRow { hidden: starred, ..Default::default() }), empty));
+                    empty = true;
+                    stream.push(MathML::NewLine);
+                }
+                _ => {
+                    empty = false;
+                    stream.push(child.as_mathml(context));
+                }
+            }
+        }
+        rows.push((row, empty));
+        trim_last_row(&mut stream);
+        let mut table = MathTable::matrix(stream);
+        for (index, (row, empty)) in rows.iter().enumerate() {
+            // the empty row after a final `\\`
+            if *empty && row.tag.is_none() {
+                continue;
+            }
+            if let Some(number) = context.number_equation(row) {
+                table.add_row_label(index, MathML::text(number));
+            }
+        }
+        if kind == "multline" && rows.len() > 1 {
+            table.add_row_attribute(0, "columnalign", "left");
+            table.add_row_attribute(rows.len() - 1, "columnalign", "right");
+            table.add_attribute("width", "100%");
+        }
+        align_columns(table, kind).with_attribute("displaystyle", true).with_attribute("side", "right").into()
+    }
+}
+
+/// A final `\\` does not start another row.
+fn trim_last_row(stream: &mut Vec<MathML>) {
+    if matches!(stream.last(), Some(MathML::NewLine)) {
+        stream.pop();
+    }
+}
+
+/// The columns of `align` are right and left aligned in turn at `&`, with space between the pairs.
+///
+/// `alignat` and `split` have no space between the columns, the others are centered.
+fn align_columns(table: MathTable, kind: &str) -> MathTable {
+    let columns = table.get_rows().iter().map(Vec::len).max().unwrap_or(1);
+    let align = ["right", "left"].iter().cycle().take(columns).copied().collect::<Vec<_>>().join(" ");
+    match kind {
+        "align" | "aligned" => {
+            let spacing = ["0em", "2em"].iter().cycle().take(columns.max(2) - 1).copied().collect::<Vec<_>>().join(" ");
+            table.with_attribute("columnalign", align).with_attribute("columnspacing", spacing)
+        }
+        "alignat" | "split" => table.with_attribute("columnalign", align).with_attribute("columnspacing", "0em"),
+        _ => table.with_attribute("columnalign", "center"),
+    }
+}
+
+impl<'i> LaTeXFenced<'i> {
+    /// Converts the delimiters into stretchy operators around their parts, `.` is left out.
+    pub fn as_mathml(&self, context: &LaTeXEngine) -> MathML {
+        let mut row = vec![];
+        let last = self.delimiters.len() - 1;
+        for (index, delimiter) in self.delimiters.iter().enumerate() {
+            if let Some(part) = index.checked_sub(1).and_then(|part| self.parts.get(part)) {
+                if !matches!(part, LaTeXNode::Row { children } if children.is_empty()) {
+                    row.push(part.as_mathml(context));
+                }
+            }
+            if *delimiter == "." {
+                continue;
+            }
+            let symbol = context.get_delimiter(delimiter).unwrap_or_else(|| {
+                tracing::warn!("Unknown delimiter `{}`", delimiter);
+                delimiter.trim_start_matches('\\')
+            });
+            let form = match index {
+                0 => "prefix",
+                _ if index == last => "postfix",
+                _ => "infix",
+            };
+            row.push(MathOperator::new(symbol).mark_symmetric().with_attribute("form", form).into());
+        }
+        MathRow::group(row).into()
+    }
+}
+
+impl<'i> LaTeXNode<'i> {
+    /// Whether the scripts of the node are written as limits by default, under and over it.
+    fn has_limits(&self, context: &LaTeXEngine) -> bool {
+        match self {
+            LaTeXNode::Command(command) => match context.get_large_operator(command.name) {
+                Some((_, limits)) => limits,
+                None if context.get_limit_function(command.name).is_some() => true,
+                None => match context.get_math_operator(command.name) {
+                    Some((_, limits)) => limits,
+                    None => matches!(command.name, "overbrace" | "underbrace" | "operatorname*"),
+                },
+            },
+            _ => false,
+        }
+    }
+}
+
+impl<'i> LaTeXScript<'i> {
+    /// Converts the scripts into `msub`, `msup`, `msubsup` or `mmultiscripts` with prescripts.
+    pub fn as_mathml(&self, context: &LaTeXEngine) -> MathML {
+        let base = match (&*self.base, self.limits) {
+            // `\sum\limits` keeps its limits inline
+            (LaTeXNode::Command(command), Some(true)) => match context.get_large_operator(command.name) {
+                Some((symbol, _)) => MathOperator::new(symbol).mark_large_operator().into(),
+                // `\lim\limits` and declared operators
+                None => match self.base.as_mathml(context) {
+                    MathML::Operator(mut operator) => {
+                        operator.mut_attributes().remove("movablelimits");
+                        MathML::Operator(operator)
+                    }
+                    base => base,
+                },
+            },
+            _ => self.base.as_mathml(context),
+        };
+        let script = |node: &Option<Box<LaTeXNode>>| node.iter().map(|node| node.as_mathml(context)).collect::<Vec<_>>();
+        if self.pre_sub.is_some() || self.pre_sup.is_some() {
+            return MathMultiScript::new(
+                base,
+                script(&self.pre_sup),
+                script(&self.pre_sub),
+                script(&self.sup),
+                script(&self.sub),
+            )
+            .into();
+        }
+        // the label of `\overbrace{..}^{n}` or the bounds of `\sum`
+        if self.limits.unwrap_or_else(|| self.base.has_limits(context)) {
+            // the brace is the base of a `munderover` between the content and the label, the content is kept full size
+            if let (LaTeXNode::Command(command), MathML::UnderOver(brace)) = (&*self.base, &base) {
+                match (command.name, brace.get_under(), brace.get_over(), &self.sub, &self.sup) {
+                    ("overbrace", None, Some(symbol), None, Some(label)) => {
+                        let content = brace.get_base().clone();
+                        return MathUnderOver::under_over(symbol.clone(), content, label.as_mathml(context))
+                            .with_accent_under()
+                            .into();
+                    }
+                    ("underbrace", Some(symbol), None, Some(label), None) => {
+                        let content = brace.get_base().clone();
+                        return MathUnderOver::under_over(symbol.clone(), label.as_mathml(context), content)
+                            .with_accent_over()
+                            .into();
+                    }
+                    _ => {}
+                }
+            }
+            return match (&self.sub, &self.sup) {
+                (Some(sub), Some(sup)) => {
+                    MathUnderOver::under_over(base, sub.as_mathml(context), sup.as_mathml(context)).into()
+                }
+                (Some(sub), None) => MathUnderOver::under(base, sub.as_mathml(context)).into(),
+                (None, Some(sup)) => MathUnderOver::over(base, sup.as_mathml(context)).into(),
+                (None, None) => base,
+            };
+        }
+        match (&self.sub, &self.sup) {
+            (Some(sub), Some(sup)) => {
+                MathMultiScript::sub_super_script(base, sub.as_mathml(context), sup.as_mathml(context)).into()
+            }
+            (Some(sub), None) => MathMultiScript::sub_script(base, sub.as_mathml(context)).into(),
+            (None, Some(sup)) => MathMultiScript::super_script(base, sup.as_mathml(context)).into(),
+            (None, None) => base,
         }
     }
 }
@@ -55,56 +257,171 @@ impl<'i> LaTeXCommand<'i> {
     pub fn as_mathml(&self, context: &LaTeXEngine) -> MathML {
         match self.name {
             "usepackage" => return MathML::Nothing,
+            // only meaningful in numbered environments
+            "label" | "tag" | "tag*" | "notag" | "nonumber" => return MathML::Nothing,
+            "ref" | "eqref" => {
+                let label = self.as_raw_text();
+                let number = context.get_label(label).unwrap_or_else(|| {
+                    tracing::warn!("Undefined reference `{}`", label);
+                    "??".to_string()
+                });
+                return match self.name {
+                    "eqref" => MathML::text(format!("({})", number)),
+                    _ => MathML::text(number),
+                };
+            }
+            "operatorname*" => match self.children.as_slice() {
+                [] => return self.missing_argument(),
+                [head] => return limit_function(&operator_name(head.as_identifier())),
+                [head, rest @ ..] => {
+                    let mut row = MathRow::new(vec![limit_function(&operator_name(head.as_identifier()))]);
+                    row.mut_items().push(MathSpace::new(0.167).into());
+                    row.mut_items().extend(rest.iter().map(|node| node.as_mathml(context)));
+                    return row.into();
+                }
+            },
+            // registered when the macros of the formula are expanded
+            "DeclareMathOperator" | "DeclareMathOperator*" => return MathML::Nothing,
             "operatorname" => match self.children.as_slice() {
-                [] => panic!("operatorname command must have exactly one argument"),
+                [] => return self.missing_argument(),
                 [head, rest @ ..] => {
-                    return MathFunction::new(head.as_identifier(), rest.iter().map(|node| node.as_mathml(context))).into();
+                    return MathFunction::new(
+                        operator_name(head.as_identifier()),
+                        rest.iter().map(|node| node.as_mathml(context)),
+                    )
+                    .into();
                 }
             },
             kind @ ("frac" | "dfrac") => match self.children.as_slice() {
-                [] => panic!("frac command must have at least two arguments"),
-                [numerator] => panic!("frac command must have at least two arguments"),
-                [numerator, denominator, rest @ ..] => {
-                    let term = match kind {
-                        "frac" => frac(numerator.as_mathml(context), denominator.as_mathml(context)),
+                [numerator, denominator] => {
+                    return match kind {
                         "dfrac" => dfrac(numerator.as_mathml(context), denominator.as_mathml(context)),
-                        _ => unreachable!(),
+                        _ => frac(numerator.as_mathml(context), denominator.as_mathml(context)),
                     };
-                    if rest.len() == 0 {
-                        return term;
-                    }
-                    let mut terms = MathRow::new(vec![term]);
-                    terms.mut_items().extend(rest.iter().map(|node| node.as_mathml(context)));
-                    return terms.into();
                 }
+                _ => return self.missing_argument(),
             },
-            "binom" => match self.children.as_slice() {
-                [numerator, denominator, rest @ ..] => {
-                    let term = binom(numerator.as_mathml(context), denominator.as_mathml(context));
-                    if rest.len() == 0 {
-                        return term;
+            kind @ ("text" | "textrm" | "textnormal" | "textbf" | "textit" | "textsf" | "texttt" | "mbox") => {
+                let variant = match kind {
+                    "textbf" => FontVariant::Bold,
+                    "textit" => FontVariant::Italic,
+                    "textsf" => FontVariant::SansSerif,
+                    "texttt" => FontVariant::Monospace,
+                    _ => FontVariant::Normal,
+                };
+                // the runs between escapes and groups are merged into one `<mtext>`
+                let mut items: Vec<MathML> = vec![];
+                let mut run = String::new();
+                for node in &self.children {
+                    match node {
+                        LaTeXNode::MathText { text } => run.push_str(text),
+                        node => {
+                            if !run.is_empty() {
+                                items.push(context.styled_text(&std::mem::take(&mut run), variant).into());
+                            }
+                            items.push(node.as_mathml(context));
+                        }
                     }
-                    let mut terms = MathRow::new(vec![term]);
-                    terms.mut_items().extend(rest.iter().map(|node| node.as_mathml(context)));
-                    return terms.into();
                 }
-                _ => panic!("binom command must have exactly two arguments"),
+                if !run.is_empty() || items.is_empty() {
+                    items.push(context.styled_text(&run, variant).into());
+                }
+                return match items.len() {
+                    1 => items.remove(0),
+                    _ => MathRow::new(items).into(),
+                };
+            }
+            "sqrt" => {
+                let base = self.children.first().map_or(MathML::Nothing, |node| node.as_mathml(context));
+                return match &self.optional {
+                    Some(index) => MathSqrt::surd(base, index.as_mathml(context)).into(),
+                    None => MathSqrt::sqrt(base).into(),
+                };
+            }
+            "overset" | "stackrel" | "underset" => match self.children.as_slice() {
+                [script, base] => {
+                    let (base, script) = (base.as_mathml(context), script.as_mathml(context));
+                    return match self.name {
+                        "underset" => MathUnderOver::under(base, script).into(),
+                        _ => MathUnderOver::over(base, script).into(),
+                    };
+                }
+                _ => return self.missing_argument(),
+            },
+            "binom" => match self.children.as_slice() {
+                [numerator, denominator] => return binom(numerator.as_mathml(context), denominator.as_mathml(context)),
+                _ => return self.missing_argument(),
             },
             _ => {}
         }
+        if let Some(accent) = context.get_accent(self.name) {
+            let base = self.children.first().map_or(MathML::Nothing, |node| node.as_mathml(context));
+            let operator = match accent.stretchy {
+                true => MathOperator::new(&accent.symbol).mark_stretchy(),
+                false => MathOperator::new(&accent.symbol).with_attribute("stretchy", false),
+            };
+            return match accent.under {
+                true => MathUnderOver::under(base, operator.into()).with_accent_under().into(),
+                false => MathUnderOver::over(base, operator.into()).with_accent_over().into(),
+            };
+        }
+        if let Some(variant) = context.get_font(self.name) {
+            return context
+                .with_font(variant, || self.children.first().map_or(MathML::Nothing, |node| node.as_mathml(context)));
+        }
         if let Some(s) = context.get_function(&self.name) {
             return MathFunction::new(s, self.children.iter().map(|node| node.as_mathml(context))).into();
         }
+        if let Some((s, limits)) = context.get_math_operator(self.name) {
+            return match limits {
+                true => limit_function(&s),
+                false => MathFunction::new(s, self.children.iter().map(|node| node.as_mathml(context))).into(),
+            };
+        }
+        if let Some(s) = context.get_limit_function(self.name) {
+            return limit_function(s);
+        }
+        // limits move to the right of the operator when inline
+        if let Some((s, limits)) = context.get_large_operator(self.name) {
+            let operator = MathOperator::new(s).mark_large_operator();
+            return match limits {
+                true => operator.with_attribute("movablelimits", true).into(),
+                false => operator.into(),
+            };
+        }
         if let Some(s) = context.get_operator(&self.name) {
             return MathOperator::new(s).into();
         }
         if let Some(s) = context.get_letters(&self.name) {
-            return MathIdentifier::normal(s).into();
+            return context.styled_identifier(s, FontVariant::Normal).into();
         }
         if let Some(s) = context.get_space(&self.name) {
             return MathSpace::new(s).into();
         }
+        // `\langle` outside of `\left` does not stretch
+        if let Some(s) = context.get_delimiter(&format!("\\{}", self.name)) {
+            return MathOperator::new(s).into();
+        }
+        tracing::warn!("Unknown command `\\{}`", self.name);
+        MathML::error(MathML::text(format!("\\{}", self.name)))
+    }
+}
 
-        todo!()
+/// `\lim` and similar names, the limits move to the right when inline.
+fn limit_function(name: &str) -> MathML {
+    MathOperator::new(name).with_attribute("movablelimits", true).into()
+}
+
+impl<'i> LaTeXCommand<'i> {
+    /// Commands built without the arguments of the parser are shown as errors.
+    fn missing_argument(&self) -> MathML {
+        MathML::error(MathML::text(format!("Missing argument for `\\{}`", self.name)))
+    }
+    /// The argument of commands such as `\label{..}`, kept as written.
+    fn as_raw_text(&self) -> &'i str {
+        match self.children.as_slice() {
+            [LaTeXNode::MathText { text }] => text,
+            _ => "",
+        }
     }
 }
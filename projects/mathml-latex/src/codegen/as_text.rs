@@ -5,6 +5,7 @@ impl<'i> LaTeXNode<'i> {
     pub fn as_identifier(&self) -> &'i str {
         match self {
             LaTeXNode::Letter { identifier } => identifier,
+            LaTeXNode::MathText { text } => text,
             LaTeXNode::Operation { operator } => operator,
             _ => "",
         }
@@ -33,15 +34,34 @@ impl<'i> Display for LaTeXNode<'i> {
             LaTeXNode::Command { .. } => {
                 todo!()
             }
-            LaTeXNode::MathText { .. } => {
-                todo!()
-            }
+            LaTeXNode::MathText { text } => f.write_str(text),
             LaTeXNode::Number { number } => f.write_str(number),
             LaTeXNode::Operation { .. } => {
                 todo!()
             }
-            LaTeXNode::Superscript { .. } => {
-                todo!()
+            LaTeXNode::Fenced(fenced) => {
+                for (index, delimiter) in fenced.delimiters.iter().enumerate() {
+                    if let Some(part) = index.checked_sub(1).and_then(|part| fenced.parts.get(part)) {
+                        write!(f, "{} ", part)?;
+                    }
+                    let command = match index {
+                        0 => "left",
+                        _ if index + 1 == fenced.delimiters.len() => "right",
+                        _ => "middle",
+                    };
+                    write!(f, "\\{}{} ", command, delimiter)?;
+                }
+                Ok(())
+            }
+            LaTeXNode::Script(script) => {
+                if script.pre_sub.is_some() || script.pre_sup.is_some() {
+                    f.write_str("{}")?;
+                    write_script(f, "_", &script.pre_sub)?;
+                    write_script(f, "^", &script.pre_sup)?;
+                }
+                write!(f, "{{{}}}", script.base)?;
+                write_script(f, "_", &script.sub)?;
+                write_script(f, "^", &script.sup)
             }
             LaTeXNode::Letter { .. } => {
                 todo!()
@@ -61,3 +81,10 @@ impl<'i> Display for LaTeXBlock<'i> {
         writeln!(f, "\\end{{{}}}", self.kind)
     }
 }
+
+fn write_script(f: &mut Formatter<'_>, mark: &str, node: &Option<Box<LaTeXNode>>) -> std::fmt::Result {
+    match node {
+        Some(node) => write!(f, "{}{{{}}}", mark, node),
+        None => Ok(()),
+    }
+}
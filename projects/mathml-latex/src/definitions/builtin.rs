@@ -4,9 +4,19 @@ impl Default for LaTeXEngine {
     fn default() -> Self {
         Self {
             functions: Default::default(),
+            limit_functions: Default::default(),
+            math_operators: Default::default(),
             operators: Default::default(),
+            large_operators: Default::default(),
             spaces: Default::default(),
             letters: Default::default(),
+            delimiters: Default::default(),
+            accents: Default::default(),
+            fonts: Default::default(),
+            unicode_fonts: false,
+            font: Default::default(),
+            macros: Default::default(),
+            numbering: Default::default(),
         }
     }
 }
@@ -15,9 +25,14 @@ impl LaTeXEngine {
     pub fn builtin() -> Self {
         let mut empty = Self::default();
         empty.add_builtin_operators();
+        empty.add_builtin_large_operators();
         empty.add_builtin_functions();
+        empty.add_builtin_limit_functions();
         empty.add_builtin_letters();
         empty.add_builtin_space();
+        empty.add_builtin_delimiters();
+        empty.add_builtin_accents();
+        empty.add_builtin_fonts();
         empty
     }
 }
@@ -63,11 +78,56 @@ impl LaTeXEngine {
             "log" => "log",
             "erf" => "erf",
             "erfc" => "erfc",
-            "arg" => "arg",
-            "gcd" => "gcd",
             "lcm" => "lcm",
-            "min" => "min",
+        }
+    }
+}
+
+impl LaTeXEngine {
+    /// Gets the name of a function such as `\lim`, whose subscript is an under-limit in display style.
+    pub fn get_limit_function(&self, name: &str) -> Option<&str> {
+        Some(self.limit_functions.get(name)?.as_str())
+    }
+    /// Add a function such as `\lim`, see [`LaTeXEngine::get_limit_function`].
+    pub fn add_limit_function<K, V>(&mut self, key: K, value: V)
+    where
+        K: ToString,
+        V: ToString,
+    {
+        self.limit_functions.insert(key.to_string(), value.to_string());
+    }
+    /// Register an operator name as `\DeclareMathOperator` does, `limits` for the starred form.
+    pub fn declare_math_operator<K, V>(&self, key: K, value: V, limits: bool)
+    where
+        K: ToString,
+        V: ToString,
+    {
+        self.math_operators.borrow_mut().insert(key.to_string(), (value.to_string(), limits));
+    }
+    /// Gets the name of an operator from `\DeclareMathOperator`, and whether its scripts are limits.
+    pub fn get_math_operator(&self, name: &str) -> Option<(String, bool)> {
+        self.math_operators.borrow().get(name).cloned()
+    }
+    fn add_builtin_limit_functions(&mut self) {
+        macro_rules! add_limit_function {
+            ($($name:literal => $symbol:literal),* $(,)?) => {
+                $(
+                    self.limit_functions.insert($name.to_string(), $symbol.to_string());
+                )*
+            };
+        }
+        add_limit_function! {
+            "lim" => "lim",
+            "liminf" => "lim inf",
+            "limsup" => "lim sup",
             "max" => "max",
+            "min" => "min",
+            "sup" => "sup",
+            "inf" => "inf",
+            "det" => "det",
+            "gcd" => "gcd",
+            "Pr" => "Pr",
+            "arg" => "arg",
         }
     }
 }
@@ -106,8 +166,59 @@ impl LaTeXEngine {
             "setminus" => "∖",
             "uplus"  => "⊎",
             "amalg"  => "⨿",
-            "bigcup" => "⋃",
-            "bigcap" => "⋂",
+            "to" => "→",
+            "gets" => "←",
+            "rightarrow" => "→",
+            "leftarrow" => "←",
+            "leftrightarrow" => "↔",
+            "Rightarrow" => "⇒",
+            "Leftarrow" => "⇐",
+            "Leftrightarrow" => "⇔",
+            "mapsto" => "↦",
+            "le" => "≤",
+            "leq" => "≤",
+            "ge" => "≥",
+            "geq" => "≥",
+            "neq" => "≠",
+        }
+    }
+}
+
+impl LaTeXEngine {
+    /// Gets the character of a large operator such as `\sum`, and whether its scripts are limits by default.
+    pub fn get_large_operator(&self, name: &str) -> Option<(&str, bool)> {
+        let (symbol, limits) = self.large_operators.get(name)?;
+        Some((symbol.as_str(), *limits))
+    }
+    fn add_builtin_large_operators(&mut self) {
+        macro_rules! add_large_operator {
+            ($($name:literal => ($symbol:literal, $limits:literal)),* $(,)?) => {
+                $(
+                    self.large_operators.insert($name.to_string(), ($symbol.to_string(), $limits));
+                )*
+            };
+        }
+
+        add_large_operator! {
+            "sum" => ("∑", true),
+            "prod" => ("∏", true),
+            "coprod" => ("∐", true),
+            "bigcup" => ("⋃", true),
+            "bigcap" => ("⋂", true),
+            "bigvee" => ("⋁", true),
+            "bigwedge" => ("⋀", true),
+            "bigoplus" => ("⨁", true),
+            "bigotimes" => ("⨂", true),
+            "bigodot" => ("⨀", true),
+            "biguplus" => ("⨄", true),
+            "bigsqcup" => ("⨆", true),
+            "int" => ("∫", false),
+            "iint" => ("∬", false),
+            "iiint" => ("∭", false),
+            "iiiint" => ("⨌", false),
+            "oint" => ("∮", false),
+            "oiint" => ("∯", false),
+            "oiiint" => ("∰", false),
         }
     }
 }
@@ -212,6 +323,7 @@ impl LaTeXEngine {
             "alefsym" => "ℵ",
             "real" => "ℜ",
             "partial" => "∂",
+            "nabla" => "∇",
             "prime" => "′",
             "emptyset" => "∅",
             "clubs" => "♣",
@@ -236,3 +348,139 @@ impl LaTeXEngine {
         self.spaces.insert("qquad".to_string(), 2.0);
     }
 }
+
+/// The name of `\operatorname{arg\,max}`, the spaces are written as thin spaces.
+pub(crate) fn operator_name(text: &str) -> String {
+    text.replace("\\,", "\u{2009}").replace("\\ ", " ").replace(['{', '}'], "")
+}
+
+/// The delimiters of `\left`, `\middle` and `\right`, the parser rejects other names.
+pub(crate) const DELIMITERS: &[(&str, &str)] = &[
+    ("(", "("),
+    (")", ")"),
+    ("[", "["),
+    ("]", "]"),
+    ("|", "|"),
+    ("/", "/"),
+    ("<", "⟨"),
+    (">", "⟩"),
+    ("\\{", "{"),
+    ("\\}", "}"),
+    ("\\|", "‖"),
+    ("\\lbrace", "{"),
+    ("\\rbrace", "}"),
+    ("\\lbrack", "["),
+    ("\\rbrack", "]"),
+    ("\\langle", "⟨"),
+    ("\\rangle", "⟩"),
+    ("\\vert", "|"),
+    ("\\lvert", "|"),
+    ("\\rvert", "|"),
+    ("\\Vert", "‖"),
+    ("\\lVert", "‖"),
+    ("\\rVert", "‖"),
+    ("\\lfloor", "⌊"),
+    ("\\rfloor", "⌋"),
+    ("\\lceil", "⌈"),
+    ("\\rceil", "⌉"),
+    ("\\lgroup", "⟮"),
+    ("\\rgroup", "⟯"),
+    ("\\lmoustache", "⎰"),
+    ("\\rmoustache", "⎱"),
+    ("\\ulcorner", "⌜"),
+    ("\\urcorner", "⌝"),
+    ("\\llcorner", "⌞"),
+    ("\\lrcorner", "⌟"),
+    ("\\backslash", "\\"),
+    ("\\uparrow", "↑"),
+    ("\\downarrow", "↓"),
+    ("\\updownarrow", "↕"),
+    ("\\Uparrow", "⇑"),
+    ("\\Downarrow", "⇓"),
+    ("\\Updownarrow", "⇕"),
+];
+
+impl LaTeXEngine {
+    /// Gets the character of a `\left`, `\middle` or `\right` delimiter, written as in the source, such as `(` or `\langle`.
+    pub fn get_delimiter(&self, name: &str) -> Option<&str> {
+        Some(self.delimiters.get(name)?.as_str())
+    }
+    fn add_builtin_delimiters(&mut self) {
+        for (name, symbol) in DELIMITERS {
+            self.delimiters.insert(name.to_string(), symbol.to_string());
+        }
+    }
+}
+
+impl LaTeXEngine {
+    /// Gets the font of a command such as `\mathbb`, without the backslash.
+    pub fn get_font(&self, name: &str) -> Option<FontVariant> {
+        self.fonts.get(name).copied()
+    }
+    fn add_builtin_fonts(&mut self) {
+        macro_rules! add_font {
+            ($($name:literal => $variant:ident),* $(,)?) => {
+                $(
+                    self.fonts.insert($name.to_string(), FontVariant::$variant);
+                )*
+            };
+        }
+
+        add_font! {
+            "mathbb" => DoubleStruck,
+            "mathcal" => Script,
+            "mathscr" => Script,
+            "mathfrak" => Fraktur,
+            "mathbf" => Bold,
+            "boldsymbol" => BoldItalic,
+            "bm" => BoldItalic,
+            "mathrm" => Normal,
+            "mathit" => Italic,
+            "mathsf" => SansSerif,
+            "mathtt" => Monospace,
+        }
+    }
+}
+
+impl LaTeXEngine {
+    /// Gets the accent of a command such as `\hat`, without the backslash.
+    pub fn get_accent(&self, name: &str) -> Option<&LaTeXAccent> {
+        self.accents.get(name)
+    }
+    fn add_builtin_accents(&mut self) {
+        macro_rules! add_accent {
+            ($($name:literal => ($symbol:literal, $under:literal, $stretchy:literal)),* $(,)?) => {
+                $(
+                    self.accents.insert($name.to_string(), LaTeXAccent { symbol: $symbol.to_string(), under: $under, stretchy: $stretchy });
+                )*
+            };
+        }
+
+        add_accent! {
+            "hat" => ("^", false, false),
+            "widehat" => ("^", false, true),
+            "check" => ("ˇ", false, false),
+            "widecheck" => ("ˇ", false, true),
+            "tilde" => ("~", false, false),
+            "widetilde" => ("~", false, true),
+            "acute" => ("´", false, false),
+            "grave" => ("`", false, false),
+            "dot" => ("˙", false, false),
+            "ddot" => ("¨", false, false),
+            "dddot" => ("⃛", false, false),
+            "breve" => ("˘", false, false),
+            "bar" => ("¯", false, false),
+            "vec" => ("→", false, false),
+            "mathring" => ("˚", false, false),
+            "overline" => ("‾", false, true),
+            "underline" => ("_", true, true),
+            "overbrace" => ("⏞", false, true),
+            "underbrace" => ("⏟", true, true),
+            "overrightarrow" => ("→", false, true),
+            "overleftarrow" => ("←", false, true),
+            "overleftrightarrow" => ("↔", false, true),
+            "underrightarrow" => ("→", true, true),
+            "underleftarrow" => ("←", true, true),
+        }
+    }
+}
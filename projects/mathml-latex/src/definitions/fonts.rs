@@ -0,0 +1,57 @@
+use super::*;
+use mathml_core::{MathIdentifier, MathNumber, MathText};
+
+/// The font of the innermost `\mathbf{..}` or similar command, restored even if rendering panics.
+struct FontScope<'c> {
+    font: &'c Cell<Option<FontVariant>>,
+    outer: Option<FontVariant>,
+}
+
+impl<'c> Drop for FontScope<'c> {
+    fn drop(&mut self) {
+        self.font.set(self.outer);
+    }
+}
+
+impl LaTeXEngine {
+    /// Write styled letters and digits as Mathematical Alphanumeric Symbols such as `𝐱`, instead of `mathvariant`.
+    pub fn with_unicode_fonts(mut self, enable: bool) -> Self {
+        self.unicode_fonts = enable;
+        self
+    }
+    /// Render the letters and digits of the closure in the font of a command such as `\mathbf`.
+    pub(crate) fn with_font<T>(&self, variant: FontVariant, render: impl FnOnce() -> T) -> T {
+        let _scope = FontScope { font: &self.font, outer: self.font.replace(Some(variant)) };
+        render()
+    }
+    /// An identifier in the current font, `default` is the font outside of font commands.
+    pub(crate) fn styled_identifier(&self, text: &str, default: FontVariant) -> MathIdentifier {
+        match self.font.get() {
+            None => MathIdentifier::new(text, default),
+            Some(variant) if self.unicode_fonts => match variant.map_str(text) {
+                // styled characters are never transformed by `<mi>`
+                styled if styled != text => MathIdentifier::italic(styled),
+                _ => MathIdentifier::new(text, variant),
+            },
+            Some(variant) => MathIdentifier::new(text, variant),
+        }
+    }
+    /// A number in the current font.
+    pub(crate) fn styled_number(&self, text: &str) -> MathNumber {
+        match self.font.get() {
+            None => MathNumber::new(text),
+            Some(variant) if self.unicode_fonts => match variant.map_str(text) {
+                styled if styled != text => MathNumber::new(styled),
+                _ => MathNumber::new(text).with_variant(variant),
+            },
+            Some(variant) => MathNumber::new(text).with_variant(variant),
+        }
+    }
+    /// The text of `\text{..}` or similar commands, kept as written.
+    pub(crate) fn styled_text(&self, text: &str, variant: FontVariant) -> MathText {
+        match variant.map_str(text) {
+            styled if self.unicode_fonts && styled != text => MathText::text(styled),
+            _ => MathText::text(text).with_variant(variant),
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use super::*;
+use crate::parse_latex;
+use mathml_core::MathML;
+use pex::StopBecause;
+use std::{borrow::Cow, iter::repeat_n};
+
+/// Commands which define macros, formulas without them or defined macros are never rewritten.
+const DEFINITIONS: &[&str] = &[
+    "\\newcommand",
+    "\\renewcommand",
+    "\\providecommand",
+    "\\def",
+    "\\let",
+    "\\newenvironment",
+    "\\renewenvironment",
+    "\\DeclareMathOperator",
+];
+
+/// Stops `\def\a{\a}` and other recursive macros.
+const MAX_EXPANSIONS: usize = 10000;
+
+/// The macros and environments defined by the formulas or the application, shared by every formula.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LaTeXMacros {
+    commands: BTreeMap<String, LaTeXMacro>,
+    environments: BTreeMap<String, (LaTeXMacro, String)>,
+}
+
+/// A macro from `\newcommand`, `\def` or [`LaTeXEngine::define_macro`].
+#[derive(Clone, Debug, Default)]
+struct LaTeXMacro {
+    /// Text which must follow the name, such as `(` in `\def\pair(#1,#2){..}`.
+    prefix: String,
+    /// The delimiter after each parameter, empty for a group or a single token.
+    parameters: Vec<String>,
+    /// The default of the first parameter, which is then optional and written in brackets.
+    default: Option<String>,
+    body: String,
+}
+
+impl LaTeXEngine {
+    /// Define a macro such as `R` for `\mathbb{R}`, the parameters `#1` to `#9` are counted from the body.
+    pub fn define_macro(&self, name: &str, body: &str) {
+        let count = body.split('#').skip(1).filter_map(|rest| rest.chars().next()?.to_digit(10)).max().unwrap_or(0);
+        let command =
+            LaTeXMacro { parameters: vec![String::new(); count as usize], body: body.to_string(), ..Default::default() };
+        self.macros.borrow_mut().commands.insert(name.trim_start_matches('\\').to_string(), command);
+    }
+    /// Expand the macros of the formula, `\newcommand` and the other definitions are registered and removed.
+    ///
+    /// ```
+    /// # use mathml_latex::LaTeXEngine;
+    /// let context = LaTeXEngine::builtin();
+    /// context.expand_macros(r"\newcommand{\norm}[1]{\lVert #1 \rVert}").unwrap();
+    /// assert_eq!(context.expand_macros(r"\norm{x}").unwrap(), r"\lVert x \rVert");
+    /// ```
+    pub fn expand_macros<'i>(&self, source: &'i str) -> Result<Cow<'i, str>, StopBecause> {
+        Ok(self.expand_with_origins(source)?.0)
+    }
+    /// Expand the macros, parse and convert the formula, errors are reported at their offset in the source.
+    ///
+    /// An error inside the expansion of a macro is reported at the macro.
+    ///
+    /// ```
+    /// # use mathml_latex::LaTeXEngine;
+    /// # use pex::StopBecause;
+    /// let context = LaTeXEngine::builtin();
+    /// let mathml = context.render(r"\newcommand{\R}{\mathbb{R}} x + \R").unwrap();
+    /// assert_eq!(
+    ///     mathml.to_string(),
+    ///     r#"<mrow><mi>x</mi><mo>+</mo><mi mathvariant="double-struck">R</mi></mrow>"#
+    /// );
+    /// assert_eq!(
+    ///     context.render(r"\R^").unwrap_err(),
+    ///     StopBecause::Custom { message: "Missing superscript", position: 3 }
+    /// );
+    /// ```
+    pub fn render(&self, source: &str) -> Result<MathML, StopBecause> {
+        let (text, origins) = self.expand_with_origins(source)?;
+        match parse_latex(&text) {
+            Ok(node) => Ok(node.as_mathml(self)),
+            Err(error) if origins.is_empty() => Err(error),
+            Err(error) => Err(relocate(error, |position| origins[position.min(origins.len() - 1)])),
+        }
+    }
+    /// The expanded text and the offset in the source of each byte, empty if nothing was expanded.
+    fn expand_with_origins<'i>(&self, source: &'i str) -> Result<(Cow<'i, str>, Vec<usize>), StopBecause> {
+        let defined = {
+            let macros = self.macros.borrow();
+            !macros.commands.is_empty() || !macros.environments.is_empty()
+        };
+        if !defined && !DEFINITIONS.iter().any(|name| source.contains(name)) {
+            return Ok((Cow::Borrowed(source), vec![]));
+        }
+        let mut expander = Expander {
+            context: self,
+            text: source.to_string(),
+            origins: (0..=source.len()).collect(),
+            position: 0,
+            expansions: 0,
+        };
+        if let Err(error) = expander.expand() {
+            return Err(relocate(error, |position| expander.origins[position.min(expander.text.len())]));
+        }
+        match expander.text == source {
+            true => Ok((Cow::Borrowed(source), vec![])),
+            false => Ok((Cow::Owned(expander.text), expander.origins)),
+        }
+    }
+}
+
+/// Move the error to another offset, such as from the expanded text to the source.
+fn relocate(error: StopBecause, offset: impl Fn(usize) -> usize) -> StopBecause {
+    match error {
+        StopBecause::Uninitialized => StopBecause::Uninitialized,
+        StopBecause::ExpectEof { position } => StopBecause::ExpectEof { position: offset(position) },
+        StopBecause::ExpectRepeats { min, current, position } => {
+            StopBecause::ExpectRepeats { min, current, position: offset(position) }
+        }
+        StopBecause::MissingCharacter { expected, position } => {
+            StopBecause::MissingCharacter { expected, position: offset(position) }
+        }
+        StopBecause::MissingCharacterRange { start, end, position } => {
+            StopBecause::MissingCharacterRange { start, end, position: offset(position) }
+        }
+        StopBecause::MissingString { message, position } => StopBecause::MissingString { message, position: offset(position) },
+        StopBecause::MustBe { message, position } => StopBecause::MustBe { message, position: offset(position) },
+        StopBecause::ShouldNotBe { message, position } => StopBecause::ShouldNotBe { message, position: offset(position) },
+        StopBecause::Custom { message, position } => StopBecause::Custom { message, position: offset(position) },
+    }
+}
+
+/// Rewrites the text in place, the expansion of a macro is scanned again as in TeX.
+struct Expander<'c> {
+    context: &'c LaTeXEngine,
+    text: String,
+    /// The offset in the source of each byte of the text and of its end, an expansion points to its macro.
+    origins: Vec<usize>,
+    position: usize,
+    expansions: usize,
+}
+
+impl<'c> Expander<'c> {
+    fn expand(&mut self) -> Result<(), StopBecause> {
+        while let Some(c) = self.text[self.position..].chars().next() {
+            if c != '\\' {
+                self.position += c.len_utf8();
+                continue;
+            }
+            let start = self.position;
+            let end = self.control_sequence_end(start);
+            let name = self.text[start + 1..end].to_string();
+            let starred = name.ends_with('*');
+            let name = name.trim_end_matches('*');
+            match name {
+                "newcommand" | "renewcommand" | "providecommand" => {
+                    self.define_command(start, end, name == "providecommand")?
+                }
+                "def" => self.define_delimited(start, end)?,
+                "let" => self.define_alias(start, end)?,
+                "newenvironment" | "renewenvironment" => self.define_environment(start, end)?,
+                "DeclareMathOperator" => self.declare_operator(start, end, starred)?,
+                "begin" | "end" => self.expand_environment(start, end, name == "begin")?,
+                _ => {
+                    let command = self.context.macros.borrow().commands.get(name).cloned();
+                    match command {
+                        Some(command) => {
+                            let (expansion, end) = self.expand_macro(&command, end, start)?;
+                            self.replace(start, end, &expansion)?;
+                        }
+                        None => self.position = end,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn replace(&mut self, start: usize, end: usize, expansion: &str) -> Result<(), StopBecause> {
+        self.expansions += 1;
+        if self.expansions > MAX_EXPANSIONS {
+            StopBecause::custom_error("Too many macro expansions", start)?
+        }
+        self.text.replace_range(start..end, expansion);
+        let origin = self.origins[start];
+        self.origins.splice(start..end, repeat_n(origin, expansion.len()));
+        self.position = start;
+        Ok(())
+    }
+    /// `\name`, `\name*` for the definitions, or a backslash and a single character.
+    fn control_sequence_end(&self, start: usize) -> usize {
+        let rest = &self.text[start + 1..];
+        match rest.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                let end = start + 1 + rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+                match self.text[end..].starts_with('*') && DEFINITIONS.contains(&&self.text[start..end]) {
+                    true => end + 1,
+                    false => end,
+                }
+            }
+            Some(c) => start + 1 + c.len_utf8(),
+            None => start + 1,
+        }
+    }
+    fn skip_spaces(&self, at: usize) -> usize {
+        at + self.text[at..].len() - self.text[at..].trim_start().len()
+    }
+    /// `{..}` without the braces, `at` is the opening brace.
+    fn read_group(&self, at: usize) -> Result<(String, usize), StopBecause> {
+        let mut depth = 0usize;
+        let mut escaped = false;
+        for (offset, c) in self.text[at..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '{' => depth += 1,
+                '}' if depth == 1 => return Ok((self.text[at + 1..at + offset].to_string(), at + offset + 1)),
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        StopBecause::custom_error("Missing `}`", at)
+    }
+    /// A group, a control sequence or a single character.
+    fn read_argument(&self, at: usize) -> Result<(String, usize), StopBecause> {
+        let at = self.skip_spaces(at);
+        match self.text[at..].chars().next() {
+            Some('{') => self.read_group(at),
+            Some('\\') => {
+                let end = self.control_sequence_end(at);
+                Ok((self.text[at..end].to_string(), end))
+            }
+            Some('}') | None => StopBecause::custom_error("Missing argument", at),
+            Some(c) => Ok((c.to_string(), at + c.len_utf8())),
+        }
+    }
+    /// `[..]` without the brackets, braces protect the brackets inside.
+    fn read_optional(&self, at: usize) -> Result<Option<(String, usize)>, StopBecause> {
+        let start = self.skip_spaces(at);
+        if !self.text[start..].starts_with('[') {
+            return Ok(None);
+        }
+        let mut depth = 0usize;
+        for (offset, c) in self.text[start..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                ']' if depth == 0 => return Ok(Some((self.text[start + 1..start + offset].to_string(), start + offset + 1))),
+                _ => {}
+            }
+        }
+        StopBecause::custom_error("Missing `]`", start)
+    }
+    /// The text up to the delimiter of a `\def` parameter, a single group loses its braces.
+    fn read_delimited(&self, at: usize, delimiter: &str) -> Result<(String, usize), StopBecause> {
+        let mut depth = 0usize;
+        for (offset, c) in self.text[at..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ if depth == 0 && self.text[at + offset..].starts_with(delimiter) => {
+                    let argument = &self.text[at..at + offset];
+                    let argument = match argument.starts_with('{') {
+                        true => match self.read_group(at) {
+                            Ok((inner, end)) if end == at + offset => inner,
+                            _ => argument.to_string(),
+                        },
+                        false => argument.to_string(),
+                    };
+                    return Ok((argument, at + offset + delimiter.len()));
+                }
+                _ => {}
+            }
+        }
+        StopBecause::custom_error("Use of a macro does not match its definition", at)
+    }
+    /// The name after `\newcommand` or `\let`, with or without braces.
+    fn read_name(&self, at: usize) -> Result<(String, usize), StopBecause> {
+        let (name, end) = self.read_argument(at)?;
+        match name.trim().strip_prefix('\\') {
+            Some(name) if !name.is_empty() => Ok((name.to_string(), end)),
+            _ => StopBecause::custom_error("Missing control sequence", at),
+        }
+    }
+    /// `[n][default]` of `\newcommand` and `\newenvironment`.
+    fn read_parameters(&self, at: usize) -> Result<(LaTeXMacro, usize), StopBecause> {
+        let mut command = LaTeXMacro::default();
+        let Some((count, end)) = self.read_optional(at)?
+        else {
+            return Ok((command, at));
+        };
+        match count.trim().parse::<usize>() {
+            Ok(count) if count <= 9 => command.parameters = vec![String::new(); count],
+            _ => StopBecause::custom_error("Invalid number of parameters", at)?,
+        }
+        match self.read_optional(end)? {
+            Some((default, end)) if !command.parameters.is_empty() => {
+                command.default = Some(default);
+                Ok((command, end))
+            }
+            _ => Ok((command, end)),
+        }
+    }
+    /// `\newcommand{\name}[n][default]{body}`
+    fn define_command(&mut self, start: usize, end: usize, provide: bool) -> Result<(), StopBecause> {
+        let (name, end) = self.read_name(end)?;
+        let (mut command, end) = self.read_parameters(end)?;
+        let (body, end) = self.read_argument(end)?;
+        command.body = body;
+        let mut macros = self.context.macros.borrow_mut();
+        if !(provide && macros.commands.contains_key(&name)) {
+            macros.commands.insert(name, command);
+        }
+        drop(macros);
+        self.remove(start, end);
+        Ok(())
+    }
+    /// `\def\name<parameter text>{body}`, such as `\def\pair(#1,#2){..}`.
+    fn define_delimited(&mut self, start: usize, end: usize) -> Result<(), StopBecause> {
+        let (name, end) = self.read_name(end)?;
+        let brace = match self.text[end..].find('{') {
+            Some(offset) => end + offset,
+            None => StopBecause::custom_error("Missing `{`", end)?,
+        };
+        let mut parts = self.text[end..brace].split('#');
+        let mut command = LaTeXMacro { prefix: parts.next().unwrap_or_default().trim().to_string(), ..Default::default() };
+        for (index, part) in parts.enumerate() {
+            match part.strip_prefix(char::from_digit(index as u32 + 1, 10).unwrap_or('#')) {
+                Some(delimiter) => command.parameters.push(delimiter.trim().to_string()),
+                None => StopBecause::custom_error("Parameters must be numbered consecutively", end)?,
+            }
+        }
+        let (body, end) = self.read_group(brace)?;
+        command.body = body;
+        self.context.macros.borrow_mut().commands.insert(name, command);
+        self.remove(start, end);
+        Ok(())
+    }
+    /// `\let\name\other` or `\let\name=\other`, other commands are kept as an alias.
+    fn define_alias(&mut self, start: usize, end: usize) -> Result<(), StopBecause> {
+        let (name, end) = self.read_name(end)?;
+        let mut end = self.skip_spaces(end);
+        if self.text[end..].starts_with('=') {
+            end += 1;
+        }
+        let (target, end) = self.read_argument(end)?;
+        let mut macros = self.context.macros.borrow_mut();
+        let command = match target.strip_prefix('\\').and_then(|target| macros.commands.get(target)) {
+            Some(command) => command.clone(),
+            None => LaTeXMacro { body: target, ..Default::default() },
+        };
+        macros.commands.insert(name, command);
+        drop(macros);
+        self.remove(start, end);
+        Ok(())
+    }
+    /// `\newenvironment{name}[n][default]{begin}{end}`
+    fn define_environment(&mut self, start: usize, end: usize) -> Result<(), StopBecause> {
+        let (name, end) = self.read_argument(end)?;
+        let (mut environment, end) = self.read_parameters(end)?;
+        let (begin, end) = self.read_argument(end)?;
+        let (finish, end) = self.read_argument(end)?;
+        environment.body = begin;
+        self.context.macros.borrow_mut().environments.insert(name.trim().to_string(), (environment, finish));
+        self.remove(start, end);
+        Ok(())
+    }
+    /// `\DeclareMathOperator{\argmax}{arg\,max}`, the scripts of the starred form are limits.
+    fn declare_operator(&mut self, start: usize, end: usize, limits: bool) -> Result<(), StopBecause> {
+        let (name, end) = self.read_name(end)?;
+        let (text, end) = self.read_argument(end)?;
+        self.context.declare_math_operator(name, operator_name(&text), limits);
+        self.remove(start, end);
+        Ok(())
+    }
+    /// `\begin{name}` and `\end{name}` of environments from `\newenvironment`.
+    fn expand_environment(&mut self, start: usize, end: usize, begin: bool) -> Result<(), StopBecause> {
+        let group = self.skip_spaces(end);
+        if !self.text[group..].starts_with('{') {
+            self.position = end;
+            return Ok(());
+        }
+        let (name, end) = self.read_group(group)?;
+        let environment = self.context.macros.borrow().environments.get(name.trim()).cloned();
+        match environment {
+            Some((command, _)) if begin => {
+                let (expansion, end) = self.expand_macro(&command, end, start)?;
+                self.replace(start, end, &expansion)
+            }
+            Some((_, finish)) => self.replace(start, end, &finish),
+            None => {
+                self.position = end;
+                Ok(())
+            }
+        }
+    }
+    /// Read the arguments after `at`, returns the body with the parameters replaced and the end of the arguments.
+    fn expand_macro(&self, command: &LaTeXMacro, at: usize, start: usize) -> Result<(String, usize), StopBecause> {
+        let mut end = at;
+        if !command.prefix.is_empty() {
+            end = self.skip_spaces(end);
+            match self.text[end..].starts_with(&command.prefix) {
+                true => end += command.prefix.len(),
+                false => StopBecause::custom_error("Use of a macro does not match its definition", start)?,
+            }
+        }
+        let mut arguments = Vec::with_capacity(command.parameters.len());
+        for (index, delimiter) in command.parameters.iter().enumerate() {
+            let (argument, next) = match &command.default {
+                Some(default) if index == 0 => self.read_optional(end)?.unwrap_or((default.clone(), end)),
+                _ if delimiter.is_empty() => self.read_argument(end)?,
+                _ => self.read_delimited(end, delimiter)?,
+            };
+            arguments.push(argument);
+            end = next;
+        }
+        Ok((substitute(&command.body, &arguments), end))
+    }
+    fn remove(&mut self, start: usize, end: usize) {
+        self.text.replace_range(start..end, "");
+        self.origins.drain(start..end);
+        self.position = start;
+    }
+}
+
+/// Replace `#1` to `#9` with the arguments, `##` is a single `#`.
+fn substitute(body: &str, arguments: &[String]) -> String {
+    let mut output = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek().copied()) {
+            ('#', Some('#')) => {
+                chars.next();
+                output.push('#');
+            }
+            ('#', Some(digit @ '1'..='9')) if (digit as usize - '1' as usize) < arguments.len() => {
+                chars.next();
+                output.push_str(&arguments[digit as usize - '1' as usize]);
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
@@ -1,10 +1,43 @@
-use std::collections::BTreeMap;
+use mathml_core::FontVariant;
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+};
 mod builtin;
+mod fonts;
+mod macros;
+mod numbering;
+
+pub(crate) use self::{
+    builtin::{operator_name, DELIMITERS},
+    numbering::EquationRow,
+};
 
 #[derive(Clone, Debug)]
 pub struct LaTeXEngine {
     functions: BTreeMap<String, String>,
+    limit_functions: BTreeMap<String, String>,
+    math_operators: RefCell<BTreeMap<String, (String, bool)>>,
     operators: BTreeMap<String, String>,
+    large_operators: BTreeMap<String, (String, bool)>,
     spaces: BTreeMap<String, f32>,
     letters: BTreeMap<String, String>,
+    delimiters: BTreeMap<String, String>,
+    accents: BTreeMap<String, LaTeXAccent>,
+    fonts: BTreeMap<String, FontVariant>,
+    unicode_fonts: bool,
+    font: Cell<Option<FontVariant>>,
+    macros: RefCell<macros::LaTeXMacros>,
+    numbering: RefCell<numbering::LaTeXNumbering>,
+}
+
+/// An accent command such as `\hat` or `\underbrace`, drawn over or under its argument.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaTeXAccent {
+    /// The character of the accent operator.
+    pub symbol: String,
+    /// Whether the accent is drawn under the argument.
+    pub under: bool,
+    /// Whether the accent stretches to the width of the argument, as `\widehat` does.
+    pub stretchy: bool,
 }
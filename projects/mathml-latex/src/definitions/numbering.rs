@@ -0,0 +1,71 @@
+use super::*;
+
+/// Equation counters and labels, shared by every formula converted with the engine.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LaTeXNumbering {
+    by_section: bool,
+    section: usize,
+    equation: usize,
+    labels: BTreeMap<String, String>,
+}
+
+/// The numbering of a row in `equation`, `align` or `gather`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EquationRow<'i> {
+    /// `\tag{..}` or `\tag*{..}`, the latter without parentheses.
+    pub tag: Option<(&'i str, bool)>,
+    /// `\notag` or `\nonumber`
+    pub hidden: bool,
+    /// `\label{..}`
+    pub labels: Vec<&'i str>,
+}
+
+impl LaTeXEngine {
+    /// Number equations within sections, as `(2.3)`, see [`LaTeXEngine::next_section`].
+    pub fn with_section_numbering(mut self, enable: bool) -> Self {
+        self.numbering.get_mut().by_section = enable;
+        self
+    }
+    /// Start a new section, the equation counter restarts if numbering within sections.
+    pub fn next_section(&self) {
+        let mut numbering = self.numbering.borrow_mut();
+        numbering.section += 1;
+        if numbering.by_section {
+            numbering.equation = 0;
+        }
+    }
+    /// Reset the counters but keep the labels, converting the document again resolves references to later equations.
+    pub fn reset_counters(&self) {
+        let mut numbering = self.numbering.borrow_mut();
+        numbering.section = 0;
+        numbering.equation = 0;
+    }
+    /// Gets the number of a `\label`, without parentheses.
+    pub fn get_label(&self, label: &str) -> Option<String> {
+        self.numbering.borrow().labels.get(label).cloned()
+    }
+    /// Step the equation counter for the row, returns the displayed number.
+    pub(crate) fn number_equation(&self, row: &EquationRow) -> Option<String> {
+        let mut numbering = self.numbering.borrow_mut();
+        let (number, display) = match row.tag {
+            Some((tag, true)) => (tag.to_string(), tag.to_string()),
+            Some((tag, false)) => (tag.to_string(), format!("({})", tag)),
+            None if row.hidden => return None,
+            None => {
+                numbering.equation += 1;
+                let number = match numbering.by_section {
+                    true => format!("{}.{}", numbering.section, numbering.equation),
+                    false => numbering.equation.to_string(),
+                };
+                (number.clone(), format!("({})", number))
+            }
+        };
+        for label in &row.labels {
+            match numbering.labels.insert(label.to_string(), number.clone()) {
+                Some(old) if old != number => tracing::warn!("Label `{}` multiply defined: {} vs {}", label, old, number),
+                _ => {}
+            }
+        }
+        Some(display)
+    }
+}
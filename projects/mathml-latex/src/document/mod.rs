@@ -0,0 +1,97 @@
+use crate::{parse_latex, LaTeXEngine, LaTeXNode};
+use mathml_core::{MathML, MathRoot};
+use pex::StopBecause;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::Range,
+};
+
+mod scanner;
+
+/// A formula found in a document, such as `$x^2$` or `\[x^2\]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaTeXFormula<'i> {
+    /// The LaTeX source, without delimiters.
+    pub source: &'i str,
+    /// Byte offset of the source in the document.
+    pub offset: usize,
+    /// Byte range of the formula in the document, with delimiters.
+    pub range: Range<usize>,
+    /// Whether the formula is display math, `$$..$$` or `\[..\]`.
+    pub display: bool,
+}
+
+/// A formula of the document which failed to parse.
+#[derive(Clone, Debug)]
+pub struct LaTeXFormulaError {
+    /// Byte range of the formula in the document, with delimiters.
+    pub range: Range<usize>,
+    /// Byte offset of the error in the document.
+    pub offset: usize,
+    /// The parse error, with offsets relative to the formula source, an error inside a macro is at the macro.
+    pub error: StopBecause,
+}
+
+/// Find all formulas in Markdown, HTML or plain text.
+///
+/// - inline math: `$..$` and `\(..\)`
+/// - display math: `$$..$$` and `\[..\]`
+///
+/// As in pandoc, the opening `$` must be followed by a non-space character, and the closing `$` must follow a non-space
+/// character and must not be followed by a digit, so `$5 and $10` is not math. Dollars escaped as `\$`, code spans and the
+/// content of `<code>`, `<pre>`, `<script>` and `<style>` are skipped.
+pub fn find_formulas(text: &str) -> Vec<LaTeXFormula<'_>> {
+    scanner::Scanner::new(text).collect()
+}
+
+/// Replace all formulas in the document with MathML, the text around them is kept as written.
+///
+/// ```
+/// # use mathml_latex::{replace, LaTeXEngine};
+/// let html = replace("$a + b$ costs $5 and $10", &LaTeXEngine::builtin()).unwrap();
+/// assert_eq!(html, r#"<math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math> costs $5 and $10"#);
+/// ```
+pub fn replace(text: &str, context: &LaTeXEngine) -> Result<String, Vec<LaTeXFormulaError>> {
+    let mut output = String::with_capacity(text.len());
+    let mut errors = vec![];
+    let mut last = 0;
+    for formula in find_formulas(text) {
+        output.push_str(&text[last..formula.range.start]);
+        match formula.as_mathml(context) {
+            Ok(mathml) => output.push_str(&mathml.to_string()),
+            Err(e) => errors.push(e),
+        }
+        last = formula.range.end;
+    }
+    output.push_str(&text[last..]);
+    match errors.is_empty() {
+        true => Ok(output),
+        false => Err(errors),
+    }
+}
+
+impl<'i> LaTeXFormula<'i> {
+    /// Parse the source of the formula.
+    pub fn parse(&self) -> Result<LaTeXNode<'i>, LaTeXFormulaError> {
+        parse_latex(self.source).map_err(|error| self.error(error))
+    }
+    /// Converts the formula into a `<math>` element, with the display style of its delimiters.
+    ///
+    /// The macros of the engine are expanded first, see [`LaTeXEngine::render`].
+    pub fn as_mathml(&self, context: &LaTeXEngine) -> Result<MathML, LaTeXFormulaError> {
+        let node = context.render(self.source).map_err(|error| self.error(error))?;
+        Ok(MathRoot::new(vec![node]).with_display_style(self.display).into())
+    }
+    fn error(&self, error: StopBecause) -> LaTeXFormulaError {
+        LaTeXFormulaError { range: self.range.clone(), offset: self.offset + error.range().start, error }
+    }
+}
+
+impl Error for LaTeXFormulaError {}
+
+impl Display for LaTeXFormulaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid formula at {}..{}, {} at {}", self.range.start, self.range.end, self.error, self.offset)
+    }
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+/// HTML elements whose content is never math.
+const RAW_ELEMENTS: &[&str] = &["code", "pre", "script", "style"];
+
+/// Finds formulas from left to right, all delimiters are ASCII so byte offsets are always char boundaries.
+pub(super) struct Scanner<'i> {
+    text: &'i str,
+    bytes: &'i [u8],
+    position: usize,
+}
+
+impl<'i> Scanner<'i> {
+    pub fn new(text: &'i str) -> Self {
+        Self { text, bytes: text.as_bytes(), position: 0 }
+    }
+    fn formula(&self, start: usize, open: usize, close: usize, end: usize, display: bool) -> LaTeXFormula<'i> {
+        LaTeXFormula { source: &self.text[start + open..end - close], offset: start + open, range: start..end, display }
+    }
+    /// `\(..\)` or `\[..\]`, other escapes inside are skipped.
+    fn scan_escaped(&self, start: usize, close: u8) -> Option<usize> {
+        let mut i = start + 2;
+        while i + 1 < self.bytes.len() {
+            if self.bytes[i] == b'\\' {
+                if self.bytes[i + 1] == close {
+                    return Some(i + 2);
+                }
+                i += 1;
+            }
+            i += 1;
+        }
+        None
+    }
+    /// `$$..$$`
+    fn scan_display(&self, start: usize) -> Option<usize> {
+        let mut i = start + 2;
+        while i + 1 < self.bytes.len() {
+            match self.bytes[i] {
+                b'\\' => i += 1,
+                b'$' if self.bytes[i + 1] == b'$' => return Some(i + 2),
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+    /// `$..$`, with the pandoc rules for the opening and closing dollars.
+    fn scan_inline(&self, start: usize) -> Option<usize> {
+        match self.bytes.get(start + 1) {
+            Some(c) if !c.is_ascii_whitespace() => {}
+            _ => return None,
+        }
+        let mut i = start + 1;
+        while i < self.bytes.len() {
+            match self.bytes[i] {
+                b'\\' => i += 1,
+                // code spans bind tighter than math
+                b'`' => return None,
+                b'<' if self.skip_raw_element(i).is_some() => return None,
+                // inline math never spans paragraphs
+                b'\n' if self.text[i + 1..].trim_start_matches([' ', '\t', '\r']).starts_with('\n') => return None,
+                b'$' => {
+                    let after_space = self.bytes[i - 1].is_ascii_whitespace();
+                    let before_digit = self.bytes.get(i + 1).is_some_and(|c| c.is_ascii_digit());
+                    if !after_space && !before_digit {
+                        return Some(i + 1);
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+    /// A code span closes with a backtick run of the same length, otherwise the backticks are literal.
+    fn skip_code_span(&self, start: usize) -> usize {
+        let run = self.bytes[start..].iter().take_while(|c| **c == b'`').count();
+        let mut i = start + run;
+        while i < self.bytes.len() {
+            if self.bytes[i] == b'`' {
+                let close = self.bytes[i..].iter().take_while(|c| **c == b'`').count();
+                if close == run {
+                    return i + close;
+                }
+                i += close;
+            }
+            else {
+                i += 1;
+            }
+        }
+        start + run
+    }
+    /// `<code>..</code>` and similar elements.
+    fn skip_raw_element(&self, start: usize) -> Option<usize> {
+        let rest = &self.bytes[start + 1..];
+        let name = RAW_ELEMENTS.iter().find(|name| {
+            rest.len() > name.len()
+                && rest[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+                && matches!(rest[name.len()], b'>' | b' ' | b'\t' | b'\r' | b'\n')
+        })?;
+        let close = format!("</{}>", name);
+        let end = (start..self.bytes.len().saturating_sub(close.len() - 1))
+            .find(|i| self.bytes[*i..*i + close.len()].eq_ignore_ascii_case(close.as_bytes()))
+            .map_or(self.bytes.len(), |i| i + close.len());
+        Some(end)
+    }
+}
+
+impl<'i> Iterator for Scanner<'i> {
+    type Item = LaTeXFormula<'i>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.bytes.len() {
+            let start = self.position;
+            match self.bytes[start] {
+                b'\\' => match self.bytes.get(start + 1) {
+                    Some(b'(') | Some(b'[') => {
+                        let (close, display) = if self.bytes[start + 1] == b'(' { (b')', false) } else { (b']', true) };
+                        if let Some(end) = self.scan_escaped(start, close) {
+                            self.position = end;
+                            return Some(self.formula(start, 2, 2, end, display));
+                        }
+                        self.position += 2;
+                    }
+                    // `\$`, `\\` or any other escape
+                    _ => self.position += 2,
+                },
+                b'`' => self.position = self.skip_code_span(start),
+                b'<' => self.position = self.skip_raw_element(start).unwrap_or(start + 1),
+                b'$' if self.bytes.get(start + 1) == Some(&b'$') => match self.scan_display(start) {
+                    Some(end) if !self.text[start + 2..end - 2].trim().is_empty() => {
+                        self.position = end;
+                        return Some(self.formula(start, 2, 2, end, true));
+                    }
+                    _ => self.position += 2,
+                },
+                b'$' => match self.scan_inline(start) {
+                    Some(end) => {
+                        self.position = end;
+                        return Some(self.formula(start, 1, 1, end, false));
+                    }
+                    None => self.position += 1,
+                },
+                _ => self.position += 1,
+            }
+        }
+        None
+    }
+}
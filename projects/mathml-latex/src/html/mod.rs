@@ -0,0 +1,193 @@
+use crate::{find_formulas, LaTeXEngine, LaTeXFormula};
+
+mod entities;
+
+/// Elements whose content is never math.
+const RAW_ELEMENTS: &[&str] = &["code", "pre", "script", "style", "textarea"];
+
+/// Rewrites the math of html pages written for MathJax into MathML, chunk by chunk.
+///
+/// Formulas are only searched in text nodes, so tags, attributes, comments and the content of `<code>`, `<pre>`,
+/// `<script>`, `<style>` and `<textarea>` are kept as written. Character references inside formulas are decoded, so
+/// `\(a &lt; b\)` is read as `a < b`. Formulas which fail to parse are kept as written.
+///
+/// ```
+/// # use mathml_latex::{HtmlRewriter, LaTeXEngine};
+/// let context = LaTeXEngine::builtin();
+/// let mut rewriter = HtmlRewriter::new(&context);
+/// let mut html = rewriter.write(r#"<p title="\(x\)">Solve \(a "#);
+/// html.push_str(&rewriter.write(r#"+ b\)</p><code>\(c\)</code>"#));
+/// html.push_str(&rewriter.finish());
+/// assert_eq!(
+///     html,
+///     r#"<p title="\(x\)">Solve <math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math></p><code>\(c\)</code>"#
+/// );
+/// ```
+#[derive(Debug)]
+pub struct HtmlRewriter<'c> {
+    context: &'c LaTeXEngine,
+    inline_dollars: bool,
+    buffer: String,
+    raw: Option<&'static str>,
+}
+
+/// Rewrite the math of a whole html page, see [`HtmlRewriter`].
+pub fn rewrite_html(html: &str, context: &LaTeXEngine) -> String {
+    let mut rewriter = HtmlRewriter::new(context);
+    let mut output = rewriter.write(html);
+    output.push_str(&rewriter.finish());
+    output
+}
+
+impl<'c> HtmlRewriter<'c> {
+    /// Create a rewriter, all formulas of the page share the engine.
+    pub fn new(context: &'c LaTeXEngine) -> Self {
+        Self { context, inline_dollars: false, buffer: String::new(), raw: None }
+    }
+    /// Also rewrite `$..$`, which MathJax leaves alone by default.
+    pub fn with_inline_dollars(mut self, enable: bool) -> Self {
+        self.inline_dollars = enable;
+        self
+    }
+    /// Feed the next chunk of the page, returns the html which is complete so far.
+    pub fn write(&mut self, chunk: &str) -> String {
+        self.buffer.push_str(chunk);
+        let mut output = String::with_capacity(self.buffer.len());
+        let consumed = self.rewrite_complete(&mut output);
+        self.buffer.drain(..consumed);
+        output
+    }
+    /// End of the page, returns the rest of the html.
+    pub fn finish(mut self) -> String {
+        let buffer = std::mem::take(&mut self.buffer);
+        match self.raw {
+            Some(_) => buffer,
+            None => self.rewrite_text(&buffer),
+        }
+    }
+    /// Rewrite all complete nodes of the buffer, returns the bytes consumed.
+    fn rewrite_complete(&mut self, output: &mut String) -> usize {
+        let bytes = self.buffer.as_bytes();
+        let mut position = 0;
+        loop {
+            let rest = &self.buffer[position..];
+            if let Some(name) = self.raw {
+                match find_close_tag(rest, name) {
+                    Some(end) => {
+                        output.push_str(&rest[..end]);
+                        position += end;
+                        self.raw = None;
+                        continue;
+                    }
+                    // keep a partial `</name` for the next chunk
+                    None => {
+                        let mut keep = rest.len().saturating_sub(name.len() + 2);
+                        while !rest.is_char_boundary(keep) {
+                            keep -= 1;
+                        }
+                        output.push_str(&rest[..keep]);
+                        return position + keep;
+                    }
+                }
+            }
+            // text nodes end at the next markup, which may not have arrived yet
+            let start = match find_markup(rest) {
+                Some(start) => start,
+                None => return position,
+            };
+            let end = match markup_end(&bytes[position + start..]) {
+                Some(end) => start + end,
+                None => return position,
+            };
+            output.push_str(&self.rewrite_text(&rest[..start]));
+            let markup = &rest[start..end];
+            output.push_str(markup);
+            self.raw = raw_element(markup);
+            position += end;
+        }
+    }
+    fn rewrite_text(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+        for formula in find_formulas(text) {
+            if !formula.display && text[formula.range.start..].starts_with('$') && !self.inline_dollars {
+                continue;
+            }
+            let source = entities::decode_entities(formula.source);
+            match (LaTeXFormula { source: &source, ..formula.clone() }).as_mathml(self.context) {
+                Ok(mathml) => {
+                    output.push_str(&text[last..formula.range.start]);
+                    output.push_str(&mathml.to_string());
+                    last = formula.range.end;
+                }
+                Err(e) => tracing::warn!("Invalid formula `{}`: {}", formula.source, e),
+            }
+        }
+        output.push_str(&text[last..]);
+        output
+    }
+}
+
+/// A `<` which starts a tag, a comment or a doctype, other `<` are text.
+fn find_markup(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut offset = 0;
+    while let Some(i) = text[offset..].find('<') {
+        let start = offset + i;
+        match bytes.get(start + 1) {
+            // wait for the next chunk
+            None => return Some(start),
+            Some(c) if c.is_ascii_alphabetic() || matches!(c, b'/' | b'!' | b'?') => return Some(start),
+            Some(_) => offset = start + 1,
+        }
+    }
+    None
+}
+
+/// The end of the markup at the start of `bytes`, `None` if it is not complete yet.
+fn markup_end(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < 4 && b"<!--".starts_with(bytes) {
+        return None;
+    }
+    if bytes.starts_with(b"<!--") {
+        let end = bytes[4..].windows(3).position(|w| w == b"-->")?;
+        return Some(end + 7);
+    }
+    if bytes.len() < 2 {
+        return None;
+    }
+    // `>` inside quoted attribute values doesn't close the tag
+    let mut quote = None;
+    for (i, c) in bytes.iter().enumerate().skip(1) {
+        match (quote, c) {
+            (None, b'"' | b'\'') => quote = Some(*c),
+            (Some(q), _) if q == *c => quote = None,
+            (None, b'>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The name of the raw element opened by the tag.
+fn raw_element(markup: &str) -> Option<&'static str> {
+    let name = markup[1..].split(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/').next()?;
+    let element = RAW_ELEMENTS.iter().find(|raw| raw.eq_ignore_ascii_case(name))?;
+    // `<code/>` has no content
+    match markup.ends_with("/>") {
+        true => None,
+        false => Some(element),
+    }
+}
+
+/// The start of the close tag of the raw element.
+fn find_close_tag(text: &str, name: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    (0..bytes.len()).find(|i| {
+        let rest = &bytes[*i..];
+        rest.len() > name.len() + 2
+            && rest.starts_with(b"</")
+            && rest[2..2 + name.len()].eq_ignore_ascii_case(name.as_bytes())
+            && matches!(rest[2 + name.len()], b'>' | b' ' | b'\t' | b'\r' | b'\n')
+    })
+}
@@ -7,10 +7,19 @@
 mod block;
 mod codegen;
 mod definitions;
+mod document;
+mod html;
+#[cfg(feature = "pulldown-cmark")]
+mod markdown;
 mod parser;
 
 pub use crate::{
     block::LaTeXBlock,
-    definitions::LaTeXEngine,
+    definitions::{LaTeXAccent, LaTeXEngine},
+    document::{find_formulas, replace, LaTeXFormula, LaTeXFormulaError},
+    html::{rewrite_html, HtmlRewriter},
     parser::{parse_latex, LaTeXNode},
 };
+
+#[cfg(feature = "pulldown-cmark")]
+pub use crate::markdown::{MarkdownMath, MathErrorStyle};
@@ -0,0 +1,77 @@
+use crate::LaTeXEngine;
+use mathml_core::{MathError, MathML, MathRoot};
+use pulldown_cmark::{CowStr, Event};
+
+/// How formulas which fail to parse are rendered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MathErrorStyle {
+    /// An [`<merror>`](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/merror) with the source of the formula.
+    #[default]
+    Error,
+    /// The source of the formula as text, with its delimiters.
+    Source,
+}
+
+/// Converts the `InlineMath` and `DisplayMath` events of [pulldown-cmark](https://docs.rs/pulldown-cmark) into MathML html.
+///
+/// Enable math with `Options::ENABLE_MATH`, all formulas of the document share the engine.
+///
+/// ```
+/// # use mathml_latex::{LaTeXEngine, MarkdownMath};
+/// # use pulldown_cmark::{html::push_html, Options, Parser};
+/// let context = LaTeXEngine::builtin();
+/// let parser = Parser::new_ext("Solve $a + b = c$.", Options::ENABLE_MATH);
+/// let mut html = String::new();
+/// push_html(&mut html, MarkdownMath::new(parser, &context));
+/// assert_eq!(html, "<p>Solve <math display=\"inline\"><mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>=</mo><mi>c</mi></mrow></math>.</p>\n");
+/// ```
+#[derive(Debug)]
+pub struct MarkdownMath<'c, I> {
+    events: I,
+    context: &'c LaTeXEngine,
+    error_style: MathErrorStyle,
+}
+
+impl<'c, I> MarkdownMath<'c, I> {
+    /// Wrap the events of a pulldown-cmark parser.
+    pub fn new(events: I, context: &'c LaTeXEngine) -> Self {
+        Self { events, context, error_style: MathErrorStyle::default() }
+    }
+    /// Render the formulas which fail to parse in the given style.
+    pub fn with_error_style(mut self, style: MathErrorStyle) -> Self {
+        self.error_style = style;
+        self
+    }
+    fn render<'a>(&self, source: CowStr<'a>, display: bool) -> Event<'a> {
+        let node = match self.context.render(&source) {
+            Ok(node) => node,
+            Err(e) => {
+                tracing::warn!("Invalid formula `{}`: {}", source, e);
+                match self.error_style {
+                    MathErrorStyle::Error => MathError::new(MathML::text(&*source)).into(),
+                    MathErrorStyle::Source => {
+                        let delimiter = if display { "$$" } else { "$" };
+                        return Event::Text(format!("{}{}{}", delimiter, source, delimiter).into());
+                    }
+                }
+            }
+        };
+        let math = MathRoot::new(vec![node]).with_display_style(display);
+        Event::InlineHtml(math.to_string().into())
+    }
+}
+
+impl<'c, 'a, I> Iterator for MarkdownMath<'c, I>
+where
+    I: Iterator<Item = Event<'a>>,
+{
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.events.next()? {
+            Event::InlineMath(source) => Some(self.render(source, false)),
+            Event::DisplayMath(source) => Some(self.render(source, true)),
+            event => Some(event),
+        }
+    }
+}
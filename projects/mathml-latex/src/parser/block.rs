@@ -1,9 +1,28 @@
 use super::*;
 
+/// The environments of `\begin{..}`, others must be defined by `\newenvironment`.
+#[rustfmt::skip]
+const ENVIRONMENTS: &[&str] = &[
+    "matrix", "Bmatrix", "bmatrix", "pmatrix", "Vmatrix", "vmatrix", "cases",
+    "aligned", "gathered", "split",
+    "equation", "equation*", "align", "align*", "alignat", "alignat*", "gather", "gather*", "multline", "multline*",
+];
+
 impl<'i> LaTeXBlock<'i> {
     pub fn parse(input: ParseState<'i>) -> ParseResult<LaTeXBlock<'i>> {
         let (state, begin) = input.skip(whitespace).match_fn(parse_begin)?;
-        let (state, children) = state.skip(whitespace).match_repeats(LaTeXNode::parse_atomic)?;
+        if !ENVIRONMENTS.contains(&begin) {
+            StopBecause::custom_error("Unknown environment", input.skip(whitespace).start_offset)?
+        }
+        // the number of column pairs is implied by the `&`
+        let state = match begin.trim_end_matches('*') {
+            "alignat" => match state.skip(whitespace).match_fn(parse_raw_group) {
+                ParseResult::Pending(state, _) => state,
+                ParseResult::Stop(_) => StopBecause::custom_error("Missing number of columns", state.start_offset)?,
+            },
+            _ => state,
+        };
+        let (state, children) = parse_repeats(state.skip(whitespace), LaTeXNode::parse_scripted)?;
         let (state, end) = state.skip(whitespace).match_fn(parse_end)?;
         if begin != end {
             tracing::warn!("Mismatched begin/end: {} vs {}", begin, end);
@@ -15,7 +34,7 @@ impl<'i> LaTeXBlock<'i> {
 fn parse_begin<'i>(input: ParseState<'i>) -> ParseResult<&'i str> {
     let (state, _) = input.match_str("\\begin", false)?;
     let (state, _) = state.skip(whitespace).match_char('{')?;
-    let (state, kind) = state.skip(whitespace).match_str_if(|c| c.is_ascii_alphabetic(), "ASCII_ALPHA")?;
+    let (state, kind) = state.skip(whitespace).match_fn(parse_environment)?;
     let (state, _) = state.skip(whitespace).match_char('}')?;
     state.finish(kind)
 }
@@ -23,7 +42,14 @@ fn parse_begin<'i>(input: ParseState<'i>) -> ParseResult<&'i str> {
 fn parse_end<'i>(input: ParseState<'i>) -> ParseResult<&'i str> {
     let (state, _) = input.match_str("\\end", false)?;
     let (state, _) = state.skip(whitespace).match_char('{')?;
-    let (state, kind) = state.skip(whitespace).match_str_if(|c| c.is_ascii_alphabetic(), "ASCII_ALPHA")?;
+    let (state, kind) = state.skip(whitespace).match_fn(parse_environment)?;
     let (state, _) = state.skip(whitespace).match_char('}')?;
     state.finish(kind)
 }
+
+/// `environment := ASCII_ALPHA+ '*'?`, such as `align*`.
+fn parse_environment<'i>(input: ParseState<'i>) -> ParseResult<'i, &'i str> {
+    let (state, name) = input.match_str_if(|c| c.is_ascii_alphabetic(), "ASCII_ALPHA")?;
+    let starred = usize::from(state.rest_text.starts_with('*'));
+    state.advance(starred).finish(&input.rest_text[..name.len() + starred])
+}
@@ -0,0 +1,73 @@
+use super::*;
+use crate::{block::LaTeXFenced, definitions::DELIMITERS};
+
+/// Delimiters written as a single character.
+const DELIMITER_CHARACTERS: &str = "()[]|/.<>";
+
+impl<'i> LaTeXNode<'i> {
+    /// `fenced := '\left' delimiter (scripted | '\middle' delimiter)* '\right' delimiter`
+    pub(super) fn parse_fenced(input: ParseState<'i>) -> ParseResult<'i, LaTeXNode<'i>> {
+        let (state, open) = input.match_fn(|state| parse_delimiter_command(state, "\\left"))?;
+        let mut state = state;
+        let mut fenced = LaTeXFenced { delimiters: vec![open], parts: vec![] };
+        let mut children = vec![];
+        loop {
+            let next = state.skip(whitespace);
+            match parse_delimiter_command(next, "\\right") {
+                ParseResult::Pending(next, close) => {
+                    fenced.delimiters.push(close);
+                    fenced.parts.push(LaTeXNode::Row { children }.refine());
+                    return next.finish(LaTeXNode::Fenced(fenced));
+                }
+                ParseResult::Stop(e) if is_fatal(&e) => return ParseResult::Stop(e),
+                ParseResult::Stop(_) => {}
+            }
+            match parse_delimiter_command(next, "\\middle") {
+                ParseResult::Pending(next, middle) => {
+                    fenced.delimiters.push(middle);
+                    fenced.parts.push(LaTeXNode::Row { children: std::mem::take(&mut children) }.refine());
+                    state = next;
+                    continue;
+                }
+                ParseResult::Stop(e) if is_fatal(&e) => return ParseResult::Stop(e),
+                ParseResult::Stop(_) => {}
+            }
+            match Self::parse_scripted(next) {
+                ParseResult::Pending(next, node) => {
+                    children.push(node);
+                    state = next;
+                }
+                ParseResult::Stop(e) if is_fatal(&e) => return ParseResult::Stop(e),
+                ParseResult::Stop(_) => StopBecause::custom_error("Missing `\\right` for `\\left`", input.start_offset)?,
+            }
+        }
+    }
+}
+
+/// `\left`, `\middle` or `\right` with its delimiter, a missing or unknown delimiter is fatal.
+fn parse_delimiter_command<'i>(input: ParseState<'i>, command: &'static str) -> ParseResult<'i, &'i str> {
+    let (state, _) = input.match_str(command, false)?;
+    if state.rest_text.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        StopBecause::missing_string(command, input.start_offset)?
+    }
+    let state = state.skip(whitespace);
+    let rest = state.rest_text;
+    let length = match rest.strip_prefix('\\') {
+        Some(name) => match name.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(name.len()) {
+            // `\{`, `\}` and `\|`
+            0 if name.starts_with(['{', '}', '|']) => 2,
+            0 => 0,
+            letters => letters + 1,
+        },
+        None if rest.starts_with(|c| DELIMITER_CHARACTERS.contains(c)) => 1,
+        None => 0,
+    };
+    if length == 0 {
+        StopBecause::custom_error("Missing delimiter", state.start_offset)?
+    }
+    let name = &rest[..length];
+    if name != "." && !DELIMITERS.iter().any(|(delimiter, _)| *delimiter == name) {
+        StopBecause::custom_error("Unknown delimiter", state.start_offset)?
+    }
+    state.advance_view(length)
+}
@@ -1,9 +1,14 @@
-use crate::{block::LaTeXCommand, LaTeXBlock};
+use crate::{
+    block::{LaTeXCommand, LaTeXFenced, LaTeXScript},
+    LaTeXBlock,
+};
 
 use pex::{helpers::whitespace, ParseResult, ParseState, StopBecause};
 
 mod block;
+mod fenced;
 mod sup_sub;
+mod text;
 
 pub fn parse_latex(s: &str) -> Result<LaTeXNode, StopBecause> {
     let state = ParseState::new(s.trim_end()).skip(whitespace);
@@ -39,10 +44,10 @@ pub enum LaTeXNode<'i> {
     Operation {
         operator: &'i str,
     },
-    Superscript {
-        lhs: Box<LaTeXNode<'i>>,
-        rhs: Box<LaTeXNode<'i>>,
-    },
+    /// `\left( x \right)`
+    Fenced(LaTeXFenced<'i>),
+    /// `x_i^2`, `f'` or `{}_n C_k`
+    Script(LaTeXScript<'i>),
     Letter {
         identifier: &'i str,
     },
@@ -70,27 +75,22 @@ impl<'i> LaTeXNode<'i> {
 
 impl<'i> LaTeXNode<'i> {
     pub fn parse(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
-        let (state, node) = input.begin_choice().or_else(Self::parse_combined).or_else(Self::parse_row).end_choice()?;
-        state.finish(node)
+        Self::parse_row(input)
     }
     fn parse_block(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
         let (state, block) = LaTeXBlock::parse(input)?;
         state.finish(LaTeXNode::Block(block))
     }
-    fn parse_combined(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
-        let (state, lhs) = input.begin_choice().or_else(Self::parse_super_script).end_choice()?;
-        state.finish(lhs)
-    }
-    /// `group := '{' atomic* '}'`
+    /// `group := '{' scripted* '}'`
     fn parse_group(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
         let (state, _) = input.match_char('{')?;
-        let (state, children) = state.match_repeats(LaTeXNode::parse_atomic)?;
+        let (state, children) = parse_repeats(state, LaTeXNode::parse_scripted)?;
         let (state, _) = state.skip(whitespace).match_char('}')?;
         state.finish(LaTeXNode::Row { children }.refine())
     }
-    /// `row := atomic*`
+    /// `row := scripted*`
     fn parse_row(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
-        let (state, children) = input.match_repeats(LaTeXNode::parse_atomic)?;
+        let (state, children) = parse_repeats(input, LaTeXNode::parse_scripted)?;
         state.finish(LaTeXNode::Row { children }.refine())
     }
     fn parse_command(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
@@ -100,28 +100,84 @@ impl<'i> LaTeXNode<'i> {
             .or_else(|state| state.match_char(' ').map_inner(|_| " "))
             .or_else(|state| state.match_str_if(|c| c.is_ascii_alphabetic(), "ASCII_ALPHA"))
             .end_choice()?;
+        // `\tag*`
+        let (state, cmd) = match state.match_char('*') {
+            ParseResult::Pending(state, _) if STARRED_COMMANDS.contains(&cmd) => (state, &input.rest_text[1..cmd.len() + 2]),
+            _ => (state, cmd),
+        };
         if cmd.eq("begin") {
             Err(StopBecause::ShouldNotBe { message: "\\begin", position: state.start_offset })?;
         }
         if cmd.eq("end") {
             Err(StopBecause::ShouldNotBe { message: "\\end", position: state.start_offset })?;
         }
-        let (state, args) = state.match_repeats(|state| state.skip(whitespace).match_fn(LaTeXNode::parse_group))?;
-        state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, children: args }))
+        match cmd {
+            "right" => StopBecause::custom_error("Missing `\\left` for `\\right`", input.start_offset)?,
+            "middle" => StopBecause::custom_error("Missing `\\left` for `\\middle`", input.start_offset)?,
+            "limits" | "nolimits" => {
+                StopBecause::custom_error("Limit controls must follow a math operator", input.start_offset)?
+            }
+            _ => {}
+        }
+        if RAW_ARGUMENT_COMMANDS.contains(&cmd) {
+            let (state, text) = state.skip(whitespace).match_fn(parse_raw_group)?;
+            let children = vec![LaTeXNode::MathText { text }];
+            return state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, optional: None, children }));
+        }
+        if TEXT_COMMANDS.contains(&cmd) {
+            let (state, children) = match text::parse_text_argument(state.skip(whitespace)) {
+                ParseResult::Stop(e) if !is_fatal(&e) => StopBecause::custom_error("Missing argument", state.start_offset)?,
+                result => result?,
+            };
+            return state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, optional: None, children }));
+        }
+        // `\operatorname{arg max}{x}`, the name is kept as written
+        if matches!(cmd, "operatorname" | "operatorname*") {
+            let (state, text) = state.skip(whitespace).match_fn(parse_raw_group)?;
+            let (state, args) = parse_repeats(state, |state| state.skip(whitespace).match_fn(LaTeXNode::parse_group))?;
+            let mut children = vec![LaTeXNode::MathText { text }];
+            children.extend(args);
+            return state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, optional: None, children }));
+        }
+        // `\DeclareMathOperator{\argmax}{arg\,max}`
+        if matches!(cmd, "DeclareMathOperator" | "DeclareMathOperator*") {
+            let (state, name) = state.skip(whitespace).match_fn(parse_raw_group)?;
+            let (state, text) = state.skip(whitespace).match_fn(parse_raw_group)?;
+            let children = vec![LaTeXNode::MathText { text: name }, LaTeXNode::MathText { text }];
+            return state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, optional: None, children }));
+        }
+        if let Some((_, arity)) = TOKEN_ARGUMENT_COMMANDS.iter().find(|(name, _)| *name == cmd) {
+            let (mut state, optional) = match state.skip(whitespace).match_char('[') {
+                ParseResult::Pending(..) => {
+                    parse_optional_argument(state.skip(whitespace)).map_inner(|node| Some(Box::new(node)))?
+                }
+                ParseResult::Stop(_) => (state, None),
+            };
+            let mut children = Vec::with_capacity(*arity);
+            for _ in 0..*arity {
+                let (next, argument) = parse_argument(state, "Missing argument")?;
+                state = next;
+                children.push(argument);
+            }
+            return state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, optional, children }));
+        }
+        let (state, args) = parse_repeats(state, |state| state.skip(whitespace).match_fn(LaTeXNode::parse_group))?;
+        state.finish(LaTeXNode::Command(LaTeXCommand { name: cmd, optional: None, children: args }))
     }
     fn parse_atomic(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
-        let (state, node) = input
-            .skip(whitespace)
-            .begin_choice()
-            .or_else(Self::parse_block)
-            .or_else(Self::parse_group)
-            .or_else(Self::parse_command)
-            .or_else(Self::parse_letter)
-            .or_else(Self::parse_operator)
-            .or_else(Self::parse_number)
-            .or_else(Self::parse_special)
-            .end_choice()?;
-        state.finish(node)
+        parse_choice(
+            input.skip(whitespace),
+            &[
+                Self::parse_block,
+                Self::parse_fenced,
+                Self::parse_group,
+                Self::parse_command,
+                Self::parse_letter,
+                Self::parse_operator,
+                Self::parse_number,
+                Self::parse_special,
+            ],
+        )
     }
     // pub fn parse_maybe_digit(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
     //     let (state, node) = input.begin_choice().or_else(Self::parse_number).or_else(Self::parse_letter).end_choice()?;
@@ -143,7 +199,14 @@ impl<'i> LaTeXNode<'i> {
         let (state, dec) = input
             .begin_choice()
             .or_else(|state| state.match_str("+", false))
+            .or_else(|state| state.match_str("=", false))
             .or_else(|state| state.match_str("-", false).map_inner(|_| "−"))
+            .or_else(|state| state.match_str("<", false))
+            .or_else(|state| state.match_str(">", false))
+            .or_else(|state| state.match_str(",", false))
+            .or_else(|state| state.match_str(";", false))
+            .or_else(|state| state.match_str("(", false))
+            .or_else(|state| state.match_str(")", false))
             .end_choice()?;
         state.finish(LaTeXNode::Operation { operator: dec })
     }
@@ -156,3 +219,112 @@ impl<'i> LaTeXNode<'i> {
         state.finish(item)
     }
 }
+
+/// Errors such as `x^a^b` are [`StopBecause::Custom`], they are reported instead of ending a repeat or trying the next choice.
+fn is_fatal(error: &StopBecause) -> bool {
+    matches!(error, StopBecause::Custom { .. })
+}
+
+/// [`ParseState::match_repeats`] which keeps fatal errors.
+fn parse_repeats<'i, T, F>(input: ParseState<'i>, mut parse: F) -> ParseResult<'i, Vec<T>>
+where
+    F: FnMut(ParseState<'i>) -> ParseResult<'i, T>,
+{
+    let mut items = vec![];
+    let mut state = input;
+    loop {
+        match parse(state) {
+            ParseResult::Pending(next, item) => {
+                state = next;
+                items.push(item);
+            }
+            ParseResult::Stop(e) if is_fatal(&e) => return ParseResult::Stop(e),
+            ParseResult::Stop(_) => return state.finish(items),
+        }
+    }
+}
+
+/// [`ParseState::begin_choice`] which keeps fatal errors.
+fn parse_choice<'i, T>(input: ParseState<'i>, choices: &[fn(ParseState<'i>) -> ParseResult<'i, T>]) -> ParseResult<'i, T> {
+    let mut error = StopBecause::Uninitialized;
+    for parse in choices {
+        match parse(input) {
+            ParseResult::Stop(e) if !is_fatal(&e) => error = e,
+            result => return result,
+        }
+    }
+    ParseResult::Stop(error)
+}
+
+/// Commands with a starred form, such as `\tag*{..}` without parentheses.
+const STARRED_COMMANDS: &[&str] = &["tag", "operatorname", "DeclareMathOperator"];
+
+/// Commands whose argument is kept as written, such as `\label{eq:energy}`.
+const RAW_ARGUMENT_COMMANDS: &[&str] = &["label", "ref", "eqref", "tag", "tag*"];
+
+/// Commands whose argument is text, with math between dollars.
+const TEXT_COMMANDS: &[&str] = &["text", "textrm", "textnormal", "textbf", "textit", "textsf", "texttt", "mbox"];
+
+/// Commands with a fixed number of arguments, each one a group or a single token as in `\sqrt x`.
+#[rustfmt::skip]
+const TOKEN_ARGUMENT_COMMANDS: &[(&str, usize)] = &[
+    ("sqrt", 1), ("frac", 2), ("dfrac", 2), ("binom", 2),
+    ("mathbb", 1), ("mathcal", 1), ("mathscr", 1), ("mathfrak", 1), ("mathbf", 1), ("boldsymbol", 1), ("bm", 1),
+    ("mathrm", 1), ("mathit", 1), ("mathsf", 1), ("mathtt", 1),
+    ("hat", 1), ("widehat", 1), ("check", 1), ("widecheck", 1), ("tilde", 1), ("widetilde", 1), ("acute", 1), ("grave", 1),
+    ("dot", 1), ("ddot", 1), ("dddot", 1), ("breve", 1), ("bar", 1), ("vec", 1), ("mathring", 1),
+    ("overline", 1), ("underline", 1), ("overbrace", 1), ("underbrace", 1),
+    ("overrightarrow", 1), ("overleftarrow", 1), ("overleftrightarrow", 1), ("underrightarrow", 1), ("underleftarrow", 1),
+    ("overset", 2), ("underset", 2), ("stackrel", 2),
+];
+
+/// `argument := digit | letter | atomic`, so `x^12` is `{x^1}2` as in TeX.
+fn parse_argument<'i>(input: ParseState<'i>, missing: &'static str) -> ParseResult<'i, LaTeXNode<'i>> {
+    let state = input.skip(whitespace);
+    match state.rest_text.chars().next() {
+        Some(c) if c.is_ascii_digit() => {
+            let (state, number) = state.advance_view(1)?;
+            state.finish(LaTeXNode::Number { number })
+        }
+        Some(c) if c.is_ascii_alphabetic() => {
+            let (state, identifier) = state.advance_view(1)?;
+            state.finish(LaTeXNode::Letter { identifier })
+        }
+        _ => match LaTeXNode::parse_atomic(state) {
+            ParseResult::Stop(e) if !is_fatal(&e) => StopBecause::custom_error(missing, state.start_offset)?,
+            result => result,
+        },
+    }
+}
+
+/// `optional := '[' scripted* ']'`, such as the index of `\sqrt[3]{x}`.
+fn parse_optional_argument(input: ParseState<'_>) -> ParseResult<'_, LaTeXNode<'_>> {
+    let (state, _) = input.match_char('[')?;
+    let (state, children) = parse_repeats(state, |state| match state.skip(whitespace).rest_text.starts_with(']') {
+        true => StopBecause::missing_character(']', state.start_offset)?,
+        false => LaTeXNode::parse_scripted(state),
+    })?;
+    let (state, _) = match state.skip(whitespace).match_char(']') {
+        ParseResult::Stop(_) => StopBecause::custom_error("Missing `]`", input.start_offset)?,
+        result => result?,
+    };
+    state.finish(LaTeXNode::Row { children }.refine())
+}
+
+/// `raw := '{' [^{}]* ('{' raw '}')* '}'`
+fn parse_raw_group(input: ParseState<'_>) -> ParseResult<'_, &str> {
+    let (state, _) = input.match_char('{')?;
+    let mut depth = 0usize;
+    for (offset, c) in state.rest_text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                let (state, text) = state.advance_view(offset)?;
+                return state.advance(1).finish(text);
+            }
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    StopBecause::missing_character('}', input.start_offset + input.rest_text.len())?
+}
@@ -1,18 +1,97 @@
 use super::*;
+use crate::block::LaTeXScript;
+
+/// `'`, `''`, `'''` and `''''` as single characters.
+const PRIMES: [&str; 4] = ["′", "″", "‴", "⁗"];
 
 impl<'i> LaTeXNode<'i> {
+    /// Whether the node has a superscript on the right side, primes included.
     pub fn is_super_script(&self) -> bool {
-        matches!(self, LaTeXNode::Superscript { .. })
+        matches!(self, LaTeXNode::Script(script) if script.sup.is_some())
+    }
+    /// Whether the node has a subscript on the right side.
+    pub fn is_sub_script(&self) -> bool {
+        matches!(self, LaTeXNode::Script(script) if script.sub.is_some())
+    }
+    /// `scripted := atomic? (('_' | '^') argument | "'"+ | limits)*`
+    ///
+    /// The scripts of an empty group are the prescripts of the next node, as in `{}_n C_k`.
+    pub(super) fn parse_scripted(input: ParseState<'i>) -> ParseResult<'i, LaTeXNode<'i>> {
+        let (mut state, base) = match Self::parse_atomic(input) {
+            ParseResult::Pending(state, base) => (state, Some(base)),
+            ParseResult::Stop(e) if is_fatal(&e) => return ParseResult::Stop(e),
+            // `^2` has an empty base
+            ParseResult::Stop(e) => match input.skip(whitespace).rest_text.starts_with(['^', '_', '\'']) {
+                true => (input, None),
+                false => return ParseResult::Stop(e),
+            },
+        };
+        let empty_group = matches!(&base, Some(LaTeXNode::Row { children }) if children.is_empty());
+        let mut script = LaTeXScript::new(base.unwrap_or(LaTeXNode::Row { children: vec![] }));
+        let mut primes = 0;
+        loop {
+            let next = state.skip(whitespace);
+            match next.rest_text.chars().next() {
+                Some('^') => {
+                    if script.sup.is_some() {
+                        StopBecause::custom_error("Double superscript", next.start_offset)?
+                    }
+                    let (next, sup) = parse_argument(next.advance(1), "Missing superscript")?;
+                    script.sup = Some(Box::new(sup));
+                    state = next;
+                }
+                Some('_') => {
+                    if script.sub.is_some() {
+                        StopBecause::custom_error("Double subscript", next.start_offset)?
+                    }
+                    let (next, sub) = parse_argument(next.advance(1), "Missing subscript")?;
+                    script.sub = Some(Box::new(sub));
+                    state = next;
+                }
+                Some('\\') if matches!(*script.base, LaTeXNode::Command(_)) => match parse_limits(next) {
+                    Some((next, limits)) => {
+                        script.limits = Some(limits);
+                        state = next;
+                    }
+                    None => break,
+                },
+                // `f^2'` is a double superscript, `f'^2` is not
+                Some('\'') if script.sup.is_some() => StopBecause::custom_error("Double superscript", next.start_offset)?,
+                Some('\'') => {
+                    primes += 1;
+                    state = next.advance(1);
+                }
+                _ => break,
+            }
+        }
+        if primes > 0 {
+            let mut children: Vec<_> =
+                std::iter::repeat_n(PRIMES[3], (primes - 1) / 4).map(|p| LaTeXNode::Operation { operator: p }).collect();
+            children.push(LaTeXNode::Operation { operator: PRIMES[(primes - 1) % 4] });
+            children.extend(script.sup.take().map(|sup| *sup));
+            script.sup = Some(Box::new(LaTeXNode::Row { children }.refine()));
+        }
+        if !script.has_scripts() {
+            return state.finish(*script.base);
+        }
+        if empty_group && primes == 0 {
+            if let ParseResult::Pending(next, node) = Self::parse_scripted(state) {
+                return next.finish(LaTeXNode::Script(script.into_prescripts(node)));
+            }
+        }
+        state.finish(LaTeXNode::Script(script))
     }
+}
 
-    pub(super) fn parse_super_script(input: ParseState<'i>) -> ParseResult<LaTeXNode<'i>> {
-        let (state, lhs) = input.match_fn(Self::parse_atomic)?;
-        if lhs.is_super_script() {
-            // StopBecause::ShouldNotBe("^", state.start_offset)
-            todo!()
+/// `limits := '\limits' | '\nolimits'`
+fn parse_limits(input: ParseState<'_>) -> Option<(ParseState<'_>, bool)> {
+    for (name, limits) in [("\\limits", true), ("\\nolimits", false)] {
+        match input.rest_text.strip_prefix(name) {
+            Some(rest) if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                return Some((input.advance(name.len()), limits))
+            }
+            _ => {}
         }
-        let (state, _) = state.skip(whitespace).match_str("^", false)?;
-        let (state, rhs) = state.skip(whitespace).match_fn(Self::parse_atomic)?;
-        state.finish(LaTeXNode::Superscript { lhs: Box::new(lhs), rhs: Box::new(rhs) })
     }
+    None
 }
@@ -0,0 +1,59 @@
+use super::*;
+
+/// Characters escaped in text mode, such as `\$`.
+const TEXT_ESCAPES: &str = "$%&#_{} ";
+
+/// `text := '{' (run | escape | '{' text '}' | '$' row '$' | command)* '}'`
+///
+/// The runs are kept as written, whitespace included, the groups are flattened.
+pub(super) fn parse_text_argument<'i>(input: ParseState<'i>) -> ParseResult<'i, Vec<LaTeXNode<'i>>> {
+    let (state, _) = input.match_char('{')?;
+    let mut children = vec![];
+    let (state, _) = parse_text_content(state, &mut children)?;
+    let (state, _) = match state.match_char('}') {
+        ParseResult::Stop(_) => StopBecause::custom_error("Missing `}`", input.start_offset)?,
+        result => result?,
+    };
+    state.finish(children)
+}
+
+fn parse_text_content<'i>(input: ParseState<'i>, children: &mut Vec<LaTeXNode<'i>>) -> ParseResult<'i, ()> {
+    let mut state = input;
+    loop {
+        let mut chars = state.rest_text.chars();
+        state = match (chars.next(), chars.next()) {
+            (None, _) | (Some('}'), _) => return state.finish(()),
+            (Some('{'), _) => {
+                let (next, _) = parse_text_content(state.advance(1), children)?;
+                match next.match_char('}') {
+                    ParseResult::Pending(next, _) => next,
+                    ParseResult::Stop(_) => StopBecause::custom_error("Missing `}`", state.start_offset)?,
+                }
+            }
+            (Some('$'), _) => {
+                let (next, math) = LaTeXNode::parse_row(state.advance(1))?;
+                children.push(math);
+                match next.skip(whitespace).match_char('$') {
+                    ParseResult::Pending(next, _) => next,
+                    ParseResult::Stop(_) => StopBecause::custom_error("Missing `$`", state.start_offset)?,
+                }
+            }
+            (Some('\\'), Some(c)) if TEXT_ESCAPES.contains(c) => {
+                let (next, text) = state.advance(1).advance_view(c.len_utf8())?;
+                children.push(LaTeXNode::MathText { text });
+                next
+            }
+            (Some('\\'), _) => {
+                let (next, command) = LaTeXNode::parse_command(state)?;
+                children.push(command);
+                next
+            }
+            _ => {
+                let length = state.rest_text.find(['{', '}', '$', '\\']).unwrap_or(state.rest_text.len());
+                let (next, text) = state.advance_view(length)?;
+                children.push(LaTeXNode::MathText { text });
+                next
+            }
+        };
+    }
+}
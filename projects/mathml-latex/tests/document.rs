@@ -1,4 +1,4 @@
-use mathml_core::replace;
+use mathml_latex::{find_formulas, replace, rewrite_html, HtmlRewriter, LaTeXEngine};
 
 #[test]
 fn main() {
@@ -6,15 +6,57 @@ fn main() {
 Let us consider a rigid sphere (i.e., one having a spherical figure when tested in the stationary system) of radius $R$ 
 which is at rest relative to the system ($K$), and whose centre coincides with the origin of $K$ then the equation of the 
 surface of this sphere, which is moving with a velocity $v$ relative to $K$, is
-$$\xi^2 + \eta^2 + \zeta^2 = R^2$$
+$$\xi + \eta + \zeta = R$$
 
-At time $t = 0$ the equation is expressed by means of $(x, y, z, t)$ as
-$$\frac{ x^2 }{ \left( \sqrt{ 1 - \frac{ v^2 }{ c^2 } } \right)^2 } + y^2 + z^2 = R^2 .$$
-    
-A rigid body which has the figure of a sphere when measured in the moving system, has therefore in the moving 
-condition — when considered from the stationary system, the figure of a rotational ellipsoid with semi-axes
-$$R {\sqrt{1-{\frac {v^{2}}{c^{2}}}}}, \ R, \ R .$$
+At time \(t = 0\) the equation costs $5 and $10 in `$code$`, and \$ stays.
 "#;
-    let mathml = replace(text).unwrap();
-    println!("{}", mathml);
+    let mathml = replace(text, &LaTeXEngine::builtin()).unwrap();
+    assert!(mathml.contains(r#"<math display="block"><mrow><mi mathvariant="normal">ξ</mi><mo>+</mo>"#));
+    assert!(mathml.contains(r#"<math display="inline"><mrow><mi>t</mi><mo>=</mo><mn>0</mn></mrow></math>"#));
+    assert!(mathml.contains("costs $5 and $10 in `$code$`, and \\$ stays."));
+}
+
+#[test]
+fn delimiters() {
+    let formulas = find_formulas(r"$a$ \[b\] $$ c $$ \(d\) $ e$ costs $5 and $10 <code>$g$</code> `$i$` $h$");
+    let sources: Vec<_> = formulas.iter().map(|formula| (formula.source, formula.display)).collect();
+    assert_eq!(sources, vec![("a", false), ("b", true), (" c ", true), ("d", false), ("h", false)]);
+    assert_eq!(formulas[1].range, 4..9);
+}
+
+#[test]
+fn errors() {
+    let errors = replace("ok $a + b$, broken $a + {$", &LaTeXEngine::builtin()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].range, 19..26);
+    assert_eq!(errors[0].offset, 23);
+    let context = LaTeXEngine::builtin();
+    context.define_macro("R", r"\mathbb{R}");
+    let errors = replace(r"see $\R + \R + {$", &context).unwrap_err();
+    assert_eq!(errors[0].offset, 14);
+}
+
+#[test]
+fn html() {
+    let page = r#"<!DOCTYPE html><script>var s = "\(x\)";</script><!-- \(y\) -->
+<p data-tex="\(z\)">Since \(a &#43; b\) &lt; $$c$$, <pre><b>\(p\)</b></pre> and $d$</p>"#;
+    let context = LaTeXEngine::builtin();
+    let whole = rewrite_html(page, &context);
+    assert!(whole.starts_with(r#"<!DOCTYPE html><script>var s = "\(x\)";</script><!-- \(y\) -->"#));
+    assert!(whole.contains(r#"<p data-tex="\(z\)">Since <math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math> &lt; <math display="block"><mi>c</mi></math>, <pre><b>\(p\)</b></pre> and $d$</p>"#));
+    // any split of the page gives the same html
+    for split in (1..page.len()).filter(|i| page.is_char_boundary(*i)) {
+        let mut rewriter = HtmlRewriter::new(&context);
+        let mut chunks = rewriter.write(&page[..split]);
+        chunks.push_str(&rewriter.write(&page[split..]));
+        chunks.push_str(&rewriter.finish());
+        assert_eq!(chunks, whole, "split at {}", split);
+    } // decoded references are parsed as operators and escaped again in the MathML
+    assert_eq!(
+        rewrite_html(r"<p>\(a &lt; b\) and \(a &gt; b\)</p>", &context),
+        concat!(
+            r#"<p><math display="inline"><mrow><mi>a</mi><mo>&lt;</mo><mi>b</mi></mrow></math> and "#,
+            r#"<math display="inline"><mrow><mi>a</mi><mo>&gt;</mo><mi>b</mi></mrow></math></p>"#
+        )
+    );
 }
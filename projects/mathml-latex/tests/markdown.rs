@@ -0,0 +1,21 @@
+#![cfg(feature = "pulldown-cmark")]
+
+use mathml_latex::{LaTeXEngine, MarkdownMath, MathErrorStyle};
+use pulldown_cmark::{html::push_html, Options, Parser};
+
+fn render(markdown: &str, style: MathErrorStyle) -> String {
+    let context = LaTeXEngine::builtin();
+    let mut html = String::new();
+    push_html(&mut html, MarkdownMath::new(Parser::new_ext(markdown, Options::ENABLE_MATH), &context).with_error_style(style));
+    html
+}
+
+#[test]
+fn markdown_math() {
+    let html = render("$$\n\\frac{a}{b}\n$$\n\n`$x$` and $a^$", MathErrorStyle::Error);
+    assert!(html.contains(r#"<math display="block"><mfrac><mi>a</mi><mi>b</mi></mfrac></math>"#));
+    assert!(html.contains("<code>$x$</code>"));
+    assert!(html.contains(r#"<math display="inline"><merror><mtext>a^</mtext></merror></math>"#));
+    let html = render("and $a^$", MathErrorStyle::Source);
+    assert_eq!(html, "<p>and $a^$</p>\n");
+}
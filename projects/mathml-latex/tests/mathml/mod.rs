@@ -1,4 +1,4 @@
-use mathml_latex::{parse_latex, LaTeXEngine};
+use mathml_latex::{parse_latex, replace, LaTeXEngine};
 use pex::StopBecause;
 
 #[test]
@@ -15,9 +15,191 @@ pub fn test_term() {
     assert_mathml(r"{1} + {1}", r"<mrow><mn>1</mn><mo>+</mo><mn>1</mn></mrow>").unwrap();
     assert_mathml(r"a + b", r"<mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow>").unwrap();
     assert_mathml(r"a ^ b", r"<msup><mi>a</mi><mi>b</mi></msup>").unwrap();
+    assert_mathml(r"a<b", r"<mrow><mi>a</mi><mo>&lt;</mo><mi>b</mi></mrow>").unwrap();
+    assert_mathml(r"a \neq b \le c", r"<mrow><mi>a</mi><mo>≠</mo><mi>b</mi><mo>≤</mo><mi>c</mi></mrow>").unwrap();
+    assert_mathml(r"x_1, x_2", r"<mrow><msub><mi>x</mi><mn>1</mn></msub><mo>,</mo><msub><mi>x</mi><mn>2</mn></msub></mrow>")
+        .unwrap();
+    assert_mathml(
+        r"\max(1,2)",
+        r#"<mrow><mo movablelimits="true">max</mo><mo>(</mo><mn>1</mn><mo>,</mo><mn>2</mn><mo>)</mo></mrow>"#,
+    )
+    .unwrap();
     // assert_mathml(r"{a ^ b} ^ c", r"<msup><mi>a</mi><mi>b</mi></msup>").unwrap();
 }
 
+#[test]
+pub fn test_script() {
+    assert_mathml(r"x_i^2", r"<msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup>").unwrap();
+    assert_mathml(r"x^2_i", r"<msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup>").unwrap();
+    assert_mathml(r"f''^{2}", r"<msup><mi>f</mi><mrow><mo>″</mo><mn>2</mn></mrow></msup>").unwrap();
+    assert_mathml(r"{}_n C_k", r"<mmultiscripts><mi>C</mi><mi>k</mi><mrow/><mprescripts/><mi>n</mi><mrow/></mmultiscripts>")
+        .unwrap();
+    assert_eq!(parse_latex(r"x^a^b").unwrap_err(), StopBecause::Custom { message: "Double superscript", position: 3 });
+    assert_eq!(parse_latex(r"{x_a'_b}").unwrap_err(), StopBecause::Custom { message: "Double subscript", position: 5 });
+}
+
+#[test]
+pub fn test_fenced() {
+    assert_mathml(
+        r"\left\{ x \middle| x = 1 \right.",
+        r#"<mrow><mo form="prefix" stretchy="true" symmetric="true">{</mo><mi>x</mi><mo form="infix" stretchy="true" symmetric="true">|</mo><mrow><mi>x</mi><mo>=</mo><mn>1</mn></mrow></mrow>"#,
+    )
+    .unwrap();
+    assert_mathml(
+        r"\left\langle a \right\rangle",
+        r#"<mrow><mo form="prefix" stretchy="true" symmetric="true">⟨</mo><mi>a</mi><mo form="postfix" stretchy="true" symmetric="true">⟩</mo></mrow>"#,
+    )
+    .unwrap();
+    assert_eq!(
+        parse_latex(r"a + \left( b").unwrap_err(),
+        StopBecause::Custom { message: "Missing `\\right` for `\\left`", position: 4 }
+    );
+    assert_eq!(
+        parse_latex(r"a \right)").unwrap_err(),
+        StopBecause::Custom { message: "Missing `\\left` for `\\right`", position: 2 }
+    );
+    assert_eq!(
+        parse_latex(r"\left( a \right").unwrap_err(),
+        StopBecause::Custom { message: "Missing delimiter", position: 15 }
+    );
+    assert_eq!(
+        parse_latex(r"\left\alpha x \right)").unwrap_err(),
+        StopBecause::Custom { message: "Unknown delimiter", position: 5 }
+    );
+}
+
+#[test]
+pub fn test_sqrt() {
+    assert_mathml(r"\sqrt{x}", r"<msqrt><mi>x</mi></msqrt>").unwrap();
+    assert_mathml(r"\sqrt[3]{x}", r"<mroot><mi>x</mi><mn>3</mn></mroot>").unwrap();
+    assert_mathml(r"\sqrt x2", r"<mrow><msqrt><mi>x</mi></msqrt><mn>2</mn></mrow>").unwrap();
+    assert_mathml(
+        r"\sqrt[n]{1 + \sqrt{x}}",
+        r"<mroot><mrow><mn>1</mn><mo>+</mo><msqrt><mi>x</mi></msqrt></mrow><mi>n</mi></mroot>",
+    )
+    .unwrap();
+    assert_eq!(parse_latex(r"\sqrt[3 x").unwrap_err(), StopBecause::Custom { message: "Missing `]`", position: 5 });
+}
+
+#[test]
+pub fn test_font() {
+    assert_mathml(r"\mathbb{R}", r#"<mi mathvariant="double-struck">R</mi>"#).unwrap();
+    assert_mathml(r"\mathbf{x+1}", r#"<mrow><mi mathvariant="bold">x</mi><mo>+</mo><mn mathvariant="bold">1</mn></mrow>"#)
+        .unwrap();
+    assert_mathml(r"\mathsf{a \mathit{b}}", r#"<mrow><mi mathvariant="sans-serif">a</mi><mi>b</mi></mrow>"#).unwrap();
+    assert_mathml(r"\boldsymbol\nabla", r#"<mi mathvariant="bold-italic">∇</mi>"#).unwrap();
+    let context = LaTeXEngine::builtin().with_unicode_fonts(true);
+    let render = |source| format!("{}", parse_latex(source).unwrap().as_mathml(&context));
+    assert_eq!(render(r"\mathbb{R} \mathbf{x_1}"), "<mrow><mi>ℝ</mi><msub><mi>𝐱</mi><mn>𝟏</mn></msub></mrow>");
+    assert_eq!(render(r"\mathfrak{su} \mathrm{d}"), r#"<mrow><mi>𝔰𝔲</mi><mi mathvariant="normal">d</mi></mrow>"#);
+}
+
+#[test]
+pub fn test_text() {
+    assert_mathml(r"\text{ if  and only if }", r"<mtext> if  and only if </mtext>").unwrap();
+    assert_mathml(r"\text{if $x>0$}", r"<mrow><mtext>if </mtext><mrow><mi>x</mi><mo>&gt;</mo><mn>0</mn></mrow></mrow>")
+        .unwrap();
+    assert_mathml(r"\textbf{a \$ b}", r#"<mtext mathvariant="bold">a $ b</mtext>"#).unwrap();
+    assert_mathml(
+        r"\operatorname{sgn}{x}",
+        r#"<mrow><mi mathvariant="normal">sgn</mi><mspace width="0.167rem"/><mi>x</mi></mrow>"#,
+    )
+    .unwrap();
+    assert_eq!(parse_latex(r"\text{a $x}").unwrap_err(), StopBecause::Custom { message: "Missing `$`", position: 8 });
+    // only commands such as `\tag` have a starred form
+    assert_eq!(parse_latex(r"\alpha*").unwrap_err(), StopBecause::ExpectEof { position: 6 });
+}
+
+#[test]
+pub fn test_accent() {
+    assert_mathml(r"\hat{H}", r#"<mover accent="true"><mi>H</mi><mo stretchy="false">^</mo></mover>"#).unwrap();
+    assert_mathml(r"\dot x", r#"<mover accent="true"><mi>x</mi><mo stretchy="false">˙</mo></mover>"#).unwrap();
+    assert_mathml(
+        r"\overline{x+y}",
+        r#"<mover accent="true"><mrow><mi>x</mi><mo>+</mo><mi>y</mi></mrow><mo stretchy="true">‾</mo></mover>"#,
+    )
+    .unwrap();
+    assert_mathml(
+        r"\underbrace{a}_{n}",
+        r#"<munderover accent="true"><mo stretchy="true">⏟</mo><mi>n</mi><mi>a</mi></munderover>"#,
+    )
+    .unwrap();
+    assert_mathml(
+        r"\overbrace{a+b}^{n}",
+        r#"<munderover accentunder="true"><mo stretchy="true">⏞</mo><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>n</mi></munderover>"#,
+    )
+    .unwrap();
+    assert_mathml(r"\overset{f}{\to}", r"<mover><mo>→</mo><mi>f</mi></mover>").unwrap();
+}
+
+#[test]
+pub fn test_large_operator() {
+    assert_mathml(
+        r"\sum_{n=1}^N",
+        r#"<munderover><mo largeop="true" movablelimits="true">∑</mo><mrow><mi>n</mi><mo>=</mo><mn>1</mn></mrow><mi>N</mi></munderover>"#,
+    )
+    .unwrap();
+    assert_mathml(r"\int_0^x", r#"<msubsup><mo largeop="true">∫</mo><mn>0</mn><mi>x</mi></msubsup>"#).unwrap();
+    assert_mathml(r"\int\limits_0^x", r#"<munderover><mo largeop="true">∫</mo><mn>0</mn><mi>x</mi></munderover>"#).unwrap();
+    assert_mathml(r"\bigcup\nolimits_i", r#"<msub><mo largeop="true" movablelimits="true">⋃</mo><mi>i</mi></msub>"#).unwrap();
+    assert_mathml(r"\sum\limits_i", r#"<munder><mo largeop="true">∑</mo><mi>i</mi></munder>"#).unwrap();
+    assert_eq!(
+        parse_latex(r"x \limits").unwrap_err(),
+        StopBecause::Custom { message: "Limit controls must follow a math operator", position: 2 }
+    );
+}
+
+#[test]
+pub fn test_limit_function() {
+    assert_mathml(
+        r"\lim_{x \to 0} x",
+        r#"<mrow><munder><mo movablelimits="true">lim</mo><mrow><mi>x</mi><mo>→</mo><mn>0</mn></mrow></munder><mi>x</mi></mrow>"#,
+    )
+    .unwrap();
+    assert_mathml(r"\operatorname*{arg\,max}_x", r#"<munder><mo movablelimits="true">arg max</mo><mi>x</mi></munder>"#)
+        .unwrap();
+    assert_mathml(r"\max\nolimits_x", r#"<msub><mo movablelimits="true">max</mo><mi>x</mi></msub>"#).unwrap();
+    assert_mathml(
+        r"\operatorname*{argmax}{x}",
+        r#"<mrow><mo movablelimits="true">argmax</mo><mspace width="0.167rem"/><mi>x</mi></mrow>"#,
+    )
+    .unwrap();
+    assert_mathml(r"\lim\limits_x", r#"<munder><mo>lim</mo><mi>x</mi></munder>"#).unwrap();
+    let context = LaTeXEngine::builtin();
+    let render = |source| format!("{}", context.render(source).unwrap());
+    // declarations take effect when the formula is expanded, not when it is rendered
+    let declaration = parse_latex(r"\DeclareMathOperator{\tr}{tr}").unwrap();
+    assert_eq!(format!("{}", declaration.as_mathml(&context)), "");
+    assert!(context.get_math_operator("tr").is_none());
+    assert_eq!(render(r"\DeclareMathOperator*{\argmin}{arg\,min} \DeclareMathOperator{\sgn}{sgn}"), "<mrow></mrow>");
+    assert_eq!(render(r"\argmin_x"), r#"<munder><mo movablelimits="true">arg min</mo><mi>x</mi></munder>"#);
+    assert_eq!(render(r"\argmin\limits_x"), r#"<munder><mo>arg min</mo><mi>x</mi></munder>"#);
+    assert_eq!(render(r"\sgn"), r#"<mi mathvariant="normal">sgn</mi>"#);
+}
+
+#[test]
+pub fn test_macros() {
+    let context = LaTeXEngine::builtin();
+    let expand = |source| context.expand_macros(source).unwrap().into_owned();
+    assert_eq!(expand(r"\newcommand{\e}[1][x]{e^{#1}} \e + \e[y]"), r" e^{x} + e^{y}");
+    assert_eq!(expand(r"\def\pair(#1,#2){\langle #1, #2 \rangle}\pair({a,b},c)"), r"\langle a,b, c \rangle");
+    assert_eq!(expand(r"\let\phi=\varphi \phi"), r" \varphi");
+    assert_eq!(
+        expand(r"\newenvironment{mat}{\begin{pmatrix}}{\end{pmatrix}}\begin{mat} a \end{mat}"),
+        r"\begin{pmatrix} a \end{pmatrix}"
+    );
+    context.define_macro("ab", "#1 + #2");
+    assert_eq!(expand(r"\ab{x}y \\ \e"), r"x + y \\ e^{x}");
+    assert_eq!(
+        context.expand_macros(r"\def\a{\a}\a").unwrap_err(),
+        StopBecause::Custom { message: "Too many macro expansions", position: 10 }
+    );
+    // errors after an expansion are reported in the source, not in the expanded text
+    assert_eq!(context.render(r"\e + \e + {").unwrap_err(), StopBecause::ExpectEof { position: 9 });
+    let html = replace(r"$\newcommand{\R}{\mathbb{R}}$ $\R$", &context).unwrap();
+    assert!(html.ends_with(r#"<mi mathvariant="double-struck">R</mi></math>"#));
+}
+
 #[test]
 pub fn test_frac() {
     assert_mathml(r"\frac{a}{b}", r"<mfrac><mi>a</mi><mi>b</mi></mfrac>").unwrap();
@@ -60,6 +242,69 @@ pub fn test_matrix() {
     assert_refine(include_str!("piece_cases.tex"), include_str!("piece_cases.xml")).unwrap();
 }
 
+#[test]
+pub fn test_numbering() {
+    let context = LaTeXEngine::builtin().with_section_numbering(true);
+    let document = [
+        r"\eqref{eq:last}",
+        r"\begin{align} a &= b \label{eq:first} \\ c &= d \tag{*} \\ e &= f \notag \end{align}",
+        r"\begin{equation} x = 1 \label{eq:last} \end{equation}",
+    ];
+    let render = |source| format!("{}", parse_latex(source).unwrap().as_mathml(&context));
+    context.next_section();
+    assert_eq!(render(document[0]), "<mtext>(??)</mtext>");
+    assert!(render(document[1]).contains("<mlabeledtr><mtd><mtext>(1.1)</mtext></mtd><mtd><mi>a</mi></mtd>"));
+    assert!(render(document[2]).contains("<mtext>(1.2)</mtext>"));
+    // the second pass resolves forward references
+    context.reset_counters();
+    context.next_section();
+    assert_eq!(render(document[0]), "<mtext>(1.2)</mtext>");
+    assert!(render(document[1]).contains("<mlabeledtr><mtd><mtext>(*)</mtext></mtd><mtd><mi>c</mi></mtd>"));
+    assert_eq!(context.get_label("eq:first").as_deref(), Some("1.1"));
+}
+
+#[test]
+pub fn test_alignment() {
+    let context = LaTeXEngine::builtin();
+    let render = |source| format!("{}", parse_latex(source).unwrap().as_mathml(&context));
+    assert_eq!(
+        render(r"\begin{align*} a &= b & c &= d \\ e &= f \end{align*}"),
+        r#"<mtable columnalign="right left right left" columnspacing="0em 2em 0em" displaystyle="true" side="right"><mtr><mtd><mi>a</mi></mtd><mtd><mo>=</mo><mi>b</mi></mtd><mtd><mi>c</mi></mtd><mtd><mo>=</mo><mi>d</mi></mtd></mtr><mtr><mtd><mi>e</mi></mtd><mtd><mo>=</mo><mi>f</mi></mtd></mtr></mtable>"#
+    );
+    assert!(render(r"\begin{alignat}{2} a &= b & c &= d \end{alignat}")
+        .starts_with(r#"<mtable columnalign="right left right left" columnspacing="0em" displaystyle="true" side="right"><mlabeledtr><mtd><mtext>(1)</mtext></mtd><mtd><mi>a</mi></mtd>"#));
+    assert!(render(r"\begin{equation} \begin{split} a &= b \\ &= c \end{split} \end{equation}")
+        .contains(r#"<mtable columnalign="right left" columnspacing="0em" displaystyle="true"><mtr>"#));
+    assert!(render(r"\begin{gather*} a \\ b \end{gather*}")
+        .starts_with(r#"<mtable columnalign="center" displaystyle="true" side="right"><mtr>"#));
+    let multline = render(r"\begin{multline} a + b \\ + c \\ + d \end{multline}");
+    assert!(multline.contains(r#"<mtr columnalign="left"><mtd><mi>a</mi>"#));
+    assert!(multline.contains(r#"<mlabeledtr columnalign="right"><mtd><mtext>(3)</mtext></mtd><mtd><mo>+</mo><mi>d</mi>"#));
+    assert!(parse_latex(r"\begin{alignat} a &= b \end{alignat}").is_err());
+    assert_mathml(
+        r"\begin{aligned} a &= b \\ \end{aligned}",
+        r#"<mtable columnalign="right left" columnspacing="0em" displaystyle="true"><mtr><mtd><mi>a</mi></mtd><mtd><mo>=</mo><mi>b</mi></mtd></mtr></mtable>"#,
+    )
+    .unwrap();
+    assert_mathml(
+        r"\begin{gathered} a \\ b \end{gathered}",
+        r#"<mtable columnalign="center" displaystyle="true"><mtr><mtd><mi>a</mi></mtd></mtr><mtr><mtd><mi>b</mi></mtd></mtr></mtable>"#,
+    )
+    .unwrap();
+    assert!(!render(r"\begin{align*} a &= b \\ \end{align*}").contains("<mtr><mtd></mtd></mtr>"));
+    assert_eq!(
+        parse_latex(r"x \begin{foo} a \end{foo}").unwrap_err(),
+        StopBecause::Custom { message: "Unknown environment", position: 2 }
+    );
+}
+
+#[test]
+pub fn test_unknown_command() {
+    assert_mathml(r"\foo x", r"<mrow><merror><mtext>\foo</mtext></merror><mi>x</mi></mrow>").unwrap();
+    assert_mathml(r"\frac12", r"<mfrac><mn>1</mn><mn>2</mn></mfrac>").unwrap();
+    assert_eq!(parse_latex(r"\frac a").unwrap_err(), StopBecause::Custom { message: "Missing argument", position: 7 });
+}
+
 pub fn assert_mathml(source: &str, target: &str) -> Result<(), StopBecause> {
     assert_eq!(render_mathml(source)?, target);
     Ok(())
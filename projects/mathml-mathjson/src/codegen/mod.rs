@@ -0,0 +1,416 @@
+use crate::definitions::{find_by_value, BIG_OPERATORS, CONSTANTS, FUNCTIONS, GREEK, RELATIONS};
+use mathml_core::{
+    FontVariant, LineThickness, MathElement, MathFenced, MathFunction, MathIdentifier, MathML, MathMultiScript, MathRow,
+    MathTable,
+};
+use serde_json::{json, Value};
+
+/// Convert a MathML tree into a MathJSON expression.
+pub trait AsMathJSON {
+    /// Convert the node into a MathJSON expression, nodes without counterpart are kept as `["MathML", value]`.
+    fn as_mathjson(&self) -> Value;
+}
+
+impl AsMathJSON for MathML {
+    fn as_mathjson(&self) -> Value {
+        let value = match self {
+            MathML::Root(root) => Some(write_row(root.get_children())),
+            MathML::Row(row) => Some(write_row(row.get_items())),
+            MathML::Number(number) => Some(write_number(number.get_number())),
+            MathML::Identifier(identifier) => write_identifier(identifier),
+            MathML::Text(text) if !text.is_string() => Some(Value::from(format!("'{}'", text.get_text()))),
+            MathML::Frac(fraction) if fraction.get_thickness() == LineThickness::Medium => {
+                Some(json!(["Divide", fraction.get_numerator().as_mathjson(), fraction.get_denominator().as_mathjson()]))
+            }
+            MathML::Sqrt(sqrt) => match sqrt.get_surd() {
+                Some(surd) => Some(json!(["Root", sqrt.get_base().as_mathjson(), surd.as_mathjson()])),
+                None => Some(json!(["Sqrt", sqrt.get_base().as_mathjson()])),
+            },
+            MathML::MultiScripts(scripts) => write_scripts(scripts),
+            MathML::Function(function) => write_function(function),
+            MathML::Fenced(fenced) => Some(write_fenced(fenced)),
+            MathML::Table(table) => write_matrix(table, ".."),
+            MathML::Undefined(error) => Some(json!(["Error", format!("'{}'", error.get_message())])),
+            MathML::Nothing => Some(Value::from("Nothing")),
+            _ => None,
+        };
+        value.unwrap_or_else(|| fallback(self))
+    }
+}
+
+/// Build `[head, ...args]`.
+fn expression<I>(head: &str, args: I) -> Value
+where
+    I: IntoIterator<Item = Value>,
+{
+    std::iter::once(Value::from(head)).chain(args).collect()
+}
+
+/// Keep the node as its `serde` representation.
+fn fallback(node: &MathML) -> Value {
+    json!(["MathML", serde_json::to_value(node).unwrap_or_default()])
+}
+
+fn write_number(n: &str) -> Value {
+    if let Ok(i) = n.parse::<i64>() {
+        if i.to_string() == n {
+            return Value::from(i);
+        }
+    }
+    if let Ok(f) = n.parse::<f64>() {
+        if f.is_finite() && f.to_string() == n {
+            return Value::from(f);
+        }
+    }
+    json!({ "num": n })
+}
+
+fn write_identifier(identifier: &MathIdentifier) -> Option<Value> {
+    let text = identifier.get_identifier();
+    let variant = identifier.get_variant();
+    if variant == FontVariant::Normal {
+        if let Some(constant) = find_by_value(CONSTANTS, text) {
+            return Some(Value::from(constant));
+        }
+    }
+    if let Some(name) = find_by_value(GREEK, text) {
+        return Some(Value::from(name));
+    }
+    let single = text.chars().count() == 1;
+    let plain = text.chars().all(|c| c.is_alphanumeric());
+    match (single, variant) {
+        (true, FontVariant::Italic) | (false, FontVariant::Normal) if plain => Some(Value::from(text)),
+        _ => None,
+    }
+}
+
+fn write_scripts(scripts: &MathMultiScript) -> Option<Value> {
+    if !scripts.get_pre_sub_scripts().is_empty() || !scripts.get_pre_super_scripts().is_empty() {
+        return None;
+    }
+    let base = scripts.get_base().as_mathjson();
+    match (scripts.get_sub_scripts(), scripts.get_super_scripts()) {
+        ([sub], []) => Some(json!(["Subscript", base, sub.as_mathjson()])),
+        ([], [sup]) => Some(json!(["Power", base, sup.as_mathjson()])),
+        ([sub], [sup]) => Some(json!(["Power", ["Subscript", base, sub.as_mathjson()], sup.as_mathjson()])),
+        _ => None,
+    }
+}
+
+fn write_function(function: &MathFunction) -> Option<Value> {
+    let head = find_by_value(FUNCTIONS, function.get_name())?;
+    match function.get_arguments() {
+        [MathML::Fenced(fenced)] if fenced.get_open() == '(' && fenced.get_close() == ')' => {
+            Some(expression(head, fenced.get_items().iter().map(|item| item.as_mathjson())))
+        }
+        arguments => Some(expression(head, vec![write_row(arguments)])),
+    }
+}
+
+fn write_fenced(fenced: &MathFenced) -> Value {
+    let items: Vec<Value> = fenced.get_items().iter().map(|item| item.as_mathjson()).collect();
+    let separator = fenced.get_separators().chars().next().unwrap_or(',');
+    match (fenced.get_open(), fenced.get_close(), items.as_slice()) {
+        ('|', '|', [item]) => json!(["Abs", item]),
+        ('[', ']', _) if separator == ',' => expression("List", items),
+        ('{', '}', _) if separator == ',' => expression("Set", items),
+        (open, close, _) => {
+            let body = match items.len() {
+                1 => items[0].clone(),
+                _ => expression("Sequence", items),
+            };
+            match (open, separator, close) {
+                ('(', ',', ')') => json!(["Delimiter", body]),
+                _ => json!(["Delimiter", body, format!("'{}{}{}'", open, separator, close)]),
+            }
+        }
+    }
+}
+
+fn write_matrix(table: &MathTable, delimiter: &str) -> Option<Value> {
+    if !table.get_attributes().is_empty() {
+        return None;
+    }
+    let rows = table.get_rows().into_iter().map(|cells| expression("List", cells.iter().map(|cell| cell.as_mathjson())));
+    Some(json!(["Matrix", expression("List", rows), format!("'{}'", delimiter)]))
+}
+
+/// A node of a row, with balanced parentheses grouped.
+enum Token<'a> {
+    Node(&'a MathML),
+    Group(&'a [MathML]),
+}
+
+fn operator_text(node: &MathML) -> Option<&str> {
+    match node {
+        MathML::Operator(operator) => Some(operator.get_operator()),
+        _ => None,
+    }
+}
+
+fn token_operator<'a>(token: &Token<'a>) -> Option<&'a str> {
+    match token {
+        Token::Node(node) => operator_text(node),
+        Token::Group(_) => None,
+    }
+}
+
+fn write_row(items: &[MathML]) -> Value {
+    match items {
+        [] => Value::from("Nothing"),
+        [single] => single.as_mathjson(),
+        [open, MathML::Table(table), close] => {
+            let delimiter = match (operator_text(open), operator_text(close)) {
+                (Some("("), Some(")")) => Some("()"),
+                (Some("["), Some("]")) => Some("[]"),
+                (Some("{"), Some("}")) => Some("{}"),
+                (Some("|"), Some("|")) => Some("||"),
+                (Some("‖"), Some("‖")) => Some("‖‖"),
+                _ => None,
+            };
+            delimiter.and_then(|delimiter| write_matrix(table, delimiter)).unwrap_or_else(|| write_sequence(items))
+        }
+        _ => write_sequence(items),
+    }
+}
+
+fn write_sequence(items: &[MathML]) -> Value {
+    tokenize(items).and_then(|tokens| write_tokens(&tokens)).unwrap_or_else(|| fallback(&MathRow::new(items.to_vec()).into()))
+}
+
+fn tokenize(items: &[MathML]) -> Option<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < items.len() {
+        match operator_text(&items[i]) {
+            Some("(") => {
+                let mut depth = 0;
+                let mut close = None;
+                for (j, item) in items.iter().enumerate().skip(i) {
+                    match operator_text(item) {
+                        Some("(") => depth += 1,
+                        Some(")") => depth -= 1,
+                        _ => continue,
+                    }
+                    if depth == 0 {
+                        close = Some(j);
+                        break;
+                    }
+                }
+                let close = close?;
+                tokens.push(Token::Group(&items[i + 1..close]));
+                i = close + 1;
+            }
+            Some(")") => return None,
+            _ => {
+                tokens.push(Token::Node(&items[i]));
+                i += 1;
+            }
+        }
+    }
+    Some(tokens)
+}
+
+/// Split tokens at top level operators, each part comes with the operator before it.
+fn split<'t, 'a, F>(tokens: &'t [Token<'a>], is_separator: F) -> Vec<(Option<&'a str>, &'t [Token<'a>])>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut separator = None;
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(operator) = token_operator(token).filter(|s| is_separator(s)) {
+            parts.push((separator, &tokens[start..i]));
+            separator = Some(operator);
+            start = i + 1;
+        }
+    }
+    parts.push((separator, &tokens[start..]));
+    parts
+}
+
+fn write_tokens(tokens: &[Token]) -> Option<Value> {
+    let parts = split(tokens, |s| s == ",");
+    if parts.len() > 1 {
+        let items = parts.iter().map(|(_, part)| write_relation(part)).collect::<Option<Vec<_>>>()?;
+        return Some(expression("Sequence", items));
+    }
+    write_relation(tokens)
+}
+
+fn write_relation(tokens: &[Token]) -> Option<Value> {
+    let parts = split(tokens, |s| find_by_value(RELATIONS, s).is_some());
+    if parts.len() == 1 {
+        return write_additive(tokens);
+    }
+    let relation = parts[1].0?;
+    if parts.iter().skip(1).any(|(separator, _)| *separator != Some(relation)) {
+        return None;
+    }
+    let head = find_by_value(RELATIONS, relation)?;
+    let items = parts.iter().map(|(_, part)| write_additive(part)).collect::<Option<Vec<_>>>()?;
+    Some(expression(head, items))
+}
+
+fn write_additive(tokens: &[Token]) -> Option<Value> {
+    let (negative, tokens) = match tokens.split_first() {
+        Some((first, rest)) => match token_operator(first) {
+            Some("−" | "-") => (true, rest),
+            Some("+") => (false, rest),
+            _ => (false, tokens),
+        },
+        None => return None,
+    };
+    let mut terms = vec![];
+    for (separator, part) in split(tokens, |s| matches!(s, "+" | "−" | "-" | "±")) {
+        let negative = match separator {
+            None => negative,
+            Some("+") => false,
+            Some("−" | "-") => true,
+            _ => return None,
+        };
+        terms.push((negative, write_multiplicative(part)?));
+    }
+    match terms.as_slice() {
+        [(false, term)] => Some(term.clone()),
+        [(true, term)] => Some(negate(term.clone())),
+        [(false, lhs), (true, rhs)] => Some(json!(["Subtract", lhs, rhs])),
+        _ => {
+            let terms = terms.into_iter().map(|(negative, term)| {
+                if negative {
+                    negate(term)
+                }
+                else {
+                    term
+                }
+            });
+            Some(expression("Add", terms))
+        }
+    }
+}
+
+fn negate(value: Value) -> Value {
+    match value.as_i64() {
+        Some(i) => Value::from(-i),
+        None => match value.as_f64() {
+            Some(f) => Value::from(-f),
+            None => json!(["Negate", value]),
+        },
+    }
+}
+
+fn write_multiplicative(tokens: &[Token]) -> Option<Value> {
+    let mut result: Option<Value> = None;
+    for (separator, part) in split(tokens, |s| matches!(s, "×" | "⋅" | "·" | "*" | "∗" | "/" | "÷")) {
+        let factor = write_juxtaposition(part)?;
+        result = Some(match (result, separator) {
+            (None, _) => factor,
+            (Some(lhs), Some("/" | "÷")) => json!(["Divide", lhs, factor]),
+            (Some(lhs), _) => multiply(lhs, factor),
+        });
+    }
+    result
+}
+
+fn multiply(lhs: Value, rhs: Value) -> Value {
+    let mut factors = match lhs {
+        Value::Array(items) if items.first().and_then(Value::as_str) == Some("Multiply") => items,
+        lhs => vec![Value::from("Multiply"), lhs],
+    };
+    match rhs {
+        Value::Array(items) if items.first().and_then(Value::as_str) == Some("Multiply") => {
+            factors.extend(items.into_iter().skip(1))
+        }
+        rhs => factors.push(rhs),
+    }
+    Value::from(factors)
+}
+
+fn write_juxtaposition(tokens: &[Token]) -> Option<Value> {
+    let mut factors: Vec<Value> = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        let node = match token {
+            Token::Group(items) => {
+                factors.push(json!(["Delimiter", write_row(items)]));
+                continue;
+            }
+            Token::Node(node) => node,
+        };
+        if let Some((head, mut limits)) = big_operator(node) {
+            let mut rest = &tokens[i + 1..];
+            if let Some((integrand, index)) = differential(rest).filter(|_| head == "Integrate") {
+                rest = integrand;
+                match limits.as_mut() {
+                    Some(limits) => limits[1] = index,
+                    None => limits = Some(json!(["Triple", index, "Nothing", "Nothing"])),
+                }
+            }
+            let body = write_juxtaposition(rest)?;
+            factors.push(expression(head, std::iter::once(body).chain(limits)));
+            break;
+        }
+        match operator_text(node) {
+            Some("!") => {
+                let base = factors.pop()?;
+                factors.push(json!(["Factorial", base]))
+            }
+            Some(_) => return None,
+            None if matches!(node, MathML::Space(_)) => return None,
+            None => factors.push(node.as_mathjson()),
+        }
+    }
+    factors.into_iter().reduce(multiply)
+}
+
+/// A large `∑`, `∏` or `∫`, with the limits as `["Triple", index, lower, upper]`.
+///
+/// The limits may also be scripts, the lower limit of `∫` has no index, it comes from the differential.
+fn big_operator(node: &MathML) -> Option<(&'static str, Option<Value>)> {
+    let (base, under, over) = match node {
+        MathML::UnderOver(under_over) => (under_over.get_base(), under_over.get_under(), under_over.get_over()),
+        MathML::MultiScripts(scripts)
+            if scripts.get_pre_sub_scripts().is_empty() && scripts.get_pre_super_scripts().is_empty() =>
+        {
+            match (scripts.get_sub_scripts(), scripts.get_super_scripts()) {
+                ([sub], [sup]) => (scripts.get_base(), Some(sub), Some(sup)),
+                ([sub], []) => (scripts.get_base(), Some(sub), None),
+                ([], [sup]) => (scripts.get_base(), None, Some(sup)),
+                _ => return None,
+            }
+        }
+        _ => (node, None, None),
+    };
+    let head = match base {
+        MathML::Operator(operator) if operator.is_large_operator() => find_by_value(BIG_OPERATORS, operator.get_operator())?,
+        _ => return None,
+    };
+    if under.is_none() && over.is_none() {
+        return Some((head, None));
+    }
+    let (index, lower) = match under {
+        Some(lower) if head == "Integrate" => (Value::from("Nothing"), lower.as_mathjson()),
+        Some(MathML::Row(row)) => match row.get_items() {
+            [index, equal, lower @ ..] if operator_text(equal) == Some("=") => (index.as_mathjson(), write_row(lower)),
+            _ => return None,
+        },
+        Some(_) => return None,
+        None => (Value::from("Nothing"), Value::from("Nothing")),
+    };
+    let upper = over.map(|over| over.as_mathjson()).unwrap_or_else(|| Value::from("Nothing"));
+    Some((head, Some(json!(["Triple", index, lower, upper]))))
+}
+
+/// The integrand and the index of a trailing `d x`, the thin space before `d` is optional.
+fn differential<'a, 't>(tokens: &'a [Token<'t>]) -> Option<(&'a [Token<'t>], Value)> {
+    let (integrand, index) = match tokens {
+        [integrand @ .., Token::Node(MathML::Identifier(d)), Token::Node(index)] if d.get_identifier() == "d" => {
+            (integrand, index)
+        }
+        _ => return None,
+    };
+    match integrand {
+        [integrand @ .., Token::Node(MathML::Space(_))] => Some((integrand, index.as_mathjson())),
+        _ => Some((integrand, index.as_mathjson())),
+    }
+}
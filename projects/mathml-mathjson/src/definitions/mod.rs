@@ -0,0 +1,48 @@
+/// Symbols rendered with a dedicated character, the first matching entry wins on export.
+pub(crate) const CONSTANTS: &[(&str, &str)] = &[
+    ("Pi", "π"),
+    ("ExponentialE", "e"),
+    ("ImaginaryUnit", "i"),
+    ("PositiveInfinity", "∞"),
+    ("ComplexInfinity", "∞̃"),
+    ("EmptySet", "∅"),
+    ("Nothing", ""),
+];
+
+#[rustfmt::skip]
+pub(crate) const GREEK: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"), ("epsilon", "ε"), ("zeta", "ζ"),
+    ("eta", "η"), ("theta", "θ"), ("iota", "ι"), ("kappa", "κ"), ("lambda", "λ"), ("mu", "μ"),
+    ("nu", "ν"), ("xi", "ξ"), ("omicron", "ο"), ("rho", "ρ"), ("sigma", "σ"), ("tau", "τ"),
+    ("upsilon", "υ"), ("phi", "φ"), ("chi", "χ"), ("psi", "ψ"), ("omega", "ω"),
+    ("Gamma", "Γ"), ("Delta", "Δ"), ("Theta", "Θ"), ("Lambda", "Λ"), ("Xi", "Ξ"), ("Sigma", "Σ"),
+    ("Upsilon", "Υ"), ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"),
+];
+
+#[rustfmt::skip]
+pub(crate) const FUNCTIONS: &[(&str, &str)] = &[
+    ("Sin", "sin"), ("Cos", "cos"), ("Tan", "tan"), ("Cot", "cot"), ("Sec", "sec"), ("Csc", "csc"),
+    ("Arcsin", "arcsin"), ("Arccos", "arccos"), ("Arctan", "arctan"),
+    ("Sinh", "sinh"), ("Cosh", "cosh"), ("Tanh", "tanh"), ("Coth", "coth"),
+    ("Exp", "exp"), ("Ln", "ln"), ("Log", "log"), ("Lg", "lg"), ("Lb", "lb"),
+    ("Erf", "erf"), ("Erfc", "erfc"), ("Arg", "arg"), ("Det", "det"),
+    ("Max", "max"), ("Min", "min"), ("Gcd", "gcd"), ("Lcm", "lcm"),
+];
+
+#[rustfmt::skip]
+pub(crate) const RELATIONS: &[(&str, &str)] = &[
+    ("Equal", "="), ("NotEqual", "≠"), ("Less", "<"), ("Greater", ">"), ("LessEqual", "≤"), ("GreaterEqual", "≥"),
+    ("Approx", "≈"), ("Element", "∈"), ("NotElement", "∉"), ("Subset", "⊂"), ("SubsetEqual", "⊆"),
+    ("Assign", ":="),
+];
+
+/// Large operators, whose last argument is `["Triple", index, lower, upper]`.
+pub(crate) const BIG_OPERATORS: &[(&str, &str)] = &[("Sum", "∑"), ("Product", "∏"), ("Integrate", "∫")];
+
+pub(crate) fn find_by_key(table: &'static [(&str, &str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+pub(crate) fn find_by_value(table: &'static [(&str, &str)], value: &str) -> Option<&'static str> {
+    table.iter().find(|(_, v)| *v == value).map(|(k, _)| *k)
+}
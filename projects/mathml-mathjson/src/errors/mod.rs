@@ -0,0 +1,41 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// Errors raised while reading a MathJSON expression.
+#[derive(Debug)]
+pub enum MathJSONError {
+    /// The input is not valid JSON, or the `MathML` fallback does not deserialize.
+    JsonError(serde_json::Error),
+    /// A function expression has the wrong number of arguments.
+    InvalidArity {
+        /// The head of the function expression.
+        head: String,
+        /// The number of arguments found.
+        found: usize,
+    },
+    /// The value is not a valid MathJSON expression.
+    InvalidExpression {
+        /// The offending value.
+        value: String,
+    },
+}
+
+impl Error for MathJSONError {}
+
+impl Display for MathJSONError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathJSONError::JsonError(e) => write!(f, "Invalid MathJSON: {}", e),
+            MathJSONError::InvalidArity { head, found } => write!(f, "Wrong number of arguments for `{}`: {}", head, found),
+            MathJSONError::InvalidExpression { value } => write!(f, "Invalid MathJSON expression: {}", value),
+        }
+    }
+}
+
+impl From<serde_json::Error> for MathJSONError {
+    fn from(value: serde_json::Error) -> Self {
+        MathJSONError::JsonError(value)
+    }
+}
@@ -0,0 +1,16 @@
+#![deny(missing_debug_implementations, missing_copy_implementations)]
+#![warn(missing_docs, rustdoc::missing_crate_level_docs)]
+#![doc = include_str!("../readme.md")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/oovm/shape-rs/dev/projects/images/Trapezohedron.svg")]
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/oovm/shape-rs/dev/projects/images/Trapezohedron.svg")]
+
+mod codegen;
+mod definitions;
+mod errors;
+mod parser;
+
+pub use crate::{
+    codegen::AsMathJSON,
+    errors::MathJSONError,
+    parser::{parse_mathjson, parse_mathjson_value},
+};
@@ -0,0 +1,374 @@
+use crate::{
+    definitions::{find_by_key, BIG_OPERATORS, CONSTANTS, FUNCTIONS, GREEK, RELATIONS},
+    MathJSONError,
+};
+use mathml_core::{
+    helpers::{bmatrix, matrix, pmatrix, vmatrix, Bmatrix, Vmatrix},
+    MathFenced, MathFunction, MathIdentifier, MathML, MathMultiScript, MathNumber, MathOperator, MathRow, MathSpace, MathSqrt,
+    MathUnderOver,
+};
+use serde_json::Value;
+
+/// Parse a MathJSON string into a MathML tree.
+pub fn parse_mathjson(s: &str) -> Result<MathML, MathJSONError> {
+    let value: Value = serde_json::from_str(s)?;
+    parse_mathjson_value(&value)
+}
+
+/// Parse an already decoded MathJSON expression into a MathML tree.
+pub fn parse_mathjson_value(value: &Value) -> Result<MathML, MathJSONError> {
+    match value {
+        Value::Number(n) => Ok(parse_number(&n.to_string())),
+        Value::String(s) => Ok(parse_symbol(s)),
+        Value::Array(items) => parse_function(items),
+        Value::Object(object) => match (object.get("num"), object.get("sym"), object.get("str"), object.get("fn")) {
+            (Some(Value::String(n)), ..) => Ok(parse_number(n)),
+            (Some(Value::Number(n)), ..) => Ok(parse_number(&n.to_string())),
+            (_, Some(Value::String(s)), ..) => Ok(parse_symbol(s)),
+            (_, _, Some(Value::String(s)), _) => Ok(MathML::text(s)),
+            (_, _, _, Some(Value::Array(items))) => parse_function(items),
+            _ => Err(invalid(value)),
+        },
+        _ => Err(invalid(value)),
+    }
+}
+
+fn invalid(value: &Value) -> MathJSONError {
+    MathJSONError::InvalidExpression { value: value.to_string() }
+}
+
+fn arity<'a, const N: usize>(head: &str, args: &'a [Value]) -> Result<&'a [Value; N], MathJSONError> {
+    args.try_into().map_err(|_| MathJSONError::InvalidArity { head: head.to_string(), found: args.len() })
+}
+
+/// Strip the quotes of a MathJSON string literal such as `'text'`.
+fn unquote(s: &str) -> Option<&str> {
+    s.strip_prefix('\'')?.strip_suffix('\'')
+}
+
+fn row(items: Vec<MathML>) -> MathML {
+    MathRow::new(items).into()
+}
+
+fn parse_number(n: &str) -> MathML {
+    match n.strip_prefix('-') {
+        Some(positive) => row(vec![MathML::operation("−"), MathNumber::new(positive).into()]),
+        None => MathNumber::new(n).into(),
+    }
+}
+
+fn parse_symbol(s: &str) -> MathML {
+    if let Some(text) = unquote(s) {
+        return MathML::text(text);
+    }
+    if s == "Nothing" {
+        return row(vec![]);
+    }
+    if let Some(symbol) = find_by_key(CONSTANTS, s) {
+        return MathIdentifier::normal(symbol).into();
+    }
+    if let Some(letter) = find_by_key(GREEK, s) {
+        return MathML::identifier(letter);
+    }
+    match s.chars().count() {
+        1 => MathML::identifier(s),
+        _ => MathIdentifier::normal(s).into(),
+    }
+}
+
+/// Binding strength of an expression, used to decide where parentheses are needed.
+fn precedence(value: &Value) -> u8 {
+    match value {
+        Value::Number(n) if n.as_f64().is_some_and(|f| f < 0.0) => 3,
+        Value::Array(items) => match items.first().and_then(Value::as_str) {
+            Some(head) if find_by_key(RELATIONS, head).is_some() => 1,
+            Some("Add" | "Subtract") => 2,
+            Some("Negate" | "Multiply" | "Sum" | "Product" | "Integrate") => 3,
+            Some("Power" | "Square" | "Factorial") => 4,
+            _ => 9,
+        },
+        Value::Object(object) => object.get("fn").map(precedence).unwrap_or(9),
+        _ => 9,
+    }
+}
+
+/// Parse an argument, adding parentheses if it binds looser than `min`.
+fn operand(value: &Value, min: u8) -> Result<MathML, MathJSONError> {
+    let node = parse_mathjson_value(value)?;
+    match precedence(value) < min {
+        true => Ok(MathFenced::parentheses(vec![node]).into()),
+        false => Ok(node),
+    }
+}
+
+/// The positive part of `["Negate", x]` or a negative number.
+fn negated(value: &Value) -> Option<Value> {
+    match value {
+        Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) if i < 0 => Some(Value::from(-i)),
+            (None, Some(f)) if f < 0.0 => Some(Value::from(-f)),
+            _ => None,
+        },
+        Value::Array(items) => match items.as_slice() {
+            [Value::String(head), inner] if head == "Negate" => Some(inner.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_number(value: &Value) -> bool {
+    match value {
+        Value::Number(_) => true,
+        Value::Object(object) => object.contains_key("num"),
+        _ => false,
+    }
+}
+
+fn head_of(value: &Value) -> Option<&str> {
+    value.as_array()?.first()?.as_str()
+}
+
+fn parse_function(items: &[Value]) -> Result<MathML, MathJSONError> {
+    let (head, args) = match items.split_first() {
+        Some((Value::String(head), args)) => (head.as_str(), args),
+        _ => return Err(invalid(&Value::from(items))),
+    };
+    let node = match head {
+        "MathML" => {
+            let [value] = arity(head, args)?;
+            serde_json::from_value(value.clone())?
+        }
+        "Add" => {
+            let mut terms = vec![];
+            for (i, arg) in args.iter().enumerate() {
+                match (i, negated(arg)) {
+                    (0, _) => terms.push(operand(arg, 2)?),
+                    (_, Some(positive)) => {
+                        terms.push(MathML::operation("−"));
+                        terms.push(operand(&positive, 3)?)
+                    }
+                    (_, None) => {
+                        terms.push(MathML::operation("+"));
+                        terms.push(operand(arg, 2)?)
+                    }
+                }
+            }
+            row(terms)
+        }
+        "Subtract" => {
+            let [lhs, rhs] = arity(head, args)?;
+            row(vec![operand(lhs, 2)?, MathML::operation("−"), operand(rhs, 3)?])
+        }
+        "Negate" => {
+            let [base] = arity(head, args)?;
+            row(vec![MathML::operation("−"), operand(base, 3)?])
+        }
+        "Multiply" => {
+            let mut factors = vec![];
+            for (i, arg) in args.iter().enumerate() {
+                if i != 0 && is_number(arg) {
+                    factors.push(MathML::operation("×"));
+                }
+                factors.push(operand(arg, 3)?)
+            }
+            row(factors)
+        }
+        "Divide" | "Rational" => {
+            let [numerator, denominator] = arity(head, args)?;
+            MathML::fraction(parse_mathjson_value(numerator)?, parse_mathjson_value(denominator)?)
+        }
+        "Power" => {
+            let [base, power] = arity(head, args)?;
+            match base.as_array().map(Vec::as_slice) {
+                Some([Value::String(s), base, sub]) if s == "Subscript" => {
+                    let sub = parse_mathjson_value(sub)?;
+                    MathMultiScript::sub_super_script(operand(base, 5)?, sub, parse_mathjson_value(power)?).into()
+                }
+                _ => MathMultiScript::super_script(operand(base, 5)?, parse_mathjson_value(power)?).into(),
+            }
+        }
+        "Square" => {
+            let [base] = arity(head, args)?;
+            MathMultiScript::super_script(operand(base, 5)?, MathML::number(2)).into()
+        }
+        "Subscript" => {
+            let [base, sub] = arity(head, args)?;
+            MathMultiScript::sub_script(operand(base, 5)?, parse_mathjson_value(sub)?).into()
+        }
+        "Sqrt" => {
+            let [base] = arity(head, args)?;
+            MathSqrt::sqrt(parse_mathjson_value(base)?).into()
+        }
+        "Root" => {
+            let [base, power] = arity(head, args)?;
+            MathSqrt::surd(parse_mathjson_value(base)?, parse_mathjson_value(power)?).into()
+        }
+        "Factorial" => {
+            let [base] = arity(head, args)?;
+            row(vec![operand(base, 5)?, MathML::operation("!")])
+        }
+        "Abs" => {
+            let [base] = arity(head, args)?;
+            MathFenced::new(vec![parse_mathjson_value(base)?], '|', '|').into()
+        }
+        "Delimiter" => parse_delimiter(head, args)?,
+        "List" => MathFenced::brackets(parse_all(args)?).with_separators(",").into(),
+        "Tuple" => MathFenced::parentheses(parse_all(args)?).with_separators(",").into(),
+        "Set" => MathFenced::curly(parse_all(args)?).with_separators(",").into(),
+        "Sequence" => {
+            let mut items = vec![];
+            for (i, arg) in args.iter().enumerate() {
+                if i != 0 {
+                    items.push(MathML::operation(","));
+                }
+                items.push(parse_mathjson_value(arg)?)
+            }
+            row(items)
+        }
+        "Matrix" => parse_matrix(head, args)?,
+        "Error" => {
+            let message = args.first().and_then(Value::as_str).map(|s| unquote(s).unwrap_or(s)).unwrap_or("error");
+            MathML::error(message)
+        }
+        "Sum" | "Product" | "Integrate" => parse_big_operator(head, args)?,
+        _ => {
+            if let Some(symbol) = find_by_key(RELATIONS, head) {
+                let mut items = vec![];
+                for (i, arg) in args.iter().enumerate() {
+                    if i != 0 {
+                        items.push(MathML::operation(symbol));
+                    }
+                    items.push(operand(arg, 2)?)
+                }
+                return Ok(row(items));
+            }
+            let arguments = match args {
+                [arg] if precedence(arg) == 9 => parse_mathjson_value(arg)?,
+                _ => MathFenced::parentheses(parse_all(args)?).with_separators(",").into(),
+            };
+            match find_by_key(FUNCTIONS, head) {
+                Some(name) => MathFunction::new(name, vec![arguments]).into(),
+                None => {
+                    let arguments = match arguments {
+                        MathML::Fenced(_) => arguments,
+                        _ => MathFenced::parentheses(vec![arguments]).into(),
+                    };
+                    row(vec![parse_symbol(head), arguments])
+                }
+            }
+        }
+    };
+    Ok(node)
+}
+
+fn parse_all(args: &[Value]) -> Result<Vec<MathML>, MathJSONError> {
+    args.iter().map(parse_mathjson_value).collect()
+}
+
+fn parse_delimiter(head: &str, args: &[Value]) -> Result<MathML, MathJSONError> {
+    let (body, delimiter) = match args {
+        [body] => (body, "(,)"),
+        [body, Value::String(delimiter)] => (body, unquote(delimiter).unwrap_or(delimiter)),
+        _ => return Err(MathJSONError::InvalidArity { head: head.to_string(), found: args.len() }),
+    };
+    let items = match body.as_array().map(Vec::as_slice) {
+        Some([Value::String(s), items @ ..]) if s == "Sequence" => parse_all(items)?,
+        _ => vec![parse_mathjson_value(body)?],
+    };
+    let chars: Vec<char> = delimiter.chars().collect();
+    let (open, separator, close) = match chars.as_slice() {
+        [open, close] => (*open, ',', *close),
+        [open, separator, close] => (*open, *separator, *close),
+        _ => return Err(invalid(&Value::from(delimiter))),
+    };
+    Ok(MathFenced::new(items, open, close).with_separators(separator).into())
+}
+
+fn parse_matrix(head: &str, args: &[Value]) -> Result<MathML, MathJSONError> {
+    let (rows, delimiter) = match args {
+        [rows] => (rows, "()"),
+        [rows, Value::String(delimiter)] => (rows, unquote(delimiter).unwrap_or(delimiter)),
+        _ => return Err(MathJSONError::InvalidArity { head: head.to_string(), found: args.len() }),
+    };
+    let rows = match rows.as_array().map(Vec::as_slice) {
+        Some([Value::String(s), rows @ ..]) if s == "List" => rows,
+        _ => return Err(invalid(rows)),
+    };
+    let mut stream = vec![];
+    for (i, cells) in rows.iter().enumerate() {
+        if i != 0 {
+            stream.push(MathML::NewLine);
+        }
+        let cells = match cells.as_array().map(Vec::as_slice) {
+            Some([Value::String(s), cells @ ..]) if s == "List" => cells,
+            _ => return Err(invalid(cells)),
+        };
+        for (j, cell) in cells.iter().enumerate() {
+            if j != 0 {
+                stream.push(MathML::Ampersand);
+            }
+            stream.push(parse_mathjson_value(cell)?);
+        }
+    }
+    let matrix = match delimiter {
+        "()" => pmatrix(stream),
+        "[]" => bmatrix(stream),
+        "{}" => Bmatrix(stream),
+        "||" => vmatrix(stream),
+        "‖‖" => Vmatrix(stream),
+        _ => matrix(stream),
+    };
+    Ok(matrix)
+}
+
+/// `["Sum", body, ["Triple", index, lower, upper]]`, the limits are optional.
+fn parse_big_operator(head: &str, args: &[Value]) -> Result<MathML, MathJSONError> {
+    let (body, limits) = match args {
+        [body] => (body, None),
+        [body, limits] => (body, Some(limits)),
+        _ => return Err(MathJSONError::InvalidArity { head: head.to_string(), found: args.len() }),
+    };
+    let limit = |i: usize| -> Result<Option<MathML>, MathJSONError> {
+        match limits.and_then(Value::as_array).and_then(|limits| limits.get(i)) {
+            Some(Value::String(s)) if s == "Nothing" => Ok(None),
+            Some(value) => Ok(Some(parse_mathjson_value(value)?)),
+            None => Ok(None),
+        }
+    };
+    if let Some(limits) = limits {
+        if !matches!(head_of(limits), Some("Triple" | "Tuple" | "Limits")) {
+            return Err(invalid(limits));
+        }
+    }
+    let (index, lower, upper) = (limit(1)?, limit(2)?, limit(3)?);
+    let symbol = find_by_key(BIG_OPERATORS, head).unwrap_or("∫");
+    let operator: MathML = MathOperator::new(symbol).mark_large_operator().into();
+    let body = operand(body, 3)?;
+    if head == "Integrate" {
+        let operator = match (lower, upper) {
+            (Some(lower), Some(upper)) => MathMultiScript::sub_super_script(operator, lower, upper).into(),
+            (Some(lower), None) => MathMultiScript::sub_script(operator, lower).into(),
+            (None, Some(upper)) => MathMultiScript::super_script(operator, upper).into(),
+            (None, None) => operator,
+        };
+        let mut items = vec![operator, body];
+        if let Some(index) = index {
+            items.push(MathSpace::new(0.167).into());
+            items.push(MathIdentifier::normal("d").into());
+            items.push(index);
+        }
+        return Ok(row(items));
+    }
+    let lower = match (index, lower) {
+        (Some(index), Some(lower)) => Some(row(vec![index, MathML::operation("="), lower])),
+        (index, lower) => index.or(lower),
+    };
+    let operator = match (lower, upper) {
+        (Some(lower), Some(upper)) => MathUnderOver::under_over(operator, lower, upper).into(),
+        (Some(lower), None) => MathUnderOver::under(operator, lower).into(),
+        (None, Some(upper)) => MathUnderOver::over(operator, upper).into(),
+        (None, None) => operator,
+    };
+    Ok(row(vec![operator, body]))
+}
@@ -0,0 +1,79 @@
+use mathml_core::{helpers::bmatrix, MathML, MathMultiScript, MathOperator, MathRoot, MathRow, MathSpace, MathUnderOver};
+use mathml_mathjson::{parse_mathjson, AsMathJSON};
+use serde_json::json;
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+#[track_caller]
+fn assert_import(mathjson: &str, mathml: &str) {
+    assert_eq!(parse_mathjson(mathjson).unwrap().to_string(), mathml);
+}
+
+#[test]
+fn import() {
+    assert_import(
+        r#"["Multiply", 2, ["Add", "x", -1]]"#,
+        r#"<mrow><mn>2</mn><mrow><mo stretchy="true" form="prefix">(</mo><mrow><mi>x</mi><mo>−</mo><mn>1</mn></mrow><mo stretchy="true" form="postfix">)</mo></mrow></mrow>"#,
+    );
+    assert_import(
+        r#"["Equal", ["Sin", "Pi"], 0]"#,
+        r#"<mrow><mrow><mi mathvariant="normal">sin</mi><mspace width="0.167rem"/><mi mathvariant="normal">π</mi></mrow><mo>=</mo><mn>0</mn></mrow>"#,
+    );
+    assert_import(
+        r#"["Sum", ["Power", "i", 2], ["Triple", "i", 1, "n"]]"#,
+        r#"<mrow><munderover><mo largeop="true">∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msup><mi>i</mi><mn>2</mn></msup></mrow>"#,
+    );
+    assert!(parse_mathjson(r#"["Divide", 1]"#).is_err());
+    assert!(parse_mathjson("{").is_err());
+}
+
+#[test]
+fn export() {
+    let matrix = bmatrix(vec![MathML::number(1), MathML::Ampersand, MathML::identifier("x")]);
+    assert_eq!(matrix.as_mathjson(), json!(["Matrix", ["List", ["List", 1, "x"]], "'[]'"]));
+    let row: MathML = MathRoot::new(vec![
+        MathML::operation("-"),
+        MathML::identifier("a"),
+        MathML::operation("("),
+        MathML::identifier("b"),
+        MathML::operation("+"),
+        MathML::number(1),
+        MathML::operation(")"),
+        MathML::operation("="),
+        MathML::fraction(MathML::identifier("c"), MathML::number(2)),
+    ])
+    .into();
+    assert_eq!(
+        row.as_mathjson(),
+        json!(["Equal", ["Negate", ["Multiply", "a", ["Delimiter", ["Add", "b", 1]]]], ["Divide", "c", 2]])
+    );
+}
+
+#[test]
+fn round_trip() {
+    let sum = MathUnderOver::under_over(
+        MathOperator::new("∑").mark_large_operator().into(),
+        MathML::identifier("i"),
+        MathML::identifier("n"),
+    );
+    // the space has no MathJSON counterpart, so the whole row is kept as is
+    let math: MathML = MathRow::new(vec![
+        sum.into(),
+        MathSpace::new(1.0).into(),
+        MathMultiScript::sub_super_script('x'.into(), 'i'.into(), 2.into()).into(),
+    ])
+    .into();
+    let value = math.as_mathjson();
+    assert_eq!(value[0], "MathML");
+    assert_eq!(parse_mathjson(&value.to_string()).unwrap(), math);
+    let source = json!(["Add", ["Multiply", 3, "x"], ["Sqrt", ["Subscript", "a", 1]], ["Negate", ["Power", "y", 2]]]);
+    assert_eq!(parse_mathjson(&source.to_string()).unwrap().as_mathjson(), source);
+    // the limits of an integral are scripts, the index is the differential
+    let integral = json!(["Integrate", ["Power", "x", 2], ["Triple", "x", 0, 1]]);
+    assert_eq!(parse_mathjson(&integral.to_string()).unwrap().as_mathjson(), integral);
+    let sum = json!(["Sum", "i", ["Triple", "i", 1, "n"]]);
+    assert_eq!(parse_mathjson(&sum.to_string()).unwrap().as_mathjson(), sum);
+}
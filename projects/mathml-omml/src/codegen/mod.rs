@@ -0,0 +1,411 @@
+use crate::OMML_NAMESPACE;
+use mathml_core::{
+    helpers::safe_html_str, FontVariant, LineThickness, MathElement, MathFenced, MathIdentifier, MathML, MathMultiScript,
+    MathTable, MathUnderOver,
+};
+use std::fmt::Write;
+
+/// Serialize a MathML tree as Office Math Markup Language.
+pub trait AsOMML {
+    /// Convert the node into an `<m:oMath>` element, display style roots become `<m:oMathPara>`.
+    fn as_omml(&self) -> String;
+}
+
+impl AsOMML for MathML {
+    fn as_omml(&self) -> String {
+        let mut w = String::new();
+        match self {
+            MathML::Root(root) if root.is_display_style() => {
+                write!(w, "<m:oMathPara xmlns:m=\"{}\"><m:oMath>", OMML_NAMESPACE).ok();
+                write_row(&mut w, root.get_children());
+                w.push_str("</m:oMath></m:oMathPara>");
+            }
+            _ => {
+                write!(w, "<m:oMath xmlns:m=\"{}\">", OMML_NAMESPACE).ok();
+                write_node(&mut w, self);
+                w.push_str("</m:oMath>");
+            }
+        }
+        w
+    }
+}
+
+const NARY_OPERATORS: &[&str] = &["∑", "∏", "∐", "⋂", "⋃", "⨀", "⨁", "⨂", "⨄", "⨆", "⋁", "⋀", "∫", "∬", "∭", "∮", "∯", "∰"];
+const OPEN_FENCES: &[&str] = &["(", "[", "{", "|", "‖", "⟨", "⌈", "⌊", "⟦"];
+const CLOSE_FENCES: &[&str] = &[")", "]", "}", "|", "‖", "⟩", "⌉", "⌋", "⟧"];
+const GROUP_CHARACTERS: &[&str] = &["⏜", "⏝", "⏞", "⏟", "⎴", "⎵"];
+
+fn write_node(w: &mut String, node: &MathML) {
+    match node {
+        MathML::Root(v) => write_row(w, v.get_children()),
+        MathML::Row(v) => write_row(w, v.get_items()),
+        MathML::Space(v) => {
+            let width = v.get_attributes().get("width").and_then(|s| s.trim_end_matches("rem").parse::<f32>().ok());
+            let space = match width {
+                Some(s) if s < 0.5 => "\u{2009}",
+                _ => "\u{2003}",
+            };
+            write_run(w, space, "");
+        }
+        MathML::Number(v) => write_run(w, v.get_number(), ""),
+        MathML::Identifier(v) => write_identifier(w, v),
+        MathML::Text(v) => write_run(w, v.get_text(), "<m:nor/>"),
+        MathML::Operator(v) if is_nary(node) => write_nary(w, v.get_operator(), None, None, true, None),
+        MathML::Operator(v) => write_run(w, v.get_operator(), ""),
+        MathML::MultiScripts(v) => write_scripts(w, v),
+        MathML::UnderOver(v) => write_under_over(w, v),
+        MathML::Function(v) => {
+            w.push_str("<m:func><m:fName>");
+            write_run(w, v.get_name(), "<m:sty m:val=\"p\"/>");
+            w.push_str("</m:fName><m:e>");
+            write_row(w, v.get_arguments());
+            w.push_str("</m:e></m:func>");
+        }
+        MathML::Sqrt(v) => match v.get_surd() {
+            Some(degree) => {
+                w.push_str("<m:rad>");
+                write_argument(w, "m:deg", degree);
+                write_argument(w, "m:e", v.get_base());
+                w.push_str("</m:rad>");
+            }
+            None => {
+                w.push_str("<m:rad><m:radPr><m:degHide m:val=\"1\"/></m:radPr><m:deg/>");
+                write_argument(w, "m:e", v.get_base());
+                w.push_str("</m:rad>");
+            }
+        },
+        MathML::Frac(v) => {
+            w.push_str("<m:f>");
+            if let LineThickness::Length(0) = v.get_thickness() {
+                w.push_str("<m:fPr><m:type m:val=\"noBar\"/></m:fPr>");
+            }
+            write_argument(w, "m:num", v.get_numerator());
+            write_argument(w, "m:den", v.get_denominator());
+            w.push_str("</m:f>");
+        }
+        MathML::Phantom(v) => write_argument(w, "m:phant", v.get_inner()),
+        MathML::Style(v) => write_node(w, v.get_base()),
+        MathML::Fenced(v) => write_fenced(w, v),
+        MathML::Table(v) => write_table(w, v),
+        MathML::Undefined(v) => write_run(w, v.get_message(), "<m:nor/>"),
+        MathML::Ampersand | MathML::NewLine | MathML::Nothing => {}
+    }
+}
+
+fn write_row(w: &mut String, items: &[MathML]) {
+    match items {
+        [MathML::Operator(open), inner @ .., MathML::Operator(close)]
+            if is_fenced(open.get_operator(), inner, close.get_operator()) =>
+        {
+            write_delimiter(w, open.get_operator(), close.get_operator(), |w| write_row(w, inner));
+            return;
+        }
+        // `cases` has no closing fence
+        [MathML::Operator(open), table @ MathML::Table(_)] if OPEN_FENCES.contains(&open.get_operator()) => {
+            write_delimiter(w, open.get_operator(), "", |w| write_node(w, table));
+            return;
+        }
+        _ => {}
+    }
+    let mut items = items.iter();
+    while let Some(item) = items.next() {
+        match item {
+            // a large operator takes the next item as its operand
+            _ if is_nary(item) => write_nary_node(w, item, items.next()),
+            _ => write_node(w, item),
+        }
+    }
+}
+
+/// Whether `open` and `close` are a pair of fences around all of `inner`, unlike the first and last fence of `(a)(b)`.
+fn is_fenced(open: &str, inner: &[MathML], close: &str) -> bool {
+    match OPEN_FENCES.iter().position(|fence| *fence == open) {
+        Some(index) if CLOSE_FENCES[index] == close => {}
+        _ => return false,
+    }
+    let mut depth = 0usize;
+    for item in inner {
+        match item {
+            MathML::Operator(o) if o.get_operator() == close => match depth.checked_sub(1) {
+                Some(outer) => depth = outer,
+                None => return false,
+            },
+            MathML::Operator(o) if o.get_operator() == open => depth += 1,
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+fn write_argument(w: &mut String, tag: &str, node: &MathML) {
+    write!(w, "<{}>", tag).ok();
+    write_node(w, node);
+    write!(w, "</{}>", tag).ok();
+}
+
+fn write_run(w: &mut String, text: &str, properties: &str) {
+    w.push_str("<m:r>");
+    if !properties.is_empty() {
+        write!(w, "<m:rPr>{}</m:rPr>", properties).ok();
+    }
+    match text.starts_with(char::is_whitespace) || text.ends_with(char::is_whitespace) {
+        true => w.push_str("<m:t xml:space=\"preserve\">"),
+        false => w.push_str("<m:t>"),
+    }
+    safe_html_str(w, text).ok();
+    w.push_str("</m:t></m:r>");
+}
+
+fn write_identifier(w: &mut String, identifier: &MathIdentifier) {
+    let (script, style) = match identifier.get_variant() {
+        FontVariant::Normal => (None, Some("p")),
+        FontVariant::Italic => (None, None),
+        FontVariant::Bold => (None, Some("b")),
+        FontVariant::BoldItalic => (None, Some("bi")),
+        FontVariant::DoubleStruck => (Some("double-struck"), Some("p")),
+        FontVariant::BoldFraktur => (Some("fraktur"), Some("b")),
+        FontVariant::Script => (Some("script"), Some("p")),
+        FontVariant::BoldScript => (Some("script"), Some("b")),
+        FontVariant::Fraktur => (Some("fraktur"), Some("p")),
+        FontVariant::SansSerif => (Some("sans-serif"), Some("p")),
+        FontVariant::BoldSansSerif => (Some("sans-serif"), Some("b")),
+        FontVariant::SansSerifItalic => (Some("sans-serif"), None),
+        FontVariant::SansSerifBoldItalic => (Some("sans-serif"), Some("bi")),
+        FontVariant::Monospace => (Some("monospace"), Some("p")),
+    };
+    let mut properties = String::new();
+    if let Some(script) = script {
+        write!(properties, "<m:scr m:val=\"{}\"/>", script).ok();
+    }
+    if let Some(style) = style {
+        write!(properties, "<m:sty m:val=\"{}\"/>", style).ok();
+    }
+    write_run(w, identifier.get_identifier(), &properties)
+}
+
+fn write_scripts(w: &mut String, scripts: &MathMultiScript) {
+    let base = scripts.get_base();
+    let lower = scripts.get_pre_sub_scripts();
+    let upper = scripts.get_pre_super_scripts();
+    if lower.is_empty() && upper.is_empty() {
+        return write_post_scripts(w, base, scripts.get_sub_scripts(), scripts.get_super_scripts());
+    }
+    w.push_str("<m:sPre>");
+    write_script_list(w, "m:sub", lower);
+    write_script_list(w, "m:sup", upper);
+    w.push_str("<m:e>");
+    write_post_scripts(w, base, scripts.get_sub_scripts(), scripts.get_super_scripts());
+    w.push_str("</m:e></m:sPre>");
+}
+
+fn write_post_scripts(w: &mut String, base: &MathML, sub: &[MathML], sup: &[MathML]) {
+    let tag = match (sub.is_empty(), sup.is_empty()) {
+        (true, true) => return write_node(w, base),
+        (false, true) => "m:sSub",
+        (true, false) => "m:sSup",
+        (false, false) => "m:sSubSup",
+    };
+    write!(w, "<{}>", tag).ok();
+    write_argument(w, "m:e", base);
+    if !sub.is_empty() {
+        write_script_list(w, "m:sub", sub);
+    }
+    if !sup.is_empty() {
+        write_script_list(w, "m:sup", sup);
+    }
+    write!(w, "</{}>", tag).ok();
+}
+
+/// OMML has a single script per position, extra scripts are laid out side by side.
+fn write_script_list(w: &mut String, tag: &str, scripts: &[MathML]) {
+    write!(w, "<{}>", tag).ok();
+    for script in scripts {
+        write_node(w, script);
+    }
+    write!(w, "</{}>", tag).ok();
+}
+
+fn write_under_over(w: &mut String, node: &MathUnderOver) {
+    let base = node.get_base();
+    let accent = node.get_attributes().get("accent").map(|s| s.as_str()) == Some("true");
+    match (node.get_under(), node.get_over()) {
+        (None, Some(MathML::Operator(o))) if GROUP_CHARACTERS.contains(&o.get_operator()) => {
+            write!(w, "<m:groupChr><m:groupChrPr><m:chr m:val=\"{}\"/><m:pos m:val=\"top\"/></m:groupChrPr>", o.get_operator())
+                .ok();
+            write_argument(w, "m:e", base);
+            w.push_str("</m:groupChr>");
+        }
+        (Some(MathML::Operator(o)), None) if GROUP_CHARACTERS.contains(&o.get_operator()) => {
+            write!(w, "<m:groupChr><m:groupChrPr><m:chr m:val=\"{}\"/></m:groupChrPr>", o.get_operator()).ok();
+            write_argument(w, "m:e", base);
+            w.push_str("</m:groupChr>");
+        }
+        (None, Some(MathML::Operator(o))) if matches!(o.get_operator(), "‾" | "¯") => {
+            w.push_str("<m:bar><m:barPr><m:pos m:val=\"top\"/></m:barPr>");
+            write_argument(w, "m:e", base);
+            w.push_str("</m:bar>");
+        }
+        (Some(MathML::Operator(o)), None) if matches!(o.get_operator(), "_" | "‾" | "¯") => {
+            w.push_str("<m:bar><m:barPr><m:pos m:val=\"bot\"/></m:barPr>");
+            write_argument(w, "m:e", base);
+            w.push_str("</m:bar>");
+        }
+        (None, Some(MathML::Operator(o))) if accent => {
+            w.push_str("<m:acc><m:accPr><m:chr m:val=\"");
+            safe_html_str(w, o.get_operator()).ok();
+            w.push_str("\"/></m:accPr>");
+            write_argument(w, "m:e", base);
+            w.push_str("</m:acc>");
+        }
+        (under, over) if is_nary(base) => write_nary(w, nary_symbol(base), under, over, true, None),
+        (Some(under), Some(over)) => {
+            w.push_str("<m:limLow><m:e><m:limUpp>");
+            write_argument(w, "m:e", base);
+            write_argument(w, "m:lim", over);
+            w.push_str("</m:limUpp></m:e>");
+            write_argument(w, "m:lim", under);
+            w.push_str("</m:limLow>");
+        }
+        (Some(under), None) => {
+            w.push_str("<m:limLow>");
+            write_argument(w, "m:e", base);
+            write_argument(w, "m:lim", under);
+            w.push_str("</m:limLow>");
+        }
+        (None, Some(over)) => {
+            w.push_str("<m:limUpp>");
+            write_argument(w, "m:e", base);
+            write_argument(w, "m:lim", over);
+            w.push_str("</m:limUpp>");
+        }
+        (None, None) => write_node(w, base),
+    }
+}
+
+/// Large operators are `<mo>`, or limits attached to a `<mo>`, whose symbol is a n-ary operator.
+fn is_nary(node: &MathML) -> bool {
+    match node {
+        MathML::Operator(o) => o.is_large_operator() || NARY_OPERATORS.contains(&o.get_operator()),
+        MathML::UnderOver(v) => is_nary(v.get_base()),
+        MathML::MultiScripts(v) => {
+            is_nary(v.get_base()) && v.get_pre_sub_scripts().is_empty() && v.get_pre_super_scripts().is_empty()
+        }
+        _ => false,
+    }
+}
+
+fn nary_symbol(node: &MathML) -> &str {
+    match node {
+        MathML::Operator(o) => o.get_operator(),
+        MathML::UnderOver(v) => nary_symbol(v.get_base()),
+        MathML::MultiScripts(v) => nary_symbol(v.get_base()),
+        _ => "",
+    }
+}
+
+fn write_nary_node(w: &mut String, node: &MathML, body: Option<&MathML>) {
+    match node {
+        MathML::UnderOver(v) => write_nary(w, nary_symbol(node), v.get_under(), v.get_over(), true, body),
+        MathML::MultiScripts(v) => {
+            write_nary(w, nary_symbol(node), v.get_sub_scripts().first(), v.get_super_scripts().first(), false, body)
+        }
+        _ => write_nary(w, nary_symbol(node), None, None, true, body),
+    }
+}
+
+fn write_nary(
+    w: &mut String,
+    symbol: &str,
+    sub: Option<&MathML>,
+    sup: Option<&MathML>,
+    under_over: bool,
+    body: Option<&MathML>,
+) {
+    w.push_str("<m:nary><m:naryPr><m:chr m:val=\"");
+    safe_html_str(w, symbol).ok();
+    w.push_str("\"/>");
+    match under_over {
+        true => w.push_str("<m:limLoc m:val=\"undOvr\"/>"),
+        false => w.push_str("<m:limLoc m:val=\"subSup\"/>"),
+    }
+    if sub.is_none() {
+        w.push_str("<m:subHide m:val=\"1\"/>");
+    }
+    if sup.is_none() {
+        w.push_str("<m:supHide m:val=\"1\"/>");
+    }
+    w.push_str("</m:naryPr>");
+    match sub {
+        Some(s) => write_argument(w, "m:sub", s),
+        None => w.push_str("<m:sub/>"),
+    }
+    match sup {
+        Some(s) => write_argument(w, "m:sup", s),
+        None => w.push_str("<m:sup/>"),
+    }
+    match body {
+        Some(s) => write_argument(w, "m:e", s),
+        None => w.push_str("<m:e/>"),
+    }
+    w.push_str("</m:nary>");
+}
+
+fn write_delimiter<F>(w: &mut String, open: &str, close: &str, body: F)
+where
+    F: FnOnce(&mut String),
+{
+    w.push_str("<m:d><m:dPr><m:begChr m:val=\"");
+    safe_html_str(w, open).ok();
+    w.push_str("\"/><m:endChr m:val=\"");
+    safe_html_str(w, close).ok();
+    w.push_str("\"/></m:dPr><m:e>");
+    body(w);
+    w.push_str("</m:e></m:d>");
+}
+
+fn write_fenced(w: &mut String, fenced: &MathFenced) {
+    w.push_str("<m:d><m:dPr><m:begChr m:val=\"");
+    safe_html_str(w, &fenced.get_open().to_string()).ok();
+    w.push_str("\"/>");
+    if let Some(separator) = fenced.get_separators().chars().next() {
+        w.push_str("<m:sepChr m:val=\"");
+        safe_html_str(w, &separator.to_string()).ok();
+        w.push_str("\"/>");
+    }
+    w.push_str("<m:endChr m:val=\"");
+    safe_html_str(w, &fenced.get_close().to_string()).ok();
+    w.push_str("\"/></m:dPr>");
+    for item in fenced.get_items() {
+        write_argument(w, "m:e", item);
+    }
+    w.push_str("</m:d>");
+}
+
+fn write_table(w: &mut String, table: &MathTable) {
+    let aligned = table.get_attributes().get("columnalign").is_some_and(|s| s.starts_with("right"));
+    if aligned {
+        // equation arrays have a single `m:e` per row, with `&` as alignment points between the cells
+        w.push_str("<m:eqArr>");
+        for row in table.get_rows() {
+            w.push_str("<m:e>");
+            for (i, cell) in row.iter().enumerate() {
+                if i != 0 {
+                    write_run(w, "&", "");
+                }
+                write_node(w, cell);
+            }
+            w.push_str("</m:e>");
+        }
+        w.push_str("</m:eqArr>");
+        return;
+    }
+    w.push_str("<m:m>");
+    for row in table.get_rows() {
+        w.push_str("<m:mr>");
+        for cell in &row {
+            write_argument(w, "m:e", cell);
+        }
+        w.push_str("</m:mr>");
+    }
+    w.push_str("</m:m>");
+}
@@ -0,0 +1,30 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// Errors raised while reading an OMML document.
+#[derive(Debug)]
+pub enum OMMLError {
+    /// The input is not well-formed XML.
+    XmlError(roxmltree::Error),
+    /// The document does not contain any `<m:oMath>` or `<m:oMathPara>` element.
+    MissingMath,
+}
+
+impl Error for OMMLError {}
+
+impl Display for OMMLError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OMMLError::XmlError(e) => write!(f, "Invalid OMML document: {}", e),
+            OMMLError::MissingMath => f.write_str("Missing element `m:oMath`"),
+        }
+    }
+}
+
+impl From<roxmltree::Error> for OMMLError {
+    fn from(value: roxmltree::Error) -> Self {
+        OMMLError::XmlError(value)
+    }
+}
@@ -0,0 +1,14 @@
+#![deny(missing_debug_implementations, missing_copy_implementations)]
+#![warn(missing_docs, rustdoc::missing_crate_level_docs)]
+#![doc = include_str!("../readme.md")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/oovm/shape-rs/dev/projects/images/Trapezohedron.svg")]
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/oovm/shape-rs/dev/projects/images/Trapezohedron.svg")]
+
+mod codegen;
+mod errors;
+mod parser;
+
+pub use crate::{codegen::AsOMML, errors::OMMLError, parser::parse_omml};
+
+/// The namespace of Office Math Markup Language, bound to the `m:` prefix in `.docx` files.
+pub const OMML_NAMESPACE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/math";
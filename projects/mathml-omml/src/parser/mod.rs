@@ -0,0 +1,292 @@
+use crate::{OMMLError, OMML_NAMESPACE};
+use mathml_core::{
+    FontVariant, LineThickness, MathElement, MathFenced, MathFraction, MathFunction, MathIdentifier, MathML, MathMultiScript,
+    MathNumber, MathOperator, MathPhantom, MathRoot, MathRow, MathSqrt, MathTable, MathUnderOver,
+};
+use roxmltree::{Document, Node};
+
+mod run;
+
+/// Parse an OMML fragment into a MathML tree.
+///
+/// The input can be a bare `<m:oMath>`/`<m:oMathPara>` element or any XML document containing one, such as
+/// `word/document.xml`; the first math element found is converted.
+pub fn parse_omml(s: &str) -> Result<MathML, OMMLError> {
+    let document = Document::parse(s)?;
+    let math = document
+        .root_element()
+        .descendants()
+        .find(|node| is_element(node, "oMathPara") || is_element(node, "oMath"))
+        .ok_or(OMMLError::MissingMath)?;
+    if is_element(&math, "oMathPara") {
+        let lines: Vec<_> = elements(math, "oMath").map(|line| make_row(parse_children(line))).collect();
+        let root = match lines.len() {
+            1 => MathRoot::new(lines),
+            _ => {
+                let stream = lines.into_iter().enumerate().flat_map(|(i, line)| match i {
+                    0 => vec![line],
+                    _ => vec![MathML::NewLine, line],
+                });
+                MathRoot::new(vec![MathTable::matrix(stream).into()])
+            }
+        };
+        return Ok(root.with_display_style(true).into());
+    }
+    Ok(MathRoot::new(parse_children(math)).into())
+}
+
+/// Elements without namespace are accepted too, so that hand-written fragments can omit `xmlns:m`.
+fn is_math(node: &Node) -> bool {
+    node.is_element() && node.tag_name().namespace().is_none_or(|ns| ns == OMML_NAMESPACE)
+}
+
+fn is_element(node: &Node, name: &str) -> bool {
+    is_math(node) && node.tag_name().name() == name
+}
+
+fn elements<'a, 'i>(node: Node<'a, 'i>, name: &'static str) -> impl Iterator<Item = Node<'a, 'i>> {
+    node.children().filter(move |child| is_element(child, name))
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, name: &'static str) -> Option<Node<'a, 'i>> {
+    elements(node, name).next()
+}
+
+/// Read the `m:val` attribute of `<m:xxxPr><m:name m:val="..."/></m:xxxPr>`.
+fn property<'a>(node: Node<'a, '_>, name: &'static str) -> Option<&'a str> {
+    let properties = node.children().find(|child| is_math(child) && child.tag_name().name().ends_with("Pr"))?;
+    let value = child(properties, name)?;
+    Some(value.attributes().find(|attribute| attribute.name() == "val").map(|attribute| attribute.value()).unwrap_or(""))
+}
+
+/// On/off properties are on when present without value.
+fn flag(node: Node, name: &'static str) -> bool {
+    matches!(property(node, name), Some("" | "1" | "on" | "true"))
+}
+
+fn make_row(mut items: Vec<MathML>) -> MathML {
+    match items.len() {
+        1 => items.remove(0),
+        _ => MathRow::new(items).into(),
+    }
+}
+
+/// Parse the content of the child `m:e`, `m:num`, `m:sub` ... as one node.
+fn argument(node: Node, name: &'static str) -> MathML {
+    match child(node, name) {
+        Some(s) => make_row(parse_children(s)),
+        None => MathRow::new(vec![]).into(),
+    }
+}
+
+fn optional_argument(node: Node, name: &'static str) -> Option<MathML> {
+    let arg = child(node, name)?;
+    let items = parse_children(arg);
+    match items.is_empty() {
+        true => None,
+        false => Some(make_row(items)),
+    }
+}
+
+fn parse_children(node: Node) -> Vec<MathML> {
+    let mut out = vec![];
+    for child in node.children().filter(is_math) {
+        parse_node(child, &mut out);
+    }
+    out
+}
+
+fn parse_node(node: Node, out: &mut Vec<MathML>) {
+    let name = node.tag_name().name();
+    if name.ends_with("Pr") {
+        return;
+    }
+    let item: MathML = match name {
+        "r" => return run::parse_run(node, out),
+        "oMath" | "box" | "borderBox" => return out.extend(parse_children(child(node, "e").unwrap_or(node))),
+        "f" => {
+            let fraction = MathFraction::new(argument(node, "num"), argument(node, "den"));
+            match property(node, "type") {
+                Some("noBar") => fraction.with_thickness(LineThickness::Length(0)).into(),
+                _ => fraction.into(),
+            }
+        }
+        "rad" => match flag(node, "degHide") {
+            true => MathSqrt::sqrt(argument(node, "e")).into(),
+            false => match optional_argument(node, "deg") {
+                Some(degree) => MathSqrt::surd(argument(node, "e"), degree).into(),
+                None => MathSqrt::sqrt(argument(node, "e")).into(),
+            },
+        },
+        "sSub" => MathMultiScript::sub_script(argument(node, "e"), argument(node, "sub")).into(),
+        "sSup" => MathMultiScript::super_script(argument(node, "e"), argument(node, "sup")).into(),
+        "sSubSup" => {
+            MathMultiScript::sub_super_script(argument(node, "e"), argument(node, "sub"), argument(node, "sup")).into()
+        }
+        "sPre" => parse_pre_script(node),
+        "nary" => return parse_nary(node, out),
+        "d" => parse_delimiter(node),
+        "m" => {
+            let rows = elements(node, "mr").map(|row| elements(row, "e").map(|cell| make_row(parse_children(cell))).collect());
+            MathTable::matrix(join_rows(rows)).into()
+        }
+        "eqArr" => {
+            let rows: Vec<Vec<MathML>> = elements(node, "e").map(parse_children).collect();
+            let aligned = rows.iter().flatten().any(|item| matches!(item, MathML::Ampersand));
+            // the `&` alignment points split each row into cells
+            let cells = rows
+                .into_iter()
+                .map(|row| row.split(|item| matches!(item, MathML::Ampersand)).map(|cell| make_row(cell.to_vec())).collect());
+            let table = MathTable::matrix(join_rows(cells));
+            match aligned {
+                true => table.with_attribute("columnalign", "right left").into(),
+                false => table.into(),
+            }
+        }
+        "func" => {
+            let name = child(node, "fName").map(parse_children).unwrap_or_default();
+            match name.as_slice() {
+                [MathML::Identifier(id)] => MathFunction::new(id.get_identifier(), vec![argument(node, "e")]).into(),
+                _ => {
+                    out.extend(name);
+                    argument(node, "e")
+                }
+            }
+        }
+        "acc" => {
+            let accent = property(node, "chr").unwrap_or("\u{0302}");
+            MathUnderOver::over(argument(node, "e"), MathOperator::new(accent).mark_stretchy().into()).with_accent_over().into()
+        }
+        "bar" => match property(node, "pos") {
+            Some("top") => MathUnderOver::over(argument(node, "e"), MathML::operation("‾")).with_accent_over().into(),
+            _ => MathUnderOver::under(argument(node, "e"), MathML::operation("_")).with_accent_under().into(),
+        },
+        "groupChr" => {
+            let top = property(node, "pos") == Some("top");
+            let symbol = property(node, "chr").unwrap_or(if top { "⏞" } else { "⏟" });
+            let symbol = MathOperator::new(symbol).mark_stretchy().into();
+            match top {
+                true => MathUnderOver::over(argument(node, "e"), symbol).into(),
+                false => MathUnderOver::under(argument(node, "e"), symbol).into(),
+            }
+        }
+        "limLow" => MathUnderOver::under(argument(node, "e"), argument(node, "lim")).into(),
+        "limUpp" => MathUnderOver::over(argument(node, "e"), argument(node, "lim")).into(),
+        "phant" => MathPhantom::new(argument(node, "e")).into(),
+        unknown => MathML::error(format!("unsupported OMML element `m:{}`", unknown)),
+    };
+    out.push(item)
+}
+
+fn join_rows<I>(rows: I) -> Vec<MathML>
+where
+    I: IntoIterator<Item = Vec<MathML>>,
+{
+    let mut stream = vec![];
+    for (i, row) in rows.into_iter().enumerate() {
+        if i != 0 {
+            stream.push(MathML::NewLine);
+        }
+        for (j, cell) in row.into_iter().enumerate() {
+            if j != 0 {
+                stream.push(MathML::Ampersand);
+            }
+            stream.push(cell);
+        }
+    }
+    stream
+}
+
+fn parse_pre_script(node: Node) -> MathML {
+    let base = argument(node, "e");
+    let lu = optional_argument(node, "sup").into_iter().collect();
+    let ld = optional_argument(node, "sub").into_iter().collect();
+    match base {
+        // `m:sPre` wrapping `m:sSubSup` is the usual way to write `{}_a^b X_c^d`
+        MathML::MultiScripts(post) if post.get_pre_sub_scripts().is_empty() && post.get_pre_super_scripts().is_empty() => {
+            MathMultiScript::new(
+                post.get_base().clone(),
+                lu,
+                ld,
+                post.get_super_scripts().to_vec(),
+                post.get_sub_scripts().to_vec(),
+            )
+            .into()
+        }
+        base => MathMultiScript::new(base, lu, ld, vec![], vec![]).into(),
+    }
+}
+
+fn parse_nary(node: Node, out: &mut Vec<MathML>) {
+    let symbol = property(node, "chr").unwrap_or("∫");
+    let operator: MathML = MathOperator::new(symbol).mark_large_operator().into();
+    let sub = if flag(node, "subHide") { None } else { optional_argument(node, "sub") };
+    let sup = if flag(node, "supHide") { None } else { optional_argument(node, "sup") };
+    let under_over = match property(node, "limLoc") {
+        Some(s) => s == "undOvr",
+        None => !matches!(symbol, "∫" | "∬" | "∭" | "∮" | "∯" | "∰"),
+    };
+    let limits = match (sub, sup, under_over) {
+        (None, None, _) => operator,
+        (Some(sub), None, true) => MathUnderOver::under(operator, sub).into(),
+        (None, Some(sup), true) => MathUnderOver::over(operator, sup).into(),
+        (Some(sub), Some(sup), true) => MathUnderOver::under_over(operator, sub, sup).into(),
+        (Some(sub), None, false) => MathMultiScript::sub_script(operator, sub).into(),
+        (None, Some(sup), false) => MathMultiScript::super_script(operator, sup).into(),
+        (Some(sub), Some(sup), false) => MathMultiScript::sub_super_script(operator, sub, sup).into(),
+    };
+    out.push(limits);
+    if let Some(body) = child(node, "e") {
+        out.extend(parse_children(body))
+    }
+}
+
+fn parse_delimiter(node: Node) -> MathML {
+    let open = property(node, "begChr").unwrap_or("(");
+    let close = property(node, "endChr").unwrap_or(")");
+    let separator = property(node, "sepChr").unwrap_or("|");
+    let items: Vec<MathML> = elements(node, "e").map(|e| make_row(parse_children(e))).collect();
+    let mut open_chars = open.chars();
+    let mut close_chars = close.chars();
+    if let (Some(lhs), None, Some(rhs), None) = (open_chars.next(), open_chars.next(), close_chars.next(), close_chars.next()) {
+        return MathFenced::new(items, lhs, rhs).with_separators(separator).into();
+    }
+    let mut row = vec![];
+    if !open.is_empty() {
+        row.push(MathOperator::new(open).mark_fence().mark_stretchy().into());
+    }
+    for (i, item) in items.into_iter().enumerate() {
+        if i != 0 {
+            row.push(MathOperator::new(separator).mark_separator().into());
+        }
+        row.push(item);
+    }
+    if !close.is_empty() {
+        row.push(MathOperator::new(close).mark_fence().mark_stretchy().into());
+    }
+    MathRow::new(row).into()
+}
+
+fn font_variant(script: Option<&str>, style: Option<&str>) -> FontVariant {
+    let bold = matches!(style, Some("b" | "bi"));
+    match script.unwrap_or("roman") {
+        "double-struck" => FontVariant::DoubleStruck,
+        "fraktur" if bold => FontVariant::BoldFraktur,
+        "fraktur" => FontVariant::Fraktur,
+        "script" if bold => FontVariant::BoldScript,
+        "script" => FontVariant::Script,
+        "monospace" => FontVariant::Monospace,
+        "sans-serif" => match style {
+            Some("p") => FontVariant::SansSerif,
+            Some("b") => FontVariant::BoldSansSerif,
+            Some("bi") => FontVariant::SansSerifBoldItalic,
+            _ => FontVariant::SansSerifItalic,
+        },
+        _ => match style {
+            Some("p") => FontVariant::Normal,
+            Some("b") => FontVariant::Bold,
+            Some("bi") => FontVariant::BoldItalic,
+            _ => FontVariant::Italic,
+        },
+    }
+}
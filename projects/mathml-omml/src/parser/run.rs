@@ -0,0 +1,39 @@
+use super::*;
+
+/// Split the text of a `m:r` run into numbers, identifiers and operators.
+pub(super) fn parse_run(node: Node, out: &mut Vec<MathML>) {
+    let text: String = elements(node, "t").filter_map(|t| t.text()).collect();
+    if flag(node, "nor") {
+        out.push(MathML::text(text));
+        return;
+    }
+    let style = property(node, "sty");
+    let variant = font_variant(property(node, "scr"), style);
+    let aligned =
+        node.ancestors().find(|n| is_element(n, "eqArr") || is_element(n, "m")).is_some_and(|n| is_element(&n, "eqArr"));
+    let mut start = 0;
+    while let Some(c) = text[start..].chars().next() {
+        let mut end = start + c.len_utf8();
+        match c {
+            _ if c.is_whitespace() => {}
+            '&' if aligned => out.push(MathML::Ampersand),
+            '0'..='9' | '.' if c != '.' || text[end..].starts_with(|c: char| c.is_ascii_digit()) => {
+                end = scan(&text, end, |c| c.is_ascii_digit() || c == '.');
+                out.push(MathNumber::new(&text[start..end]).into())
+            }
+            _ if c.is_alphabetic() => {
+                // plain runs hold words such as function names
+                if style == Some("p") {
+                    end = scan(&text, end, char::is_alphabetic);
+                }
+                out.push(MathIdentifier::new(&text[start..end], variant).into())
+            }
+            _ => out.push(MathOperator::new(c).into()),
+        }
+        start = end;
+    }
+}
+
+fn scan(text: &str, start: usize, predicate: fn(char) -> bool) -> usize {
+    text[start..].find(|c| !predicate(c)).map_or(text.len(), |offset| start + offset)
+}
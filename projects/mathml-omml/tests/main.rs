@@ -0,0 +1,125 @@
+use mathml_core::{helpers::pmatrix, MathML, MathMultiScript, MathOperator, MathRoot, MathRow, MathSqrt, MathUnderOver};
+use mathml_omml::{parse_omml, AsOMML, OMML_NAMESPACE};
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+fn wrap(body: &str) -> String {
+    format!(r#"<m:oMath xmlns:m="{}">{}</m:oMath>"#, OMML_NAMESPACE, body)
+}
+
+#[track_caller]
+fn assert_import(omml: &str, mathml: &str) {
+    assert_eq!(parse_omml(&wrap(omml)).unwrap().to_string(), format!("<math>{}</math>", mathml));
+}
+
+#[test]
+fn import_scripts() {
+    assert_import(
+        "<m:sSubSup><m:e><m:r><m:t>x</m:t></m:r></m:e><m:sub><m:r><m:t>i</m:t></m:r></m:sub><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSubSup>",
+        "<msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup>",
+    );
+    assert_import(
+        "<m:sPre><m:sub><m:r><m:t>6</m:t></m:r></m:sub><m:sup><m:r><m:t>14</m:t></m:r></m:sup><m:e><m:r><m:t>C</m:t></m:r></m:e></m:sPre>",
+        "<mmultiscripts><mi>C</mi><mprescripts/><mn>6</mn><mn>14</mn></mmultiscripts>",
+    );
+}
+
+#[test]
+fn import_structures() {
+    assert_import(
+        r#"<m:f><m:num><m:r><m:t>a+1</m:t></m:r></m:num><m:den><m:rad><m:radPr><m:degHide m:val="1"/></m:radPr><m:deg/><m:e><m:r><m:t>b</m:t></m:r></m:e></m:rad></m:den></m:f>"#,
+        "<mfrac><mrow><mi>a</mi><mo>+</mo><mn>1</mn></mrow><msqrt><mi>b</mi></msqrt></mfrac>",
+    );
+    assert_import(
+        r#"<m:nary><m:naryPr><m:chr m:val="∑"/></m:naryPr><m:sub><m:r><m:t>i</m:t></m:r></m:sub><m:sup><m:r><m:t>n</m:t></m:r></m:sup><m:e><m:r><m:t>i</m:t></m:r></m:e></m:nary>"#,
+        r#"<munderover><mo largeop="true">∑</mo><mi>i</mi><mi>n</mi></munderover><mi>i</mi>"#,
+    );
+    assert_import(
+        r#"<m:func><m:fName><m:r><m:rPr><m:sty m:val="p"/></m:rPr><m:t>sin</m:t></m:r></m:fName><m:e><m:r><m:t>x</m:t></m:r></m:e></m:func>"#,
+        r#"<mrow><mi mathvariant="normal">sin</mi><mspace width="0.167rem"/><mi>x</mi></mrow>"#,
+    );
+    assert_import(
+        "<m:d><m:e><m:m><m:mr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:mr></m:m></m:e></m:d>",
+        r#"<mrow><mo stretchy="true" form="prefix">(</mo><mtable><mtr><mtd><mi>a</mi></mtd><mtd><mi>b</mi></mtd></mtr></mtable><mo stretchy="true" form="postfix">)</mo></mrow>"#,
+    );
+    assert_import(
+        "<m:eqArr><m:e><m:r><m:t>x&amp;=1</m:t></m:r></m:e><m:e><m:r><m:t>y&amp;=2</m:t></m:r></m:e></m:eqArr>",
+        r#"<mtable columnalign="right left"><mtr><mtd><mi>x</mi></mtd><mtd><mrow><mo>=</mo><mn>1</mn></mrow></mtd></mtr><mtr><mtd><mi>y</mi></mtd><mtd><mrow><mo>=</mo><mn>2</mn></mrow></mtd></mtr></mtable>"#,
+    );
+}
+
+#[test]
+fn import_document() {
+    let document = format!(
+        r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:m="{}"><w:body><w:p><m:oMathPara><m:oMath><m:r><m:t>E=m</m:t></m:r><m:sSup><m:e><m:r><m:t>c</m:t></m:r></m:e><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSup></m:oMath></m:oMathPara></w:p></w:body></w:document>"#,
+        OMML_NAMESPACE
+    );
+    assert_eq!(
+        parse_omml(&document).unwrap().to_string(),
+        r#"<math display="block"><mrow><mi>E</mi><mo>=</mo><mi>m</mi><msup><mi>c</mi><mn>2</mn></msup></mrow></math>"#
+    );
+    assert!(parse_omml("<p/>").is_err());
+}
+
+#[test]
+fn export() {
+    let math: MathML = MathRoot::new(vec![
+        MathMultiScript::new(MathML::identifier("C"), vec![MathML::number(14)], vec![MathML::number(6)], vec![], vec![]).into(),
+        MathSqrt::surd(MathML::identifier("x"), MathML::number(3)).into(),
+    ])
+    .into();
+    assert_eq!(
+        math.as_omml(),
+        wrap(concat!(
+            "<m:sPre><m:sub><m:r><m:t>6</m:t></m:r></m:sub><m:sup><m:r><m:t>14</m:t></m:r></m:sup><m:e><m:r><m:t>C</m:t></m:r></m:e></m:sPre>",
+            "<m:rad><m:deg><m:r><m:t>3</m:t></m:r></m:deg><m:e><m:r><m:t>x</m:t></m:r></m:e></m:rad>"
+        ))
+    );
+    let matrix = pmatrix(vec![MathML::identifier("a"), MathML::Ampersand, MathML::identifier("b")]);
+    assert_eq!(
+        matrix.as_omml(),
+        wrap(
+            r#"<m:d><m:dPr><m:begChr m:val="("/><m:endChr m:val=")"/></m:dPr><m:e><m:m><m:mr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:mr></m:m></m:e></m:d>"#
+        )
+    );
+    // the first and the last fence are not a pair
+    let product: MathML = MathRow::new(vec![
+        MathML::operation("("),
+        MathML::identifier("a"),
+        MathML::operation(")"),
+        MathML::operation("("),
+        MathML::identifier("b"),
+        MathML::operation(")"),
+    ])
+    .into();
+    assert_eq!(
+        product.as_omml(),
+        wrap(concat!(
+            "<m:r><m:t>(</m:t></m:r><m:r><m:t>a</m:t></m:r><m:r><m:t>)</m:t></m:r>",
+            "<m:r><m:t>(</m:t></m:r><m:r><m:t>b</m:t></m:r><m:r><m:t>)</m:t></m:r>"
+        ))
+    );
+}
+
+#[test]
+fn round_trip() {
+    let sum = MathUnderOver::under_over(
+        MathOperator::new("∑").mark_large_operator().into(),
+        MathML::identifier("i"),
+        MathML::identifier("n"),
+    );
+    let math: MathML = MathRoot::new(vec![
+        sum.into(),
+        MathML::fraction(MathML::number(1), MathMultiScript::sub_super_script('x'.into(), 'i'.into(), 2.into())),
+    ])
+    .into();
+    assert_eq!(parse_omml(&math.as_omml()).unwrap(), math);
+    let aligned = wrap("<m:eqArr><m:e><m:r><m:t>x&amp;=1</m:t></m:r></m:e><m:e><m:r><m:t>y&amp;=2</m:t></m:r></m:e></m:eqArr>");
+    let math = parse_omml(&aligned).unwrap();
+    let omml = math.as_omml();
+    assert_eq!(omml.matches("<m:t>&amp;</m:t>").count(), 2);
+    assert_eq!(parse_omml(&omml).unwrap(), math);
+}
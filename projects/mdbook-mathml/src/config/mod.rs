@@ -0,0 +1,64 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// The display style of the rendered formulas.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// Display math for `$$..$$` and `\[..\]`, inline math otherwise.
+    #[default]
+    Auto,
+    /// All formulas are inline math.
+    Inline,
+    /// All formulas are display math.
+    Block,
+}
+
+/// Options of the `[preprocessor.mathml]` table in `book.toml`.
+#[derive(Clone, Debug)]
+pub struct MathConfig {
+    /// Render `$..$` and `$$..$$`.
+    pub dollars: bool,
+    /// Render `\(..\)` and `\[..\]`.
+    pub brackets: bool,
+    /// The display style of the rendered formulas.
+    pub display: DisplayMode,
+    /// Macros such as `R = "\\mathbb{R}"` or `norm = "\\lVert #1 \\rVert"`, the names are written without backslash.
+    pub macros: BTreeMap<String, String>,
+}
+
+impl Default for MathConfig {
+    fn default() -> Self {
+        Self { dollars: true, brackets: true, display: DisplayMode::Auto, macros: BTreeMap::new() }
+    }
+}
+
+impl MathConfig {
+    /// Read the options from the preprocessor context sent by mdBook, missing options keep their default.
+    pub fn from_context(context: &Value) -> Self {
+        let mut config = Self::default();
+        let table = &context["config"]["preprocessor"]["mathml"];
+        if let Some(dollars) = table["dollars"].as_bool() {
+            config.dollars = dollars;
+        }
+        if let Some(brackets) = table["brackets"].as_bool() {
+            config.brackets = brackets;
+        }
+        match table["display"].as_str() {
+            Some("inline") => config.display = DisplayMode::Inline,
+            Some("block") => config.display = DisplayMode::Block,
+            Some("auto") | None => {}
+            Some(other) => eprintln!("mdbook-mathml: unknown display mode `{}`, expect `auto`, `inline` or `block`", other),
+        }
+        if let Some(macros) = table["macros"].as_object() {
+            for (name, body) in macros {
+                match body.as_str() {
+                    Some(body) => {
+                        config.macros.insert(name.trim_start_matches('\\').to_string(), body.to_string());
+                    }
+                    None => eprintln!("mdbook-mathml: macro `{}` must be a string", name),
+                }
+            }
+        }
+        config
+    }
+}
@@ -0,0 +1,35 @@
+use mdbook_mathml::MathPreprocessor;
+use std::{
+    io::{Read, Write},
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    // `mdbook-mathml supports <renderer>`, MathML only makes sense in html
+    if let [command, renderer] = arguments.as_slice() {
+        if command == "supports" {
+            return if renderer == "html" { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+        }
+    }
+    let mut input = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("mdbook-mathml: {}", e);
+        return ExitCode::FAILURE;
+    }
+    match MathPreprocessor::run(&input) {
+        Ok((book, diagnostics)) => {
+            for diagnostic in diagnostics {
+                eprintln!("mdbook-mathml: {}", diagnostic);
+            }
+            match std::io::stdout().write_all(book.as_bytes()) {
+                Ok(_) => ExitCode::SUCCESS,
+                Err(_) => ExitCode::FAILURE,
+            }
+        }
+        Err(e) => {
+            eprintln!("mdbook-mathml: invalid input from mdbook, {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
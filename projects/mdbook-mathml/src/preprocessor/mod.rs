@@ -0,0 +1,109 @@
+use crate::{DisplayMode, MathConfig};
+use mathml_latex::{find_formulas, LaTeXEngine, LaTeXFormula};
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+
+/// A formula which failed to render, kept as written in the chapter.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// The source path of the chapter.
+    pub chapter: String,
+    /// The line of the formula, starting from 1.
+    pub line: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+/// Renders the formulas of every chapter, all chapters share one [`LaTeXEngine`].
+#[derive(Debug)]
+pub struct MathPreprocessor {
+    config: MathConfig,
+    context: LaTeXEngine,
+}
+
+impl MathPreprocessor {
+    /// Create a preprocessor with the given options.
+    pub fn new(config: MathConfig) -> Self {
+        let context = LaTeXEngine::builtin();
+        for (name, body) in &config.macros {
+            context.define_macro(name, body);
+        }
+        Self { config, context }
+    }
+    /// Run on the `[context, book]` JSON sent by mdBook, returns the book JSON to send back.
+    pub fn run(input: &str) -> Result<(String, Vec<Diagnostic>), serde_json::Error> {
+        let (context, mut book): (Value, Value) = serde_json::from_str(input)?;
+        let preprocessor = Self::new(MathConfig::from_context(&context));
+        let diagnostics = preprocessor.render_book(&mut book);
+        Ok((serde_json::to_string(&book)?, diagnostics))
+    }
+    /// Render the chapters of the book in place.
+    pub fn render_book(&self, book: &mut Value) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        if let Some(sections) = book["sections"].as_array_mut() {
+            for item in sections {
+                self.render_item(item, &mut diagnostics);
+            }
+        }
+        diagnostics
+    }
+    fn render_item(&self, item: &mut Value, diagnostics: &mut Vec<Diagnostic>) {
+        // `Separator` and `PartTitle` have no content
+        let chapter = match item.get_mut("Chapter") {
+            Some(chapter) => chapter,
+            None => return,
+        };
+        let path = match (&chapter["source_path"], &chapter["path"]) {
+            (Value::String(path), _) | (_, Value::String(path)) => path.clone(),
+            _ => chapter["name"].as_str().unwrap_or_default().to_string(),
+        };
+        if let Some(content) = chapter["content"].as_str() {
+            let (content, errors) = self.render_chapter(content);
+            diagnostics.extend(errors.into_iter().map(|(line, message)| Diagnostic { chapter: path.clone(), line, message }));
+            chapter["content"] = Value::String(content);
+        }
+        if let Some(items) = chapter["sub_items"].as_array_mut() {
+            for item in items {
+                self.render_item(item, diagnostics);
+            }
+        }
+    }
+    /// Render the formulas of a chapter, returns the lines and messages of the formulas which failed.
+    pub fn render_chapter(&self, content: &str) -> (String, Vec<(usize, String)>) {
+        let mut output = String::with_capacity(content.len());
+        let mut errors = vec![];
+        let mut last = 0;
+        for formula in find_formulas(content) {
+            let dollars = content[formula.range.start..].starts_with('$');
+            if (dollars && !self.config.dollars) || (!dollars && !self.config.brackets) {
+                continue;
+            }
+            output.push_str(&content[last..formula.range.start]);
+            match self.render_formula(&formula) {
+                Ok(mathml) => output.push_str(&mathml),
+                Err(message) => {
+                    errors.push((content[..formula.range.start].matches('\n').count() + 1, message));
+                    output.push_str(&content[formula.range.clone()]);
+                }
+            }
+            last = formula.range.end;
+        }
+        output.push_str(&content[last..]);
+        (output, errors)
+    }
+    fn render_formula(&self, formula: &LaTeXFormula) -> Result<String, String> {
+        let display = match self.config.display {
+            DisplayMode::Auto => formula.display,
+            DisplayMode::Inline => false,
+            DisplayMode::Block => true,
+        };
+        let formula = LaTeXFormula { display, ..formula.clone() };
+        formula.as_mathml(&self.context).map(|mathml| mathml.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.chapter, self.line, self.message)
+    }
+}
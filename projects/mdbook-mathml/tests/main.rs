@@ -0,0 +1,44 @@
+use mdbook_mathml::{MathConfig, MathPreprocessor};
+use serde_json::json;
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+#[test]
+fn render_book() {
+    let context = json!({
+        "root": "/book",
+        "config": {"preprocessor": {"mathml": {"brackets": false, "macros": {"\\ab": "a + b"}}}},
+        "renderer": "html",
+        "mdbook_version": "0.4.40"
+    });
+    let chapter = |name: &str, content: &str, sub_items| json!({"Chapter": {"name": name, "content": content, "number": null, "sub_items": sub_items, "path": name, "source_path": name, "parent_names": []}});
+    let book = json!({
+        "sections": [
+            chapter("intro.md", "Solve $\\ab$ and \\(c\\).", json!([])),
+            {"Separator": null},
+            chapter("a.md", "# A", json!([chapter("a/b.md", "first\n\n$a + {b$", json!([]))]))
+        ],
+        "__non_exhaustive": null
+    });
+    let (output, diagnostics) = MathPreprocessor::run(&json!([context, book]).to_string()).unwrap();
+    let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        output["sections"][0]["Chapter"]["content"],
+        r#"Solve <math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math> and \(c\)."#
+    );
+    assert_eq!(output["sections"][2]["Chapter"]["sub_items"][0]["Chapter"]["content"], "first\n\n$a + {b$");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].to_string().starts_with("a/b.md:3: invalid formula"));
+}
+
+#[test]
+fn display_mode() {
+    let context = json!({"config": {"preprocessor": {"mathml": {"display": "block"}}}});
+    let config = MathConfig::from_context(&context);
+    let (content, errors) = MathPreprocessor::new(config).render_chapter("$x$");
+    assert_eq!(content, r#"<math display="block"><mi>x</mi></math>"#);
+    assert!(errors.is_empty());
+}
@@ -0,0 +1,16 @@
+use serde_json::Value;
+
+/// The plain text of a metadata value, `\mathbb{R}` is read by pandoc as a raw tex inline.
+pub(super) fn stringify(value: &Value) -> String {
+    match value {
+        Value::Array(items) => items.iter().map(stringify).collect(),
+        Value::Object(node) => match node.get("t").and_then(Value::as_str) {
+            Some("MetaString" | "Str") => node["c"].as_str().unwrap_or_default().to_string(),
+            Some("Space" | "SoftBreak" | "LineBreak") => " ".to_string(),
+            // `[format, text]` or `[attributes, text]`
+            Some("Math" | "RawInline" | "Code") => node["c"][1].as_str().unwrap_or_default().to_string(),
+            _ => node.get("c").map(stringify).unwrap_or_default(),
+        },
+        _ => String::new(),
+    }
+}
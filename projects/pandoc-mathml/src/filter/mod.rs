@@ -0,0 +1,81 @@
+use mathml_core::MathRoot;
+use mathml_latex::LaTeXEngine;
+use serde_json::{json, Value};
+
+mod meta;
+
+/// Writers which can show MathML, as passed to the filter by pandoc.
+const HTML_FORMATS: &[&str] =
+    &["html", "html4", "html5", "chunkedhtml", "epub", "epub2", "epub3", "revealjs", "slidy", "slideous", "dzslides", "s5"];
+
+/// Replaces the `Math` inlines of a pandoc document with MathML, all formulas share one [`LaTeXEngine`].
+#[derive(Debug)]
+pub struct MathFilter {
+    context: LaTeXEngine,
+}
+
+impl Default for MathFilter {
+    fn default() -> Self {
+        Self { context: LaTeXEngine::builtin() }
+    }
+}
+
+impl MathFilter {
+    /// Whether the output format can show MathML.
+    pub fn supports(format: &str) -> bool {
+        HTML_FORMATS.contains(&format)
+    }
+    /// Define a macro such as `R` for `\mathbb{R}`, the body may use the parameters `#1` to `#9`.
+    pub fn with_macro(self, name: &str, body: &str) -> Self {
+        self.context.define_macro(name, body);
+        self
+    }
+    /// Read the macros from the `macros` field of the document metadata.
+    pub fn with_meta(mut self, meta: &Value) -> Self {
+        match &meta["macros"] {
+            Value::Null => {}
+            Value::Object(map) if map.get("t").and_then(Value::as_str) == Some("MetaMap") => {
+                for (name, body) in map["c"].as_object().into_iter().flatten() {
+                    self = self.with_macro(name, meta::stringify(body).trim());
+                }
+            }
+            _ => eprintln!("pandoc-mathml: `macros` must be a map from names to formulas"),
+        }
+        self
+    }
+    /// Run on the JSON document sent by pandoc, returns the document to send back and the formulas which failed.
+    pub fn run(input: &str) -> Result<(String, Vec<String>), serde_json::Error> {
+        let mut document: Value = serde_json::from_str(input)?;
+        let filter = Self::default().with_meta(&document["meta"]);
+        let errors = filter.filter_document(&mut document);
+        Ok((serde_json::to_string(&document)?, errors))
+    }
+    /// Replace the formulas of the document in place, formulas which fail are kept as math.
+    pub fn filter_document(&self, document: &mut Value) -> Vec<String> {
+        let mut errors = vec![];
+        self.filter_value(document, &mut errors);
+        errors
+    }
+    fn filter_value(&self, value: &mut Value, errors: &mut Vec<String>) {
+        match value {
+            Value::Array(items) => items.iter_mut().for_each(|item| self.filter_value(item, errors)),
+            Value::Object(node) if node.get("t").and_then(Value::as_str) == Some("Math") => {
+                // `[{"t": "InlineMath"}, source]`
+                let display = node["c"][0]["t"] == "DisplayMath";
+                let source = node["c"][1].as_str().unwrap_or_default();
+                match self.render(source, display) {
+                    Ok(mathml) => *value = json!({"t": "RawInline", "c": ["html", mathml]}),
+                    Err(e) => errors.push(e),
+                }
+            }
+            Value::Object(node) => node.values_mut().for_each(|item| self.filter_value(item, errors)),
+            _ => {}
+        }
+    }
+    fn render(&self, source: &str, display: bool) -> Result<String, String> {
+        match self.context.render(source) {
+            Ok(node) => Ok(MathRoot::new(vec![node]).with_display_style(display).to_string()),
+            Err(e) => Err(format!("invalid formula `{}`, {}", source, e)),
+        }
+    }
+}
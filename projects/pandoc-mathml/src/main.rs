@@ -0,0 +1,33 @@
+use pandoc_mathml::MathFilter;
+use std::{
+    io::{Read, Write},
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let mut input = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("pandoc-mathml: {}", e);
+        return ExitCode::FAILURE;
+    }
+    // pandoc passes the output format as the first argument
+    let output = match std::env::args().nth(1) {
+        Some(format) if !MathFilter::supports(&format) => Ok((input, vec![])),
+        _ => MathFilter::run(&input),
+    };
+    match output {
+        Ok((document, errors)) => {
+            for error in errors {
+                eprintln!("pandoc-mathml: {}", error);
+            }
+            match std::io::stdout().write_all(document.as_bytes()) {
+                Ok(_) => ExitCode::SUCCESS,
+                Err(_) => ExitCode::FAILURE,
+            }
+        }
+        Err(e) => {
+            eprintln!("pandoc-mathml: invalid input from pandoc, {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use pandoc_mathml::MathFilter;
+use serde_json::{json, Value};
+
+#[test]
+fn ready() {
+    println!("it works!")
+}
+
+#[test]
+fn filter_document() {
+    let math = |kind: &str, source: &str| json!({"t": "Math", "c": [{"t": kind}, source]});
+    let document = json!({
+        "pandoc-api-version": [1, 23, 1],
+        "meta": {
+            "macros": {"t": "MetaMap", "c": {
+                "ab": {"t": "MetaInlines", "c": [{"t": "Str", "c": "a"}, {"t": "Space"}, {"t": "Str", "c": "+"}, {"t": "Space"}, {"t": "Str", "c": "b"}]}
+            }}
+        },
+        "blocks": [
+            {"t": "Para", "c": [{"t": "Str", "c": "Solve"}, {"t": "Space"}, math("InlineMath", r"\ab")]},
+            {"t": "BulletList", "c": [[{"t": "Plain", "c": [math("DisplayMath", "x"), math("InlineMath", "a + {b")]}]]}
+        ]
+    });
+    let (output, errors) = MathFilter::run(&document.to_string()).unwrap();
+    let output: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        output["blocks"][0]["c"][2],
+        json!({"t": "RawInline", "c": ["html", r#"<math display="inline"><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow></math>"#]})
+    );
+    let items = &output["blocks"][1]["c"][0][0]["c"];
+    assert_eq!(items[0]["c"][1], r#"<math display="block"><mi>x</mi></math>"#);
+    assert_eq!(items[1], math("InlineMath", "a + {b"));
+    assert_eq!(errors.len(), 1);
+    assert!(MathFilter::supports("epub3") && !MathFilter::supports("latex"));
+}